@@ -0,0 +1,37 @@
+//!
+//! With the `embed` feature, generates a table of every puzzle input for `input.rs` to compile in
+//!
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut table = String::from("pub static INPUTS: &[(&str, &str)] = &[\n");
+    if env::var_os("CARGO_FEATURE_EMBED").is_some() {
+        let input = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).parent().unwrap().join("input");
+        println!("cargo:rerun-if-changed={}", input.display());
+        let mut paths = vec![];
+        for year in fs::read_dir(&input).unwrap() {
+            let year = year.unwrap().path();
+            if !year.is_dir() {
+                continue;
+            }
+            for day in fs::read_dir(&year).unwrap() {
+                let day = day.unwrap().path();
+                if day.is_file() {
+                    paths.push(day);
+                }
+            }
+        }
+        paths.sort();
+        for path in paths {
+            let key = path.strip_prefix(&input).unwrap().to_str().unwrap().replace('\\', "/");
+            writeln!(table, "    ({:?}, include_str!({:?})),", key, path.to_str().unwrap()).unwrap();
+        }
+    }
+    table.push_str("];\n");
+    fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("embedded.rs"), table).unwrap();
+}
@@ -0,0 +1,276 @@
+//!
+//! Axis-aligned boxes and exact unions of them
+//!
+//! Like `Interval`, boxes are half-open along every axis. `AaBoxSet` keeps its boxes disjoint by
+//! splitting them, and `CompressedBoxSet` works on a grid compressed to the coordinates of a fixed
+//! set of boxes, which is faster when there are few boxes but they are large.
+//!
+
+use num::Num;
+
+use crate::geom::Vector;
+use crate::interval::Interval;
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub struct AaBox<T, const N: usize> {
+    pub min: Vector<T, N>,
+    pub max: Vector<T, N>,
+}
+
+impl<T: Num + Ord + Copy, const N: usize> AaBox<T, N> {
+    pub fn new(min: Vector<T, N>, max: Vector<T, N>) -> AaBox<T, N> {
+        AaBox { min, max }
+    }
+
+    pub fn inclusive(min: Vector<T, N>, max: Vector<T, N>) -> AaBox<T, N> {
+        AaBox { min, max: max.map(|c| c + T::one()) }
+    }
+
+    pub fn from_intervals(intervals: [Interval<T>; N]) -> AaBox<T, N> {
+        AaBox {
+            min: Vector(intervals.map(|i| i.lo)),
+            max: Vector(intervals.map(|i| i.hi)),
+        }
+    }
+
+    pub fn axis(&self, axis: usize) -> Interval<T> {
+        Interval::new(self.min[axis], self.max[axis])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|axis| self.axis(axis).is_empty())
+    }
+
+    pub fn volume(&self) -> T {
+        (0..N).fold(T::one(), |acc, axis| acc * self.axis(axis).len())
+    }
+
+    pub fn contains(&self, p: Vector<T, N>) -> bool {
+        (0..N).all(|axis| self.axis(axis).contains(p[axis]))
+    }
+
+    pub fn intersection(&self, other: &AaBox<T, N>) -> Option<AaBox<T, N>> {
+        let res = AaBox { min: self.min.component_max(other.min), max: self.max.component_min(other.max) };
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    ///
+    /// Disjoint boxes covering the part of `self` outside `other`, at most 2N of them
+    ///
+    pub fn difference(&self, other: &AaBox<T, N>) -> Vec<AaBox<T, N>> {
+        let overlap = match self.intersection(other) {
+            None => return vec![*self],
+            Some(overlap) => overlap,
+        };
+        // peel off the slabs on either side of the overlap one axis at a time
+        let mut res = vec![];
+        let mut rest = *self;
+        for axis in 0..N {
+            for slab in rest.axis(axis).difference(&overlap.axis(axis)) {
+                let mut b = rest;
+                b.min[axis] = slab.lo;
+                b.max[axis] = slab.hi;
+                res.push(b);
+            }
+            rest.min[axis] = overlap.min[axis];
+            rest.max[axis] = overlap.max[axis];
+        }
+        res
+    }
+}
+
+///
+/// A union of boxes, kept as disjoint boxes
+///
+#[derive(Eq, PartialEq, Clone, Default, Debug)]
+pub struct AaBoxSet<T, const N: usize> {
+    boxes: Vec<AaBox<T, N>>,
+}
+
+impl<T: Num + Ord + Copy, const N: usize> AaBoxSet<T, N> {
+    pub fn new() -> AaBoxSet<T, N> {
+        AaBoxSet { boxes: vec![] }
+    }
+
+    pub fn boxes(&self) -> &[AaBox<T, N>] {
+        &self.boxes
+    }
+
+    pub fn contains(&self, p: Vector<T, N>) -> bool {
+        self.boxes.iter().any(|b| b.contains(p))
+    }
+
+    pub fn insert(&mut self, b: AaBox<T, N>) {
+        if b.is_empty() {
+            return;
+        }
+        let mut added = vec![b];
+        for existing in self.boxes.iter() {
+            subtract(&mut added, existing);
+        }
+        self.boxes.extend(added);
+    }
+
+    pub fn remove(&mut self, b: AaBox<T, N>) {
+        subtract(&mut self.boxes, &b);
+    }
+
+    pub fn volume(&self) -> T {
+        self.boxes.iter().fold(T::zero(), |acc, b| acc + b.volume())
+    }
+}
+
+fn subtract<T: Num + Ord + Copy, const N: usize>(froms: &mut Vec<AaBox<T, N>>, b: &AaBox<T, N>) {
+    for i in (0..froms.len()).rev() {
+        if froms[i].intersection(b).is_some() {
+            let from = froms.swap_remove(i);
+            froms.extend(from.difference(b));
+        }
+    }
+}
+
+///
+/// A union of boxes drawn on a grid of cells, whose edges are the coordinates of the boxes
+/// given up front
+///
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct CompressedBoxSet<T, const N: usize> {
+    coords: [Vec<T>; N],
+    cells: Vec<bool>,
+}
+
+impl<T: Num + Ord + Copy, const N: usize> CompressedBoxSet<T, N> {
+    ///
+    /// An empty set that can only hold unions of the given boxes
+    ///
+    pub fn new<'a, I>(boxes: I) -> CompressedBoxSet<T, N>
+        where
+            I: IntoIterator<Item=&'a AaBox<T, N>>,
+            T: 'a,
+    {
+        let mut coords: [Vec<T>; N] = std::array::from_fn(|_| vec![]);
+        for b in boxes {
+            for (axis, coords) in coords.iter_mut().enumerate() {
+                coords.push(b.min[axis]);
+                coords.push(b.max[axis]);
+            }
+        }
+        for coords in coords.iter_mut() {
+            coords.sort_unstable();
+            coords.dedup();
+        }
+        let num_cells = coords.iter().map(|c| c.len().saturating_sub(1)).product();
+        CompressedBoxSet { coords, cells: vec![false; num_cells] }
+    }
+
+    ///
+    /// Panics if `b` does not line up with the grid
+    ///
+    pub fn insert(&mut self, b: &AaBox<T, N>) {
+        self.fill(b, true);
+    }
+
+    ///
+    /// Panics if `b` does not line up with the grid
+    ///
+    pub fn remove(&mut self, b: &AaBox<T, N>) {
+        self.fill(b, false);
+    }
+
+    pub fn volume(&self) -> T {
+        let mut res = T::zero();
+        for (i, _) in self.cells.iter().enumerate().filter(|(_, &c)| c) {
+            let mut i = i;
+            let mut volume = T::one();
+            for coords in self.coords.iter().rev() {
+                let n = coords.len() - 1;
+                volume = volume * (coords[i % n + 1] - coords[i % n]);
+                i /= n;
+            }
+            res = res + volume;
+        }
+        res
+    }
+
+    fn fill(&mut self, b: &AaBox<T, N>, value: bool) {
+        if b.is_empty() {
+            return;
+        }
+        let ranges: [(usize, usize); N] = std::array::from_fn(|axis| {
+            let coords = &self.coords[axis];
+            let lo = coords.binary_search(&b.min[axis]).expect("box not on grid");
+            let hi = coords.binary_search(&b.max[axis]).expect("box not on grid");
+            (lo, hi)
+        });
+        let mut index = ranges.map(|(lo, _)| lo);
+        loop {
+            let cell = index.iter().zip(self.coords.iter())
+                .fold(0, |acc, (&i, coords)| acc * (coords.len() - 1) + i);
+            self.cells[cell] = value;
+
+            // advance like an odometer, last axis fastest
+            let mut axis = N;
+            loop {
+                if axis == 0 {
+                    return;
+                }
+                axis -= 1;
+                index[axis] += 1;
+                if index[axis] < ranges[axis].1 {
+                    break;
+                }
+                index[axis] = ranges[axis].0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geom::Vector3;
+
+    use super::*;
+
+    fn cube(min: [i64; 3], max: [i64; 3]) -> AaBox<i64, 3> {
+        AaBox::inclusive(Vector(min), Vector(max))
+    }
+
+    #[test]
+    fn test_box() {
+        let a = cube([0, 0, 0], [2, 2, 2]);
+        let b = cube([1, 1, 1], [3, 3, 3]);
+        assert_eq!(27, a.volume());
+        assert_eq!(Some(cube([1, 1, 1], [2, 2, 2])), a.intersection(&b));
+        assert!(a.contains(Vector3::zero()) && !a.contains(Vector([3, 0, 0])));
+
+        let diff = a.difference(&b);
+        assert_eq!(19, diff.iter().map(|b| b.volume()).sum::<i64>());
+        assert!(diff.iter().all(|d| d.intersection(&b).is_none()));
+        assert_eq!(vec![a], a.difference(&cube([5, 5, 5], [6, 6, 6])));
+    }
+
+    #[test]
+    fn test_sets() {
+        let steps = [
+            (true, cube([10, 10, 10], [12, 12, 12])),
+            (true, cube([11, 11, 11], [13, 13, 13])),
+            (false, cube([9, 9, 9], [11, 11, 11])),
+            (true, cube([10, 10, 10], [10, 10, 10])),
+        ];
+        let mut set = AaBoxSet::new();
+        let mut compressed = CompressedBoxSet::new(steps.iter().map(|(_, b)| b));
+        let mut volumes = vec![];
+        for (on, b) in steps.iter() {
+            if *on {
+                set.insert(*b);
+                compressed.insert(b);
+            } else {
+                set.remove(*b);
+                compressed.remove(b);
+            }
+            volumes.push((set.volume(), compressed.volume()));
+        }
+        assert_eq!(vec![(27, 27), (46, 46), (38, 38), (39, 39)], volumes);
+        assert!(set.contains(Vector([10, 10, 10])) && !set.contains(Vector([10, 10, 11])));
+    }
+}
@@ -0,0 +1,224 @@
+//!
+//! Timing the parse, part 1 and part 2 phases of a solution
+//!
+//! Each phase runs `warmup` times untimed and then `iterations` times timed, and is summarised by
+//! its median and minimum. Results can be appended to a CSV history, one row per phase, so that
+//! runs from different commits can be compared.
+//!
+
+use std::collections::HashMap;
+use std::fs;
+use std::hint::black_box;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::solution::Solution;
+
+const HEADER: &str = "run,commit,year,day,phase,median_ns,min_ns";
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Config {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { warmup: 1, iterations: 10 }
+    }
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Timing {
+    pub median: Duration,
+    pub min: Duration,
+}
+
+///
+/// The timing of each phase, by name
+///
+pub type Timings = Vec<(&'static str, Timing)>;
+
+impl Timing {
+    ///
+    /// Panics if there are no samples
+    ///
+    pub fn from_samples(mut samples: Vec<Duration>) -> Timing {
+        samples.sort_unstable();
+        Timing { median: samples[samples.len() / 2], min: samples[0] }
+    }
+}
+
+///
+/// Time parsing and then each of `parts`
+///
+pub(crate) fn run<S: Solution>(input: &str, parts: &[u8], config: &Config) -> Result<Timings> {
+    if config.iterations == 0 {
+        bail!("need at least one iteration");
+    }
+    let mut samples = vec![vec![]; parts.len() + 1];
+    for i in 0..config.warmup + config.iterations {
+        let mut elapsed = vec![];
+        let start = Instant::now();
+        let parsed = black_box(S::parse(input).context("parse")?);
+        elapsed.push(start.elapsed());
+        for &part in parts {
+            let start = Instant::now();
+            match part {
+                1 => { black_box(S::part1(&parsed).context("part 1")?); }
+                2 => { black_box(S::part2(&parsed).context("part 2")?); }
+                _ => bail!("no part {}", part),
+            }
+            elapsed.push(start.elapsed());
+        }
+        if i >= config.warmup {
+            for (samples, elapsed) in samples.iter_mut().zip(elapsed) {
+                samples.push(elapsed);
+            }
+        }
+    }
+    let phases = ["parse"].into_iter().chain(parts.iter().map(|&part| phase(part)));
+    Ok(phases.zip(samples.into_iter().map(Timing::from_samples)).collect())
+}
+
+fn phase(part: u8) -> &'static str {
+    if part == 1 { "part1" } else { "part2" }
+}
+
+///
+/// One row of the history
+///
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Record {
+    pub run: u64,
+    pub commit: String,
+    pub year: u16,
+    pub day: u8,
+    pub phase: String,
+    pub timing: Timing,
+}
+
+impl Record {
+    fn to_csv(&self) -> String {
+        format!("{},{},{},{},{},{},{}", self.run, self.commit, self.year, self.day, self.phase,
+                self.timing.median.as_nanos(), self.timing.min.as_nanos())
+    }
+
+    fn from_csv(line: &str) -> Result<Record> {
+        let fields = line.split(',').collect::<Vec<_>>();
+        if fields.len() != 7 {
+            bail!("expected 7 fields in {:?}", line);
+        }
+        let nanos = |s: &str| s.parse().map(Duration::from_nanos);
+        Ok(Record {
+            run: fields[0].parse()?,
+            commit: fields[1].to_string(),
+            year: fields[2].parse()?,
+            day: fields[3].parse()?,
+            phase: fields[4].to_string(),
+            timing: Timing { median: nanos(fields[5])?, min: nanos(fields[6])? },
+        })
+    }
+}
+
+///
+/// Read the history at `path`, which is empty if the file does not exist yet
+///
+pub fn load_history<P: AsRef<Path>>(path: P) -> Result<Vec<Record>> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let s = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    s.lines()
+        .skip(1)
+        .enumerate()
+        .map(|(i, line)| Record::from_csv(line)
+            .with_context(|| format!("{} line {}", path.display(), i + 2)))
+        .collect()
+}
+
+pub fn append_history<P: AsRef<Path>>(path: P, records: &[Record]) -> Result<()> {
+    let path = path.as_ref();
+    let is_new = !path.exists();
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for record in records {
+        writeln!(file, "{}", record.to_csv())?;
+    }
+    Ok(())
+}
+
+///
+/// The id for a new run, after every run in the history so runs never share one
+///
+pub fn next_run(history: &[Record]) -> u64 {
+    history.iter().map(|r| r.run + 1).max().unwrap_or(1)
+}
+
+///
+/// The timing of each (year, day, phase) in the last saved run, the one with the highest id
+///
+pub fn last_timings(history: &[Record]) -> HashMap<(u16, u8, String), Timing> {
+    let last_run = history.iter().map(|r| r.run).max();
+    history.iter()
+        .filter(|r| Some(r.run) == last_run)
+        .map(|r| ((r.year, r.day, r.phase.clone()), r.timing))
+        .collect()
+}
+
+///
+/// The short hash of the checked out commit, to tell runs apart in the history
+///
+pub fn commit() -> Result<String> {
+    let out = Command::new("git").args(["rev-parse", "--short", "HEAD"]).output()?;
+    if !out.status.success() {
+        bail!("git rev-parse failed");
+    }
+    Ok(String::from_utf8(out.stdout)?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() -> Result<()> {
+        let ms = Duration::from_millis;
+        let timing = Timing::from_samples(vec![ms(5), ms(1), ms(3), ms(9)]);
+        assert_eq!(Timing { median: ms(5), min: ms(1) }, timing);
+
+        let record = |run, median| Record {
+            run,
+            commit: "abc1234".to_string(),
+            year: 2019,
+            day: 16,
+            phase: "part2".to_string(),
+            timing: Timing { median: ms(median), min: ms(1) },
+        };
+        let path = std::env::temp_dir().join(format!("aoc-bench-{}.csv", std::process::id()));
+        append_history(&path, &[record(1, 30)])?;
+        append_history(&path, &[record(2, 20)])?;
+        let history = load_history(&path)?;
+        fs::remove_file(&path)?;
+
+        assert_eq!(vec![record(1, 30), record(2, 20)], history);
+        assert_eq!(Some(&record(2, 20).timing), last_timings(&history).get(&(2019, 16, "part2".to_string())));
+
+        // only the last run counts, even when it isn't saved last or doesn't time every phase
+        let other_day = Record { day: 17, ..record(1, 40) };
+        let last = last_timings(&[record(3, 10), record(2, 20), other_day]);
+        assert_eq!(vec![(&(2019, 16, "part2".to_string()), &record(3, 10).timing)], last.iter().collect::<Vec<_>>());
+        assert_eq!(4, next_run(&[record(3, 10), record(2, 20)]));
+        assert_eq!(1, next_run(&[]));
+        assert!(Record::from_csv("1,abc,2019").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+//!
+//! Orderings and selections of a list of items
+//!
+//! ```
+//! use aoc::combinatorics::{self, Permutations};
+//!
+//! let mut perms = Permutations::new(vec![1, 2, 3]);
+//! let mut count = 0;
+//! while let Some(perm) = perms.next_perm() {
+//!     assert_eq!(6, perm.iter().sum::<i32>());
+//!     count += 1;
+//! }
+//! assert_eq!(6, count);
+//!
+//! assert_eq!(vec![vec![1, 2], vec![1, 3], vec![2, 3]], combinatorics::combinations(&[1, 2, 3], 2).collect::<Vec<_>>());
+//! assert_eq!(8, combinatorics::subsets(&[1, 2, 3]).count());
+//! assert_eq!(vec![0, 1, 0, 2, 0, 1, 0], combinatorics::gray_code(3).collect::<Vec<_>>());
+//! assert_eq!(3, combinatorics::multiset_permutations(vec!['a', 'a', 'b']).count());
+//! ```
+//!
+
+///
+/// Every ordering of some items by Heap's algorithm, each one swap away from the last
+///
+/// The items are permuted in place and lent out rather than cloned, so this isn't an `Iterator`.
+///
+pub struct Permutations<T> {
+    items: Vec<T>,
+    counters: Vec<usize>,
+    i: usize,
+    started: bool,
+}
+
+impl<T> Permutations<T> {
+    pub fn new(items: Vec<T>) -> Permutations<T> {
+        Permutations { counters: vec![0; items.len()], items, i: 0, started: false }
+    }
+
+    pub fn next_perm(&mut self) -> Option<&[T]> {
+        if !self.started {
+            self.started = true;
+            return Some(&self.items);
+        }
+        while self.i < self.items.len() {
+            if self.counters[self.i] < self.i {
+                let j = if self.i.is_multiple_of(2) { 0 } else { self.counters[self.i] };
+                self.items.swap(j, self.i);
+                self.counters[self.i] += 1;
+                self.i = 0;
+                return Some(&self.items);
+            }
+            self.counters[self.i] = 0;
+            self.i += 1;
+        }
+        None
+    }
+}
+
+///
+/// The ways to pick `k` of `items` keeping their order, in lexicographic order of position
+///
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations { items, idx: (0..k).collect(), done: k > items.len() }
+}
+
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<'a, T: Clone> Iterator for Combinations<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let res = self.idx.iter().map(|&i| self.items[i].clone()).collect();
+
+        // bump the last index that can move, and reset the ones after it to follow on
+        let (n, k) = (self.items.len(), self.idx.len());
+        match (0..k).rev().find(|&i| self.idx[i] < n - k + i) {
+            Some(i) => {
+                self.idx[i] += 1;
+                for j in i + 1..k {
+                    self.idx[j] = self.idx[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(res)
+    }
+}
+
+///
+/// Every subset of up to 63 items, keeping their order, from the empty set to all of them
+///
+pub fn subsets<T: Clone>(items: &[T]) -> impl Iterator<Item=Vec<T>> + '_ {
+    assert!(items.len() < 64, "too many items for the subsets of them");
+    (0..1_u64 << items.len()).map(move |mask| {
+        items.iter().enumerate()
+            .filter(|&(i, _)| mask >> i & 1 == 1)
+            .map(|(_, item)| item.clone())
+            .collect()
+    })
+}
+
+///
+/// The position to toggle at each step to visit all `2^n` subsets of `n` items once each, from
+/// whichever subset is first
+///
+pub fn gray_code(n: u32) -> impl Iterator<Item=usize> {
+    assert!(n < 64, "too many items for a gray code");
+    (1..1_u64 << n).map(|step| step.trailing_zeros() as usize)
+}
+
+///
+/// Every distinct ordering of items that may repeat, in lexicographic order
+///
+pub fn multiset_permutations<T: Ord + Clone>(mut items: Vec<T>) -> impl Iterator<Item=Vec<T>> {
+    items.sort();
+    let mut next = Some(items);
+    std::iter::from_fn(move || {
+        let cur = next.take()?;
+        let mut items = cur.clone();
+        // the next ordering raises the rightmost item that has a larger one after it
+        if let Some(i) = (1..items.len()).rev().find(|&i| items[i - 1] < items[i]) {
+            let j = (i..items.len()).rev().find(|&j| items[i - 1] < items[j]).unwrap();
+            items.swap(i - 1, j);
+            items[i..].reverse();
+            next = Some(items);
+        }
+        Some(cur)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_permutations() {
+        let mut perms = Permutations::new(vec![1, 2, 3]);
+        assert_eq!(Some(&[1, 2, 3][..]), perms.next_perm());
+        assert_eq!(Some(&[2, 1, 3][..]), perms.next_perm());
+        assert_eq!(Some(&[3, 1, 2][..]), perms.next_perm());
+        assert_eq!(Some(&[1, 3, 2][..]), perms.next_perm());
+        assert_eq!(Some(&[2, 3, 1][..]), perms.next_perm());
+        assert_eq!(Some(&[3, 2, 1][..]), perms.next_perm());
+        assert_eq!(None, perms.next_perm());
+        assert_eq!(None, perms.next_perm());
+
+        let mut seen = HashSet::new();
+        let mut perms = Permutations::new((0..6).collect());
+        while let Some(perm) = perms.next_perm() {
+            assert!(seen.insert(perm.to_vec()));
+        }
+        assert_eq!(720, seen.len());
+
+        let mut perms = Permutations::<u8>::new(vec![]);
+        assert_eq!(Some(&[][..]), perms.next_perm());
+        assert_eq!(None, perms.next_perm());
+    }
+
+    #[test]
+    fn test_combinations() {
+        let combs = combinations(&['a', 'b', 'c', 'd'], 2).collect::<Vec<_>>();
+        assert_eq!(vec![vec!['a', 'b'], vec!['a', 'c'], vec!['a', 'd'], vec!['b', 'c'], vec!['b', 'd'], vec!['c', 'd']], combs);
+        assert_eq!(vec![Vec::<u8>::new()], combinations(&[1, 2], 0).collect::<Vec<_>>());
+        assert_eq!(0, combinations(&[1, 2], 3).count());
+        assert_eq!(252, combinations(&[0; 10], 5).count());
+
+        let subsets = subsets(&[1, 2, 3]).collect::<Vec<_>>();
+        assert_eq!(vec![vec![], vec![1], vec![2], vec![1, 2], vec![3], vec![1, 3], vec![2, 3], vec![1, 2, 3]], subsets);
+    }
+
+    #[test]
+    fn test_gray_code() {
+        let mut mask = 0_u32;
+        let mut seen = HashSet::from([mask]);
+        for i in gray_code(5) {
+            mask ^= 1 << i;
+            assert!(seen.insert(mask));
+        }
+        assert_eq!(32, seen.len());
+        assert_eq!(0, gray_code(0).count());
+    }
+
+    #[test]
+    fn test_multiset_permutations() {
+        let perms = multiset_permutations(vec![2, 1, 1]).collect::<Vec<_>>();
+        assert_eq!(vec![vec![1, 1, 2], vec![1, 2, 1], vec![2, 1, 1]], perms);
+        // 8! / (2! 3! 3!)
+        assert_eq!(560, multiset_permutations("aabbbccc".chars().collect()).count());
+        assert_eq!(vec![Vec::<u8>::new()], multiset_permutations(vec![]).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,192 @@
+//!
+//! Finding where a deterministic simulation starts repeating itself
+//!
+//! A sequence `x0, step(x0), step(step(x0)), ...` over finitely many states eventually loops: after
+//! `start` steps (mu) it repeats with period `len` (lambda). Once that's known, the state after any
+//! number of steps is one of the first `start + len`:
+//!
+//! ```
+//! use aoc::cycle;
+//!
+//! let step = |&x: &u64| (x * x + 1) % 255;
+//! let cycle = cycle::detect_cycle(3, step);
+//! assert_eq!((2, 6), (cycle.start, cycle.len));
+//!
+//! let n = 1_000_000_000_000;
+//! let x = std::iter::successors(Some(3), |x| Some(step(x))).nth(cycle.reduce(n)).unwrap();
+//! assert_eq!(std::iter::successors(Some(3), |x| Some(step(x))).nth(n % 6 + 6).unwrap(), x);
+//! ```
+//!
+//! The searches run forever if the states never repeat.
+//!
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+///
+/// The first `start` states lead into a loop of `len` states
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    ///
+    /// The smallest number of steps ending in the same state as `n` steps
+    ///
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+///
+/// The cycle reached from `init` by Brent's algorithm, which keeps only two states at a time and
+/// takes at most about `3 * (start + len)` steps
+///
+pub fn detect_cycle<S, F>(init: S, step: F) -> Cycle
+    where
+        S: Clone + Eq,
+        F: FnMut(&S) -> S,
+{
+    detect_cycle_by_key(init, step, |s| s.clone())
+}
+
+///
+/// The cycle in `key` of the states reached from `init`, such as a program counter when the
+/// other registers keep changing
+///
+/// The key of a state must decide the keys of all the states after it.
+///
+pub fn detect_cycle_by_key<S, K, F, G>(init: S, mut step: F, key: G) -> Cycle
+    where
+        S: Clone,
+        K: Eq,
+        F: FnMut(&S) -> S,
+        G: Fn(&S) -> K,
+{
+    // find the period by letting the hare run ahead in powers of two
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = key(&init);
+    let mut hare = step(&init);
+    while tortoise != key(&hare) {
+        if power == len {
+            tortoise = key(&hare);
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // then walk two states a period apart until they meet at the start of the loop
+    let mut tortoise = init.clone();
+    let mut hare = init;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, len }
+}
+
+///
+/// The cycle reached from `init`, remembering a 64-bit fingerprint of every state in place of the
+/// state itself
+///
+/// Different states can share a fingerprint, so a match is confirmed by stepping from `init` to
+/// the earlier state again and comparing. A collision costs those steps but never gives a wrong
+/// cycle, and the loop is found after `start + len` steps and one confirmation of `start` more.
+///
+pub fn detect_cycle_hashed<S, F>(init: S, mut step: F) -> Cycle
+    where
+        S: Clone + Eq + Hash,
+        F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut state = init.clone();
+    for i in 0.. {
+        let earlier = seen.entry(fingerprint(&state)).or_default();
+        for &start in earlier.iter() {
+            let mut prev = init.clone();
+            for _ in 0..start {
+                prev = step(&prev);
+            }
+            if prev == state {
+                return Cycle { start, len: i - start };
+            }
+        }
+        earlier.push(i);
+        state = step(&state);
+    }
+    unreachable!()
+}
+
+///
+/// A 64-bit hash of `state`, which tells states apart when it differs but can be shared by
+/// different states
+///
+pub fn fingerprint<S: Hash + ?Sized>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_cycle() {
+        // 0 -> 1 -> ... -> 9 -> 4
+        let step = |&x: &u32| if x == 9 { 4 } else { x + 1 };
+        let cycle = Cycle { start: 4, len: 6 };
+        assert_eq!(cycle, detect_cycle(0, step));
+        assert_eq!(cycle, detect_cycle_hashed(0, step));
+        assert_eq!(Cycle { start: 0, len: 6 }, detect_cycle(7, step));
+        assert_eq!(Cycle { start: 0, len: 1 }, detect_cycle(5, |&x: &u32| x));
+
+        assert_eq!(3, cycle.reduce(3));
+        assert_eq!(4, cycle.reduce(10));
+        assert_eq!(9, cycle.reduce(1_000_000_000_000 * 6 + 9));
+
+        for init in 0..300 {
+            let step = |&x: &u64| (x * x + 7) % 293;
+            assert_eq!(detect_cycle_hashed(init, step), detect_cycle(init, step));
+        }
+    }
+
+    #[test]
+    fn test_detect_cycle_hashed_collisions() {
+        // every state has the same fingerprint, so only the confirmation tells them apart
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        struct Colliding(u32);
+
+        impl Hash for Colliding {
+            fn hash<H: Hasher>(&self, _: &mut H) {}
+        }
+
+        let step = |&Colliding(x): &Colliding| Colliding(if x == 9 { 4 } else { x + 1 });
+        assert_eq!(fingerprint(&Colliding(1)), fingerprint(&Colliding(2)));
+        assert_eq!(Cycle { start: 4, len: 6 }, detect_cycle_hashed(Colliding(0), step));
+    }
+
+    #[test]
+    fn test_detect_cycle_by_key() {
+        // the counter keeps growing, but the position loops through 2, 3, 4
+        let step = |&(pos, count): &(u8, u64)| (if pos == 4 { 2 } else { pos + 1 }, count + 1);
+        assert_eq!(Cycle { start: 2, len: 3 }, detect_cycle_by_key((0, 0), step, |&(pos, _)| pos));
+        assert_eq!(fingerprint(&vec![1, 2]), fingerprint(&[1, 2][..]));
+    }
+}
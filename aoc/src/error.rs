@@ -0,0 +1,165 @@
+//!
+//! The errors shared by every day
+//!
+//! Malformed input is a `Parse` error pointing at the offending line and column. Positions come
+//! from where a token sits in the input, so a parser only needs the input and the slice of it
+//! that it couldn't make sense of:
+//!
+//! ```
+//! use aoc::error::{self, AocError};
+//!
+//! let input = "1\n2\nx3\n";
+//! let nums = input.lines().map(|line| error::parse_at::<i32>(input, line)).collect::<Result<Vec<_>, _>>();
+//! assert!(matches!(nums, Err(AocError::Parse { line: 3, column: 1, .. })));
+//! ```
+//!
+
+use std::fmt::{self, Display};
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum AocError {
+    /// Malformed input, at a 1-based `line` and `column`, with the `text` of that line
+    Parse { line: usize, column: usize, text: String, message: String },
+    /// Well-formed input that breaks an assumption the solution relies on
+    Invalid(String),
+    /// A search that ran out of candidates
+    NoSolution(String),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl AocError {
+    ///
+    /// A parse error at the start of `at`, which should be a slice of `input`
+    ///
+    /// A string that isn't a slice of `input` is looked for in it instead, and failing that the
+    /// error points at the start of the input.
+    ///
+    pub fn parse<M: Display>(input: &str, at: &str, message: M) -> AocError {
+        let offset = (at.as_ptr() as usize).checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + at.len() <= input.len() && input.is_char_boundary(offset))
+            .or_else(|| input.find(at))
+            .unwrap_or(0);
+        AocError::parse_offset(input, offset, message)
+    }
+
+    ///
+    /// A parse error at byte `offset` of `input`
+    ///
+    pub fn parse_offset<M: Display>(input: &str, offset: usize, message: M) -> AocError {
+        let before = &input[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        AocError::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[start..].chars().count() + 1,
+            text: input[start..end].trim_end_matches('\r').to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn invalid<M: Display>(message: M) -> AocError {
+        AocError::Invalid(message.to_string())
+    }
+
+    pub fn no_solution<M: Display>(message: M) -> AocError {
+        AocError::NoSolution(message.to_string())
+    }
+
+    ///
+    /// Move a parse error found in `fragment`, such as by a `FromStr` on one line, to where
+    /// `fragment` is in `input`
+    ///
+    pub fn within(self, input: &str, fragment: &str) -> AocError {
+        match self {
+            AocError::Parse { line, column, message, .. } => {
+                // whole lines with their line endings, so the offset stays within `fragment`
+                let offset = fragment.split_inclusive('\n')
+                    .take(line.saturating_sub(1))
+                    .map(str::len)
+                    .sum::<usize>();
+                let offset = offset + fragment[offset..].chars()
+                    .take_while(|&c| c != '\n')
+                    .take(column.saturating_sub(1))
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+                AocError::parse(input, &fragment[offset..], message)
+            }
+            e => e,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse { line, column, text, message } => {
+                write!(f, "line {}, column {}: {}\n  {}\n  {:>width$}", line, column, message, text, "^", width = column)
+            }
+            AocError::Invalid(message) => write!(f, "invalid input: {}", message),
+            AocError::NoSolution(message) => write!(f, "no solution: {}", message),
+            AocError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+///
+/// Parse `token`, a slice of `input`, reporting where it is if it doesn't parse
+///
+pub fn parse_at<T>(input: &str, token: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+{
+    token.parse().map_err(|e| AocError::parse(input, token, format!("{:?}: {}", token, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "abc\nde f\n\nxyz";
+        let e = AocError::parse(input, &input[7..], "bad");
+        assert!(matches!(&e, AocError::Parse { line: 2, column: 4, text, .. } if text == "de f"));
+        assert_eq!("line 2, column 4: bad\n  de f\n     ^", e.to_string());
+
+        assert!(matches!(AocError::parse(input, "xyz", "bad"), AocError::Parse { line: 4, column: 1, .. }));
+        assert!(matches!(AocError::parse_offset(input, 9, "bad"), AocError::Parse { line: 3, column: 1, .. }));
+        assert!(matches!(parse_at::<u8>(input, &input[4..6]), Err(AocError::Parse { line: 2, column: 1, .. })));
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "12\n3 4x\n";
+        let line = &input[3..7];
+        let e = parse_at::<u8>(line, &line[2..]).unwrap_err().within(input, line);
+        assert!(matches!(e, AocError::Parse { line: 2, column: 3, .. }));
+        assert!(matches!(AocError::invalid("x").within(input, line), AocError::Invalid(_)));
+
+        let error = |line, column| AocError::Parse { line, column, text: String::new(), message: "bad".to_string() };
+        // past the end of a fragment with no trailing newline
+        let input = "12
+3 4";
+        let e = error(2, 1).within(input, &input[3..]);
+        assert!(matches!(e, AocError::Parse { line: 2, column: 4, .. }));
+        let e = error(1, 9).within(input, &input[3..]);
+        assert!(matches!(e, AocError::Parse { line: 2, column: 4, .. }));
+        // CRLF line endings
+        let input = "x\r\n1\r\n2é3\r\n";
+        let e = error(2, 3).within(input, &input[3..]);
+        assert!(matches!(e, AocError::Parse { line: 3, column: 3, .. }));
+    }
+}
@@ -0,0 +1,125 @@
+//!
+//! Examples from the puzzle text, kept next to the inputs and checked against every solution
+//!
+//! Each day can have a file at `input/aoc<year>/examples/day<day>` holding any number of examples.
+//! An example starts with a `--- input` line followed by the input verbatim, then a
+//! `--- part<n> <answer>` line for each part it has an answer for. Any other line, even one
+//! starting with `---`, is part of the input. Text before the first example is a free-form
+//! description.
+//!
+//! ```text
+//! The fuel examples.
+//! --- input
+//! 1969
+//! --- part1 654
+//! --- part2 966
+//! ```
+//!
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::solution::Entry;
+
+#[derive(Eq, PartialEq, Clone, Default, Debug)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<(u8, String)>,
+}
+
+pub fn parse(s: &str) -> Result<Vec<Example>> {
+    let mut examples: Vec<Example> = vec![];
+    let mut in_input = false;
+    for (i, line) in s.lines().enumerate() {
+        let answer = [(1, "--- part1 "), (2, "--- part2 ")].iter()
+            .find_map(|&(part, prefix)| line.strip_prefix(prefix).map(|answer| (part, answer)));
+        match answer {
+            _ if line == "--- input" => {
+                examples.push(Example::default());
+                in_input = true;
+            }
+            Some((part, answer)) => {
+                match examples.last_mut() {
+                    Some(example) => example.answers.push((part, answer.trim().to_string())),
+                    None => bail!("line {}: answer before any input", i + 1),
+                }
+                in_input = false;
+            }
+            None if in_input => {
+                let example = examples.last_mut().unwrap();
+                example.input.push_str(line);
+                example.input.push('\n');
+            }
+            None if examples.is_empty() || line.trim().is_empty() => (),
+            None => bail!("line {}: expected an answer or the next input", i + 1),
+        }
+    }
+    Ok(examples)
+}
+
+///
+/// Run every example found under `root` for `solutions`, returning the number of answers checked
+///
+/// Fails listing every wrong answer, rather than stopping at the first.
+///
+pub fn check_all(solutions: &[Entry], root: &Path) -> Result<usize> {
+    let mut num_checked = 0;
+    let mut failures = vec![];
+    for entry in solutions {
+        let path = root.join(entry.examples_path());
+        if !path.exists() {
+            continue;
+        }
+        let s = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        let examples = parse(&s).with_context(|| format!("parsing {}", path.display()))?;
+        for (i, example) in examples.iter().enumerate() {
+            for (part, expected) in example.answers.iter() {
+                num_checked += 1;
+                let answer = entry.solve(&example.input, &[*part]).map(|answers| answers[0].value.clone());
+                match answer {
+                    Ok(answer) if answer == *expected => (),
+                    Ok(answer) => failures.push(format!("{} example {} part {}: expected {}, got {}",
+                                                        path.display(), i + 1, part, expected, answer)),
+                    Err(e) => failures.push(format!("{} example {} part {}: {:#}",
+                                                    path.display(), i + 1, part, e)),
+                }
+            }
+        }
+    }
+    if !failures.is_empty() {
+        bail!("{} of {} examples failed:\n{}", failures.len(), num_checked, failures.join("\n"));
+    }
+    Ok(num_checked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let s = "\
+The fuel examples.
+--- input
+12
+14
+--- part1 4
+--- input
+1969
+--- part1 654
+--- part2 966
+";
+        let example = |input: &str, answers: &[(u8, &str)]| Example {
+            input: input.to_string(),
+            answers: answers.iter().map(|&(part, answer)| (part, answer.to_string())).collect(),
+        };
+        assert_eq!(vec![example("12\n14\n", &[(1, "4")]), example("1969\n", &[(1, "654"), (2, "966")])],
+                   parse(s).unwrap());
+        assert!(parse("--- part1 2").is_err());
+        assert_eq!("--- scanner 0 ---\n", parse("--- input\n--- scanner 0 ---\n--- part1 1").unwrap()[0].input);
+        assert!(parse("--- input\n1\n--- part1 2\n--- part3 2").is_err());
+        assert!(parse("--- input\n1\n--- part1 2\n3").is_err());
+    }
+}
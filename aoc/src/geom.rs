@@ -0,0 +1,379 @@
+//!
+//! N-dimensional vectors and grid directions
+//!
+//! Directions follow the screen convention used throughout: x increases to the east and y
+//! increases to the south.
+//!
+
+use std::convert::TryFrom;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num::{Num, Signed, ToPrimitive};
+
+use crate::orient::{D4, Rot3};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
+
+pub type Vector2<T = i64> = Vector<T, 2>;
+pub type Vector3<T = i64> = Vector<T, 3>;
+
+impl<T: Copy, const N: usize> Vector<T, N> {
+    pub fn new(components: [T; N]) -> Vector<T, N> {
+        Vector(components)
+    }
+
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Vector<U, N> {
+        Vector(self.0.map(f))
+    }
+
+    pub fn zip_with<U, F: FnMut(T, T) -> U>(self, other: Vector<T, N>, mut f: F) -> Vector<U, N> {
+        let mut i = 0;
+        Vector([(); N].map(|_| {
+            let res = f(self.0[i], other.0[i]);
+            i += 1;
+            res
+        }))
+    }
+}
+
+impl<T: Copy + Ord, const N: usize> Vector<T, N> {
+    pub fn component_min(self, other: Vector<T, N>) -> Vector<T, N> {
+        self.zip_with(other, std::cmp::min)
+    }
+
+    pub fn component_max(self, other: Vector<T, N>) -> Vector<T, N> {
+        self.zip_with(other, std::cmp::max)
+    }
+}
+
+impl<T: Num + Copy, const N: usize> Vector<T, N> {
+    pub fn zero() -> Vector<T, N> {
+        Vector([T::zero(); N])
+    }
+
+    ///
+    /// The vector that is 1 along `axis` and 0 elsewhere
+    ///
+    pub fn unit(axis: usize) -> Vector<T, N> {
+        let mut res = Vector::zero();
+        res.0[axis] = T::one();
+        res
+    }
+
+    ///
+    /// All 3^N - 1 vectors that differ by at most 1 in each component
+    ///
+    pub fn neighbours(&self) -> impl Iterator<Item=Vector<T, N>> {
+        let origin = *self;
+        (0..3usize.pow(N as u32)).filter_map(move |mut i| {
+            let mut res = origin;
+            let mut is_origin = true;
+            for c in res.0.iter_mut() {
+                let digit = i % 3;
+                i /= 3;
+                match digit {
+                    0 => *c = *c - T::one(),
+                    2 => *c = *c + T::one(),
+                    _ => continue,
+                }
+                is_origin = false;
+            }
+            if is_origin { None } else { Some(res) }
+        })
+    }
+
+    ///
+    /// The 2N vectors that differ by 1 in exactly one component
+    ///
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item=Vector<T, N>> {
+        let origin = *self;
+        (0..2 * N).map(move |i| {
+            let mut res = origin;
+            if i % 2 == 0 {
+                res.0[i / 2] = res.0[i / 2] - T::one();
+            } else {
+                res.0[i / 2] = res.0[i / 2] + T::one();
+            }
+            res
+        })
+    }
+}
+
+impl<T: Signed + Copy + Ord, const N: usize> Vector<T, N> {
+    pub fn signum(self) -> Vector<T, N> {
+        self.map(|c| c.signum())
+    }
+
+    pub fn manhattan(&self) -> T {
+        self.0.iter().fold(T::zero(), |acc, c| acc + c.abs())
+    }
+
+    pub fn chebyshev(&self) -> T {
+        self.0.iter().fold(T::zero(), |acc, c| std::cmp::max(acc, c.abs()))
+    }
+
+    pub fn manhattan_distance(&self, other: Vector<T, N>) -> T {
+        (*self - other).manhattan()
+    }
+
+    pub fn chebyshev_distance(&self, other: Vector<T, N>) -> T {
+        (*self - other).chebyshev()
+    }
+}
+
+impl<T: ToPrimitive + Copy, const N: usize> Vector<T, N> {
+    pub fn euclidean(&self) -> f64 {
+        self.0.iter()
+            .map(|c| c.to_f64().unwrap().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl<T, const N: usize> Vector<T, N>
+    where
+        T: ToPrimitive + Copy + Sub<Output=T>,
+{
+    pub fn euclidean_distance(&self, other: Vector<T, N>) -> f64 {
+        (*self - other).euclidean()
+    }
+}
+
+impl<T: Neg<Output=T> + Copy> Vector<T, 2> {
+    pub fn orient(self, orientation: D4) -> Vector<T, 2> {
+        Vector(orientation.apply(self.0))
+    }
+}
+
+impl<T: Neg<Output=T> + Copy> Vector<T, 3> {
+    pub fn rotate(self, rot: Rot3) -> Vector<T, 3> {
+        Vector(rot.apply(self.0))
+    }
+}
+
+impl<T: Default + Copy, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Vector([T::default(); N])
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Vector(components)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Add<Output=T> + Copy, const N: usize> Add for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<T: Sub<Output=T> + Copy, const N: usize> Sub for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<T: Mul<Output=T> + Copy, const N: usize> Mul<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.map(|c| c * rhs)
+    }
+}
+
+impl<T: Neg<Output=T> + Copy, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|c| -c)
+    }
+}
+
+impl<T: Add<Output=T> + Copy, const N: usize> AddAssign for Vector<T, N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Sub<Output=T> + Copy, const N: usize> SubAssign for Vector<T, N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Mul<Output=T> + Copy, const N: usize> MulAssign<T> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Num + Copy, const N: usize> Sum for Vector<T, N> {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Vector::zero(), |acc, v| acc.zip_with(v, |a, b| a + b))
+    }
+}
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    pub const VALUES_4D: [Direction; 4] = [
+        Direction::N,
+        Direction::E,
+        Direction::S,
+        Direction::W,
+    ];
+
+    pub const VALUES_8D: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+
+    pub fn dxdy<T: Signed + Copy>(&self) -> Vector2<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let [x, y] = match self {
+            Direction::N => [zero, -one],
+            Direction::NE => [one, -one],
+            Direction::E => [one, zero],
+            Direction::SE => [one, one],
+            Direction::S => [zero, one],
+            Direction::SW => [-one, one],
+            Direction::W => [-one, zero],
+            Direction::NW => [-one, -one],
+        };
+        Vector([x, y])
+    }
+
+    ///
+    /// Turn clockwise by `num_turns` eighths of a full turn
+    ///
+    pub fn cw(&self, num_turns: isize) -> Direction {
+        let dir = ((*self as isize) + num_turns).rem_euclid(8);
+        dir.try_into().unwrap()
+    }
+
+    ///
+    /// Turn counter-clockwise by `num_turns` eighths of a full turn
+    ///
+    pub fn ccw(&self, num_turns: isize) -> Direction {
+        self.cw(-num_turns)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.cw(4)
+    }
+}
+
+impl TryFrom<isize> for Direction {
+    type Error = ();
+
+    fn try_from(i: isize) -> Result<Self, Self::Error> {
+        Direction::VALUES_8D.get(usize::try_from(i).map_err(|_| ())?)
+            .copied()
+            .ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let a = Vector([1, -2, 3]);
+        let b = Vector([4, 5, -6]);
+        assert_eq!(Vector([5, 3, -3]), a + b);
+        assert_eq!(Vector([-3, -7, 9]), a - b);
+        assert_eq!(Vector([2, -4, 6]), a * 2);
+        assert_eq!(Vector([-1, 2, -3]), -a);
+        assert_eq!(Vector([1, -2, -6]), a.component_min(b));
+        assert_eq!(Vector([4, 5, 3]), a.component_max(b));
+        assert_eq!(Vector([5, 3, -3]), vec![a, b].into_iter().sum());
+        assert_eq!(Vector([1, -1, 1]), a.signum());
+        assert_eq!(Vector([0, 0, 1]), Vector3::<i32>::unit(2));
+    }
+
+    #[test]
+    fn test_norms() {
+        let a = Vector([3, -4]);
+        assert_eq!(7, a.manhattan());
+        assert_eq!(4, a.chebyshev());
+        assert_eq!(5.0, a.euclidean());
+        assert_eq!(3, a.manhattan_distance(Vector([2, -1]) * 2));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Vector([1, 2, 3, 4]);
+        let ns = p.neighbours().collect::<HashSet<_>>();
+        assert_eq!(80, ns.len());
+        assert!(ns.iter().all(|&n| n != p && n.chebyshev_distance(p) == 1));
+
+        let ns = p.orthogonal_neighbours().collect::<HashSet<_>>();
+        assert_eq!(8, ns.len());
+        assert!(ns.iter().all(|&n| n.manhattan_distance(p) == 1));
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::E, Direction::N.cw(2));
+        assert_eq!(Direction::W, Direction::N.ccw(2));
+        assert_eq!(Direction::NE, Direction::NW.cw(10));
+        assert_eq!(Direction::S, Direction::N.opposite());
+        assert_eq!(Vector([1, -1]), Direction::NE.dxdy::<i64>());
+        for dir in Direction::VALUES_8D {
+            assert_eq!(dir.cw(2).dxdy::<i32>(), dir.dxdy().orient(D4::ROTATE_CW));
+        }
+    }
+}
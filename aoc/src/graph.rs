@@ -0,0 +1,506 @@
+//!
+//! Directed graphs of dependencies, and trees given by each node's parent
+//!
+//! A `Graph` keeps its nodes in the order they were added, so its topological order is the same
+//! from run to run. Values can be accumulated over a graph without cycles either from each node's
+//! successors or pushed forward from some sources:
+//!
+//! ```
+//! use aoc::graph::{Graph, Tree};
+//!
+//! // a bag holding 2 bags, which each hold 3 more
+//! let mut bags = Graph::new();
+//! bags.add_edge("outer", "middle", 2);
+//! bags.add_edge("middle", "inner", 3);
+//! assert_eq!(vec![&"outer", &"middle", &"inner"], bags.topo_sort().unwrap());
+//!
+//! let inside = bags.accumulate(|_, held: &[(&u32, &u32)]| held.iter().map(|&(n, inside)| n * (1 + inside)).sum())
+//!     .unwrap();
+//! assert_eq!(8, inside["outer"]);
+//!
+//! bags.add_edge("inner", "outer", 1);
+//! assert_eq!(vec!["outer", "middle", "inner", "outer"], bags.topo_sort().unwrap_err().cycle);
+//!
+//! let tree = Tree::from_edges([("root", "a"), ("a", "b"), ("a", "c")]).unwrap();
+//! assert_eq!(Some(&"a"), tree.lca("b", "c"));
+//! assert_eq!(Some(2), tree.distance("b", "c"));
+//! ```
+//!
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+///
+/// A cycle found where none was allowed, from a node back round to the same node
+///
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+impl<N: fmt::Display> fmt::Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle through ")?;
+        for (i, n) in self.cycle.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", n)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: fmt::Debug + fmt::Display> Error for CycleError<N> {}
+
+///
+/// Nodes joined by directed edges with a weight each
+///
+#[derive(Debug, Clone)]
+pub struct Graph<N, W = ()> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    // (from, to, weight), and the edges out of and into each node by their position here
+    edges: Vec<(usize, usize, W)>,
+    out: Vec<Vec<usize>>,
+    inc: Vec<Vec<usize>>,
+}
+
+impl<N: Hash + Eq + Clone, W> Graph<N, W> {
+    pub fn new() -> Graph<N, W> {
+        Graph { nodes: Vec::new(), index: HashMap::new(), edges: Vec::new(), out: Vec::new(), inc: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item=&N> {
+        self.nodes.iter()
+    }
+
+    pub fn contains<Q>(&self, n: &Q) -> bool
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        self.index.contains_key(n)
+    }
+
+    ///
+    /// Add `n` without any edges, if it isn't already there
+    ///
+    pub fn add_node(&mut self, n: N) {
+        self.node_index(n);
+    }
+
+    ///
+    /// Add an edge, and its ends if they aren't already there
+    ///
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) {
+        let (from, to) = (self.node_index(from), self.node_index(to));
+        self.out[from].push(self.edges.len());
+        self.inc[to].push(self.edges.len());
+        self.edges.push((from, to, weight));
+    }
+
+    ///
+    /// The ends of the edges out of `n`, with their weights, in the order they were added
+    ///
+    pub fn successors<Q>(&self, n: &Q) -> impl Iterator<Item=(&N, &W)>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        let edges = self.index.get(n).map_or(&[][..], |&i| &self.out[i]);
+        edges.iter().map(|&e| (&self.nodes[self.edges[e].1], &self.edges[e].2))
+    }
+
+    ///
+    /// The starts of the edges into `n`, with their weights, in the order they were added
+    ///
+    pub fn predecessors<Q>(&self, n: &Q) -> impl Iterator<Item=(&N, &W)>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        let edges = self.index.get(n).map_or(&[][..], |&i| &self.inc[i]);
+        edges.iter().map(|&e| (&self.nodes[self.edges[e].0], &self.edges[e].2))
+    }
+
+    ///
+    /// Every node, each before all of its successors, by Kahn's algorithm
+    ///
+    pub fn topo_sort(&self) -> Result<Vec<&N>, CycleError<N>> {
+        Ok(self.order()?.into_iter().map(|i| &self.nodes[i]).collect())
+    }
+
+    ///
+    /// The nodes with a path from `n`, not counting `n` unless it's on a cycle
+    ///
+    pub fn reachable_from<Q>(&self, n: &Q) -> HashSet<&N>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        self.search(n, |i| self.out[i].iter().map(|&e| self.edges[e].1))
+    }
+
+    ///
+    /// The nodes with a path to `n`, not counting `n` unless it's on a cycle
+    ///
+    pub fn reaching<Q>(&self, n: &Q) -> HashSet<&N>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        self.search(n, |i| self.inc[i].iter().map(|&e| self.edges[e].0))
+    }
+
+    ///
+    /// A value for every node from `f`, given the node and the weight and value of each of its
+    /// successors, working back from the nodes without any
+    ///
+    pub fn accumulate<V, F>(&self, mut f: F) -> Result<HashMap<N, V>, CycleError<N>>
+        where F: FnMut(&N, &[(&W, &V)]) -> V
+    {
+        let mut values: Vec<Option<V>> = (0..self.len()).map(|_| None).collect();
+        for i in self.order()?.into_iter().rev() {
+            let succ = self.out[i].iter()
+                .map(|&e| {
+                    let (_, to, weight) = &self.edges[e];
+                    (weight, values[*to].as_ref().unwrap())
+                })
+                .collect::<Vec<_>>();
+            values[i] = Some(f(&self.nodes[i], &succ));
+        }
+        Ok(self.nodes.iter().cloned().zip(values.into_iter().map(Option::unwrap)).collect())
+    }
+
+    ///
+    /// The total pushed into every node, starting from `sources` and passing on along each edge
+    /// what `f` makes of the total at its start and its weight
+    ///
+    /// A node's total is complete before anything is passed on from it, so what it passes on can
+    /// depend on the total in ways that don't add up edge by edge. Sources not in the graph are
+    /// ignored, and nodes nothing reaches have the default total.
+    ///
+    pub fn propagate<V, I, F>(&self, sources: I, mut f: F) -> Result<HashMap<N, V>, CycleError<N>>
+        where
+            V: Default + AddAssign,
+            I: IntoIterator<Item=(N, V)>,
+            F: FnMut(&N, &V, &W) -> V,
+    {
+        let mut totals: Vec<V> = (0..self.len()).map(|_| V::default()).collect();
+        for (n, v) in sources {
+            if let Some(&i) = self.index.get(&n) {
+                totals[i] += v;
+            }
+        }
+        for i in self.order()? {
+            for &e in &self.out[i] {
+                let (from, to, weight) = &self.edges[e];
+                let v = f(&self.nodes[*from], &totals[*from], weight);
+                totals[*to] += v;
+            }
+        }
+        Ok(self.nodes.iter().cloned().zip(totals).collect())
+    }
+
+    fn node_index(&mut self, n: N) -> usize {
+        if let Some(&i) = self.index.get(&n) {
+            return i;
+        }
+        self.index.insert(n.clone(), self.nodes.len());
+        self.nodes.push(n);
+        self.out.push(Vec::new());
+        self.inc.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    fn order(&self) -> Result<Vec<usize>, CycleError<N>> {
+        let mut in_degree = self.inc.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut ready = (0..self.len()).filter(|&i| in_degree[i] == 0).collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+            for &e in &self.out[i] {
+                let to = self.edges[e].1;
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    ready.push_back(to);
+                }
+            }
+        }
+        if order.len() == self.len() {
+            return Ok(order);
+        }
+
+        // every node left over has an edge in from another one left over, so walking back along
+        // those edges must come round to a node already seen
+        let mut seen = HashMap::new();
+        let mut path = Vec::new();
+        let mut i = (0..self.len()).find(|&i| in_degree[i] > 0).unwrap();
+        while !seen.contains_key(&i) {
+            seen.insert(i, path.len());
+            path.push(i);
+            i = self.inc[i].iter()
+                .map(|&e| self.edges[e].0)
+                .find(|&from| in_degree[from] > 0)
+                .unwrap();
+        }
+        let mut cycle = path.split_off(seen[&i]);
+        cycle.push(i);
+        cycle.reverse();
+        Err(CycleError { cycle: cycle.into_iter().map(|i| self.nodes[i].clone()).collect() })
+    }
+
+    fn search<Q, F, I>(&self, n: &Q, next: F) -> HashSet<&N>
+        where
+            N: Borrow<Q>,
+            Q: Hash + Eq + ?Sized,
+            F: Fn(usize) -> I,
+            I: Iterator<Item=usize>,
+    {
+        let mut seen = HashSet::new();
+        let mut stack = self.index.get(n).into_iter().flat_map(|&i| next(i)).collect::<Vec<_>>();
+        while let Some(i) = stack.pop() {
+            if seen.insert(i) {
+                stack.extend(next(i));
+            }
+        }
+        seen.into_iter().map(|i| &self.nodes[i]).collect()
+    }
+}
+
+impl<N: Hash + Eq + Clone, W> Default for Graph<N, W> {
+    fn default() -> Graph<N, W> {
+        Graph::new()
+    }
+}
+
+///
+/// A forest given by the parent of each node that has one
+///
+#[derive(Debug, Clone)]
+pub struct Tree<N> {
+    parent: HashMap<N, N>,
+    depth: HashMap<N, usize>,
+}
+
+impl<N: Hash + Eq + Clone> Tree<N> {
+    ///
+    /// The tree of `(parent, child)` edges, where a child given two parents keeps the last
+    ///
+    pub fn from_edges<I>(edges: I) -> Result<Tree<N>, CycleError<N>>
+        where I: IntoIterator<Item=(N, N)>
+    {
+        Tree::from_parents(edges.into_iter().map(|(parent, child)| (child, parent)).collect())
+    }
+
+    pub fn from_parents(parent: HashMap<N, N>) -> Result<Tree<N>, CycleError<N>> {
+        let mut depth = HashMap::new();
+        for n in parent.keys() {
+            // walk up to a node whose depth is known, or to a root
+            let mut path = vec![n];
+            let mut node = n;
+            let mut base = loop {
+                if let Some(&d) = depth.get(node) {
+                    path.pop();
+                    break d;
+                }
+                match parent.get(node) {
+                    None => {
+                        path.pop();
+                        depth.insert(node.clone(), 0);
+                        break 0;
+                    }
+                    Some(p) => {
+                        if let Some(i) = path.iter().position(|&q| q == p) {
+                            // the path runs up from child to parent, and the cycle down
+                            let mut cycle = path[i..].iter().rev().map(|&q| q.clone()).collect::<Vec<_>>();
+                            cycle.push(cycle[0].clone());
+                            return Err(CycleError { cycle });
+                        }
+                        node = p;
+                        path.push(p);
+                    }
+                }
+            };
+            for n in path.into_iter().rev() {
+                base += 1;
+                depth.insert(n.clone(), base);
+            }
+        }
+        Ok(Tree { parent, depth })
+    }
+
+    pub fn contains<Q>(&self, n: &Q) -> bool
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        self.depth.contains_key(n)
+    }
+
+    pub fn parent<Q>(&self, n: &Q) -> Option<&N>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        self.parent.get(n)
+    }
+
+    ///
+    /// The number of steps up from `n` to its root
+    ///
+    pub fn depth<Q>(&self, n: &Q) -> Option<usize>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        self.depth.get(n).copied()
+    }
+
+    ///
+    /// Every node with its depth
+    ///
+    pub fn depths(&self) -> impl Iterator<Item=(&N, usize)> {
+        self.depth.iter().map(|(n, &d)| (n, d))
+    }
+
+    ///
+    /// The parent of `n`, its parent, and so on up to the root
+    ///
+    pub fn ancestors<'a, Q>(&'a self, n: &Q) -> impl Iterator<Item=&'a N>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        std::iter::successors(self.parent.get(n), |&p| self.up(p))
+    }
+
+    ///
+    /// The deepest node that is `a` or one of its ancestors and also `b` or one of its ancestors,
+    /// or `None` if they're in different trees
+    ///
+    pub fn lca<Q>(&self, a: &Q, b: &Q) -> Option<&N>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        let (mut a, &(mut da)) = self.depth.get_key_value(a)?;
+        let (mut b, &(mut db)) = self.depth.get_key_value(b)?;
+        while da > db {
+            (a, da) = (self.up(a)?, da - 1);
+        }
+        while db > da {
+            (b, db) = (self.up(b)?, db - 1);
+        }
+        while a != b {
+            (a, b) = (self.up(a)?, self.up(b)?);
+        }
+        Some(a)
+    }
+
+    ///
+    /// The number of edges on the path between `a` and `b`
+    ///
+    pub fn distance<Q>(&self, a: &Q, b: &Q) -> Option<usize>
+        where N: Borrow<Q>, Q: Hash + Eq + ?Sized
+    {
+        let lca = self.lca(a, b)?;
+        Some(self.depth.get(a)? + self.depth.get(b)? - 2 * self.depth.get::<N>(lca)?)
+    }
+
+    fn up(&self, n: &N) -> Option<&N> {
+        self.parent.get(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topo_sort() {
+        let mut g = Graph::new();
+        for (from, to) in [(5, 11), (7, 11), (7, 8), (3, 8), (3, 10), (11, 2), (11, 9), (11, 10), (8, 9)] {
+            g.add_edge(from, to, ());
+        }
+        g.add_node(1);
+        let order = g.topo_sort().unwrap();
+        assert_eq!(vec![&5, &7, &3, &1, &11, &8, &2, &10, &9], order);
+        let pos = |n: i32| order.iter().position(|&&m| m == n).unwrap();
+        for n in g.nodes() {
+            assert!(g.successors(n).all(|(m, _)| pos(*n) < pos(*m)));
+        }
+
+        g.add_edge(9, 7, ());
+        assert_eq!(vec![11, 9, 7, 11], g.topo_sort().unwrap_err().cycle);
+        let mut g = Graph::new();
+        g.add_edge('a', 'a', ());
+        assert_eq!("cycle through a -> a", g.topo_sort().unwrap_err().to_string());
+        assert!(Graph::<u8>::new().topo_sort().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reachable() {
+        let mut g = Graph::new();
+        for (from, to) in [("a", "b"), ("b", "c"), ("c", "b"), ("d", "c"), ("e", "a")] {
+            g.add_edge(from.to_string(), to.to_string(), ());
+        }
+        fn names(set: HashSet<&String>) -> Vec<&str> {
+            let mut names = set.into_iter().map(String::as_str).collect::<Vec<_>>();
+            names.sort();
+            names
+        }
+        assert_eq!(vec!["b", "c"], names(g.reachable_from("a")));
+        assert_eq!(vec!["b", "c"], names(g.reachable_from("b")));
+        assert_eq!(vec!["a", "b", "c", "d", "e"], names(g.reaching("c")));
+        assert_eq!(Vec::<&str>::new(), names(g.reaching("e")));
+        assert_eq!(Vec::<&str>::new(), names(g.reaching("z")));
+        assert_eq!(vec![(&"a".to_string(), &())], g.successors("e").collect::<Vec<_>>());
+        assert_eq!(0, g.predecessors("e").count());
+    }
+
+    #[test]
+    fn test_accumulate() {
+        // a diamond, counting paths and pushing amounts down it
+        let mut g = Graph::new();
+        for (from, to, w) in [("top", "left", 2), ("top", "right", 3), ("left", "bottom", 5), ("right", "bottom", 7)] {
+            g.add_edge(from, to, w);
+        }
+        let paths = g.accumulate(|_, succ: &[(&i32, &u32)]| succ.iter().map(|&(_, n)| n).sum::<u32>().max(1)).unwrap();
+        assert_eq!((2, 1, 1), (paths["top"], paths["left"], paths["bottom"]));
+
+        let totals = g.propagate([("top", 1), ("left", 1), ("nowhere", 10)], |_, total, w| total * w).unwrap();
+        assert_eq!((1, 3, 3, 15 + 21), (totals["top"], totals["left"], totals["right"], totals["bottom"]));
+
+        g.add_edge("bottom", "top", 1);
+        assert!(g.accumulate(|_, _| 0).is_err());
+        assert!(g.propagate([("top", 1)], |_, total, _| *total).is_err());
+    }
+
+    #[test]
+    fn test_tree() {
+        //       a
+        //      / \
+        //     b   c
+        //    / \   \
+        //   d   e   f
+        //  /
+        // g
+        let edges = [("a", "b"), ("a", "c"), ("b", "d"), ("b", "e"), ("c", "f"), ("d", "g"), ("x", "y")];
+        let tree = Tree::from_edges(edges).unwrap();
+        assert_eq!(Some(3), tree.depth("g"));
+        assert_eq!(Some(0), tree.depth("a"));
+        assert_eq!(None, tree.depth("z"));
+        assert_eq!(vec![&"d", &"b", &"a"], tree.ancestors("g").collect::<Vec<_>>());
+        assert_eq!(12, tree.depths().map(|(_, d)| d).sum::<usize>());
+
+        assert_eq!(Some(&"b"), tree.lca("g", "e"));
+        assert_eq!(Some(&"a"), tree.lca("g", "f"));
+        assert_eq!(Some(&"d"), tree.lca("g", "d"));
+        assert_eq!(Some(&"c"), tree.lca("c", "c"));
+        assert_eq!(None, tree.lca("g", "y"));
+        assert_eq!(None, tree.lca("g", "z"));
+        assert_eq!(Some(5), tree.distance("g", "f"));
+        assert_eq!(Some(0), tree.distance("e", "e"));
+        assert_eq!(None, tree.distance("a", "x"));
+
+        // the cycle can start anywhere round it
+        let edges = [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")];
+        let cycle = Tree::from_edges(edges).unwrap_err().cycle;
+        assert_eq!(4, cycle.len());
+        assert!(cycle.windows(2).all(|w| edges.contains(&(w[0], w[1]))));
+    }
+}
@@ -0,0 +1,194 @@
+//!
+//! Finding and reading puzzle inputs
+//!
+//! A day's input lives at `input/aoc<year>/day<day>` under the workspace root, wherever the binary
+//! is run from. A day can also have named variants next to it, such as `day18b` for the `b` variant
+//! of day 18. With the `embed` feature every input is also compiled in, and used when the file is
+//! not on disk.
+//!
+
+use std::{env, error, fmt, fs, io};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::solution::Entry;
+
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Source {
+    /// The day's own input, or the named variant of it when `variant` is not empty
+    Puzzle { variant: String },
+    File(PathBuf),
+    Stdin,
+}
+
+impl Default for Source {
+    fn default() -> Source {
+        Source::Puzzle { variant: String::new() }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No file at `path`, listing the variants of the day that do exist
+    Missing { path: PathBuf, variants: Vec<String> },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { path, variants } => {
+                write!(f, "no input at {}, save the puzzle input there or pass --input <path|->", path.display())?;
+                if !variants.is_empty() {
+                    write!(f, " (variants: {})", variants.join(", "))?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, source } => write!(f, "reading {}: {}", path.display(), source),
+        }
+    }
+}
+
+impl error::Error for InputError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+///
+/// The nearest directory above the current one with a workspace `Cargo.toml`, falling back to
+/// the workspace this crate was built in
+///
+pub fn workspace_root() -> PathBuf {
+    let is_root = |dir: &Path| fs::read_to_string(dir.join("Cargo.toml"))
+        .is_ok_and(|manifest| manifest.contains("[workspace]"));
+    env::current_dir().ok()
+        .and_then(|dir| dir.ancestors().find(|dir| is_root(dir)).map(Path::to_path_buf))
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf())
+}
+
+///
+/// The names of the variants of `entry`'s input under `root`, in order
+///
+pub fn variants(root: &Path, entry: &Entry) -> Vec<String> {
+    let path = root.join(entry.input_path());
+    let (dir, prefix) = match (path.parent(), path.file_name().and_then(|name| name.to_str())) {
+        (Some(dir), Some(prefix)) => (dir, prefix),
+        _ => return vec![],
+    };
+    let mut variants = fs::read_dir(dir).into_iter()
+        .flatten()
+        .filter_map(|file| file.ok()?.file_name().into_string().ok())
+        .filter_map(|name| {
+            let variant = name.strip_prefix(prefix)?;
+            let is_variant = variant.starts_with(|c: char| c.is_ascii_alphabetic());
+            is_variant.then(|| variant.to_string())
+        })
+        .collect::<Vec<_>>();
+    variants.sort();
+    variants
+}
+
+pub fn load(root: &Path, entry: &Entry, source: &Source) -> Result<String, InputError> {
+    match source {
+        Source::Puzzle { variant } => {
+            let relative = format!("{}{}", entry.input_path(), variant);
+            let path = root.join(&relative);
+            match read(&path) {
+                Err(InputError::Missing { .. }) => embedded(&relative).ok_or_else(|| InputError::Missing {
+                    path,
+                    variants: variants(root, entry),
+                }),
+                res => res,
+            }
+        }
+        Source::File(path) => read(path),
+        Source::Stdin => {
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s)
+                .map_err(|source| InputError::Io { path: PathBuf::from("-"), source })?;
+            Ok(s)
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => InputError::Missing { path: path.to_path_buf(), variants: vec![] },
+        _ => InputError::Io { path: path.to_path_buf(), source },
+    })
+}
+
+fn embedded(relative: &str) -> Option<String> {
+    let key = relative.strip_prefix("input/")?;
+    embedded::INPUTS.iter()
+        .find(|(path, _)| *path == key)
+        .map(|(_, input)| input.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+
+    use anyhow::Result;
+
+    use crate::solution::Solution;
+
+    use super::*;
+
+    struct Day1;
+
+    impl Solution for Day1 {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Input = String;
+
+        fn parse(input: &str) -> Result<String> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &String) -> Result<impl Display> {
+            Ok(input.clone())
+        }
+
+        fn part2(input: &String) -> Result<impl Display> {
+            Ok(input.clone())
+        }
+    }
+
+    #[test]
+    fn test_load() -> Result<()> {
+        let root = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        let dir = root.join("input/aoc2000");
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("day1"), "1")?;
+        fs::write(dir.join("day1b"), "1b")?;
+        fs::write(dir.join("day10"), "10")?;
+
+        let entry = Entry::new::<Day1>();
+        let puzzle = |variant: &str| load(&root, &entry, &Source::Puzzle { variant: variant.to_string() });
+        assert_eq!("1", puzzle("")?);
+        assert_eq!("1b", puzzle("b")?);
+        assert_eq!(vec!["b"], variants(&root, &entry));
+        assert_eq!("10", load(&root, &entry, &Source::File(dir.join("day10")))?);
+        let missing = puzzle("c");
+        fs::remove_dir_all(&root)?;
+
+        match missing {
+            Err(InputError::Missing { path, variants }) => {
+                assert_eq!(dir.join("day1c"), path);
+                assert_eq!(vec!["b"], variants);
+            }
+            res => panic!("expected a missing input, got {:?}", res),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+//!
+//! Half-open intervals and sets of them
+//!
+//! Puzzles usually give inclusive bounds such as `x=10..12`, which `Interval::inclusive` converts.
+//!
+
+use std::cmp::{max, min};
+use std::fmt;
+use std::iter::FromIterator;
+
+use num::Num;
+
+///
+/// The values `lo <= x < hi`, which is empty when `hi <= lo`
+///
+#[derive(Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+impl<T: Num + Ord + Copy> Interval<T> {
+    pub fn new(lo: T, hi: T) -> Interval<T> {
+        Interval { lo, hi }
+    }
+
+    pub fn inclusive(lo: T, hi: T) -> Interval<T> {
+        Interval { lo, hi: hi + T::one() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hi <= self.lo
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() { T::zero() } else { self.hi - self.lo }
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.lo <= x && x < self.hi
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let res = Interval { lo: max(self.lo, other.lo), hi: min(self.hi, other.hi) };
+        if res.is_empty() { None } else { Some(res) }
+    }
+
+    ///
+    /// The non-empty parts of `self` below and above `other`
+    ///
+    pub fn difference(&self, other: &Interval<T>) -> impl Iterator<Item=Interval<T>> {
+        let below = Interval { lo: self.lo, hi: min(self.hi, other.lo) };
+        let above = Interval { lo: max(self.lo, other.hi), hi: self.hi };
+        [below, above].into_iter().filter(|i| !i.is_empty())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.lo, self.hi)
+    }
+}
+
+///
+/// A union of intervals, kept as sorted, disjoint and non-adjacent intervals
+///
+#[derive(Eq, PartialEq, Hash, Clone, Default, Debug)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Num + Ord + Copy> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet { intervals: vec![] }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    ///
+    /// Total length of all the intervals
+    ///
+    pub fn len(&self) -> T {
+        self.intervals.iter().fold(T::zero(), |acc, i| acc + i.len())
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.hi <= x);
+        self.intervals.get(i).is_some_and(|i| i.contains(x))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        *self = self.difference(&IntervalSet::from(interval));
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.intervals.iter().chain(other.intervals.iter()).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (&self.intervals[i], &other.intervals[j]);
+            res.extend(a.intersection(b));
+            if a.hi < b.hi { i += 1; } else { j += 1; }
+        }
+        IntervalSet { intervals: res }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut res = vec![];
+        let mut others = other.intervals.iter().peekable();
+        for &interval in self.intervals.iter() {
+            let mut rest = Some(interval);
+            while let (Some(r), Some(o)) = (rest, others.peek()) {
+                if o.hi <= r.lo {
+                    others.next();
+                    continue;
+                }
+                if r.hi <= o.lo {
+                    break;
+                }
+                let mut parts = r.difference(o).peekable();
+                if let Some(below) = parts.next_if(|p| p.lo == r.lo) {
+                    res.push(below);
+                }
+                rest = parts.next();
+                if rest.is_some() {
+                    others.next();
+                }
+            }
+            res.extend(rest);
+        }
+        IntervalSet { intervals: res }
+    }
+
+    fn normalize(&mut self) {
+        self.intervals.retain(|i| !i.is_empty());
+        self.intervals.sort_unstable_by_key(|i| i.lo);
+        let mut res: Vec<Interval<T>> = Vec::with_capacity(self.intervals.len());
+        for &interval in self.intervals.iter() {
+            match res.last_mut() {
+                Some(last) if interval.lo <= last.hi => last.hi = max(last.hi, interval.hi),
+                _ => res.push(interval),
+            }
+        }
+        self.intervals = res;
+    }
+}
+
+impl<T: Num + Ord + Copy> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet::from_iter([interval])
+    }
+}
+
+impl<T: Num + Ord + Copy> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item=Interval<T>>>(iter: I) -> Self {
+        let mut res = IntervalSet { intervals: iter.into_iter().collect() };
+        res.normalize();
+        res
+    }
+}
+
+impl<T: Num + Ord + Copy> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item=Interval<T>>>(&mut self, iter: I) {
+        self.intervals.extend(iter);
+        self.normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().map(|&(lo, hi)| Interval::new(lo, hi)).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::inclusive(1, 3);
+        assert_eq!(Interval::new(1, 4), a);
+        assert_eq!(3, a.len());
+        assert_eq!(0, Interval::new(4, 1).len());
+        assert_eq!(Some(Interval::new(2, 4)), a.intersection(&Interval::new(2, 9)));
+        assert_eq!(None, a.intersection(&Interval::new(4, 9)));
+        assert_eq!(vec![Interval::new(1, 2), Interval::new(3, 4)],
+                   a.difference(&Interval::new(2, 3)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_set() {
+        let a = set(&[(5, 8), (1, 3), (2, 4), (8, 10), (12, 12)]);
+        assert_eq!(&[Interval::new(1, 4), Interval::new(5, 10)], a.intervals());
+        assert_eq!(8, a.len());
+        assert!(a.contains(9) && !a.contains(4) && !a.contains(10));
+
+        let b = set(&[(0, 2), (3, 6), (9, 20)]);
+        assert_eq!(set(&[(0, 10), (9, 20)]), a.union(&b));
+        assert_eq!(set(&[(1, 2), (3, 4), (5, 6), (9, 10)]), a.intersection(&b));
+        assert_eq!(set(&[(2, 3), (6, 9)]), a.difference(&b));
+        assert_eq!(set(&[(0, 1), (4, 5), (10, 20)]), b.difference(&a));
+
+        let mut c = set(&[(0, 100)]);
+        c.remove(Interval::new(10, 20));
+        c.remove(Interval::new(30, 40));
+        assert_eq!(set(&[(0, 10), (20, 30), (40, 100)]), c);
+    }
+}
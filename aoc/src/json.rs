@@ -0,0 +1,93 @@
+//!
+//! Just enough JSON to write the runner's machine-readable output
+//!
+
+use std::fmt;
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Json {
+    Null,
+    Int(i64),
+    Str(String),
+    Object(Vec<(&'static str, Json)>),
+}
+
+/// The largest integer that a double, and so every JSON reader, holds exactly
+const MAX_SAFE_INT: i64 = (1 << 53) - 1;
+
+impl Json {
+    ///
+    /// An answer as a number when it is an integer that readers can hold exactly, else a string
+    ///
+    pub fn answer(value: &str) -> Json {
+        match value.parse::<i64>() {
+            Ok(n) if n.abs() <= MAX_SAFE_INT && n.to_string() == value => Json::Int(n),
+            _ => Json::Str(value.to_string()),
+        }
+    }
+}
+
+impl From<Option<String>> for Json {
+    fn from(s: Option<String>) -> Json {
+        s.map_or(Json::Null, Json::Str)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Int(n) => write!(f, "{}", n),
+            Json::Str(s) => write_str(f, s),
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json() {
+        let record = Json::Object(vec![
+            ("day", Json::Int(8)),
+            ("answer", Json::answer("GCPHL")),
+            ("artifact", Json::from(Some("#\"\\\n\u{1}".to_string()))),
+            ("error", Json::from(None)),
+        ]);
+        assert_eq!(r##"{"day":8,"answer":"GCPHL","artifact":"#\"\\\n\u0001","error":null}"##,
+                   record.to_string());
+
+        assert_eq!(Json::Int(-42), Json::answer("-42"));
+        assert_eq!(Json::Str("007".to_string()), Json::answer("007"));
+        assert_eq!(Json::Str("9007199254740993".to_string()), Json::answer("9007199254740993"));
+        assert_eq!(Json::Str("1,2".to_string()), Json::answer("1,2"));
+    }
+}
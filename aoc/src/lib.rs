@@ -0,0 +1,27 @@
+//! Helpers shared by all years.
+
+pub mod aabox;
+pub mod bench;
+pub mod combinatorics;
+pub mod cycle;
+pub mod error;
+pub mod examples;
+pub mod geom;
+pub mod graph;
+pub mod input;
+pub mod interval;
+pub mod json;
+pub mod matrix;
+pub mod memo;
+pub mod numtheory;
+pub mod ocr;
+pub mod orient;
+pub mod parse;
+pub mod pool;
+pub mod rational;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod verify;
@@ -0,0 +1,240 @@
+//!
+//! Dense integer matrices, for evolving counts by a fixed linear step
+//!
+//! A matrix acts on column vectors, so entry `(to, from)` is how much of bucket `from` one step
+//! adds to bucket `to`. Repeating a step `k` times is the `k`th power, found by repeated squaring
+//! in `O(n^3 log k)` time:
+//!
+//! ```
+//! use aoc::matrix::Matrix;
+//!
+//! // rabbit pairs: adults stay and breed, and the young grow up
+//! let step = Matrix::<u64>::transitions(2, [(0, 0), (0, 1), (1, 0)]);
+//! assert_eq!(Some(vec![89, 55]), step.checked_pow(10).and_then(|m| m.checked_mul_vec(&[1, 0])));
+//! assert_eq!(None, step.checked_pow(100));
+//! assert_eq!(vec![708941460, 730695249], step.pow_mod(1_000_000_000_000, 1_000_000_007).mul_vec_mod(&[1, 0], 1_000_000_007));
+//! ```
+//!
+//! Plain arithmetic gives `None` rather than wrapping once a count no longer fits. Past that, the
+//! `_mod` variants are the modular mode: they work in `Z/mZ` and never overflow, giving the count
+//! modulo `m` for any number of steps.
+//!
+
+use std::ops::{Index, IndexMut};
+
+use num::PrimInt;
+
+use crate::numtheory;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: PrimInt> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix { rows, cols, data: vec![T::zero(); rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Matrix<T> {
+        let mut res = Matrix::zeros(n, n);
+        for i in 0..n {
+            res[(i, i)] = T::one();
+        }
+        res
+    }
+
+    ///
+    /// The matrix with the given rows, which must all be the same length
+    ///
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Matrix<T> {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == cols), "rows have different lengths");
+        Matrix { rows: rows.len(), cols, data: rows.concat() }
+    }
+
+    ///
+    /// The step over `n` buckets where everything in `from` moves to `to` for each `(from, to)`
+    ///
+    /// A bucket listed more than once is copied to each target, and one that isn't listed empties.
+    ///
+    pub fn transitions<I>(n: usize, rules: I) -> Matrix<T>
+        where I: IntoIterator<Item=(usize, usize)>
+    {
+        let mut res = Matrix::zeros(n, n);
+        for (from, to) in rules {
+            res[(to, from)] = res[(to, from)] + T::one();
+        }
+        res
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    ///
+    /// The product, or `None` if an entry overflows
+    ///
+    pub fn checked_mul(&self, rhs: &Matrix<T>) -> Option<Matrix<T>> {
+        self.mul_with(rhs, |acc, a, b| acc.checked_add(&a.checked_mul(&b)?))
+    }
+
+    ///
+    /// This matrix applied to `v`, or `None` if an entry overflows
+    ///
+    pub fn checked_mul_vec(&self, v: &[T]) -> Option<Vec<T>> {
+        self.checked_mul(&Matrix::column(v)).map(|m| m.data)
+    }
+
+    ///
+    /// The `k`th power, or `None` if an entry of it or of a power of two below `k` overflows
+    ///
+    pub fn checked_pow(&self, k: u64) -> Option<Matrix<T>> {
+        self.pow_with(k, Matrix::identity(self.rows), Matrix::checked_mul)
+    }
+
+    fn column(v: &[T]) -> Matrix<T> {
+        Matrix { rows: v.len(), cols: 1, data: v.to_vec() }
+    }
+
+    fn mul_with<F>(&self, rhs: &Matrix<T>, mul_add: F) -> Option<Matrix<T>>
+        where F: Fn(T, T, T) -> Option<T>
+    {
+        assert_eq!(self.cols, rhs.rows, "multiplying {}x{} by {}x{}", self.rows, self.cols, rhs.rows, rhs.cols);
+        let mut res = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                if a.is_zero() {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    res[(i, j)] = mul_add(res[(i, j)], a, rhs[(k, j)])?;
+                }
+            }
+        }
+        Some(res)
+    }
+
+    fn pow_with<F>(&self, mut k: u64, identity: Matrix<T>, mul: F) -> Option<Matrix<T>>
+        where F: Fn(&Matrix<T>, &Matrix<T>) -> Option<Matrix<T>>
+    {
+        assert_eq!(self.rows, self.cols, "raising a {}x{} matrix to a power", self.rows, self.cols);
+        let mut res = identity;
+        let mut sq = self.clone();
+        while k > 0 {
+            if k & 1 == 1 {
+                res = mul(&res, &sq)?;
+            }
+            k >>= 1;
+            if k > 0 {
+                sq = mul(&sq, &sq)?;
+            }
+        }
+        Some(res)
+    }
+}
+
+impl Matrix<u64> {
+    ///
+    /// The product in `Z/mZ`
+    ///
+    pub fn mul_mod(&self, rhs: &Matrix<u64>, m: u64) -> Matrix<u64> {
+        self.mul_with(rhs, |acc, a, b| {
+            Some(((acc as u128 + numtheory::mulmod(a, b, m) as u128) % m as u128) as u64)
+        }).unwrap()
+    }
+
+    ///
+    /// This matrix applied to `v` in `Z/mZ`
+    ///
+    pub fn mul_vec_mod(&self, v: &[u64], m: u64) -> Vec<u64> {
+        self.mul_mod(&Matrix::column(v), m).data
+    }
+
+    ///
+    /// The `k`th power in `Z/mZ`
+    ///
+    pub fn pow_mod(&self, k: u64, m: u64) -> Matrix<u64> {
+        let mut identity = Matrix::identity(self.rows);
+        identity.data.iter_mut().for_each(|x| *x %= m);
+        self.pow_with(k, identity, |a, b| Some(a.mul_mod(b, m))).unwrap()
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(row < self.rows && col < self.cols, "({}, {}) is outside {}x{}", row, col, self.rows, self.cols);
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(row < self.rows && col < self.cols, "({}, {}) is outside {}x{}", row, col, self.rows, self.cols);
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul() {
+        let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_rows(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+        assert_eq!(Some(Matrix::from_rows(vec![vec![58, 64], vec![139, 154]])), a.checked_mul(&b));
+        assert_eq!(Some(vec![14, 32]), a.checked_mul_vec(&[1, 2, 3]));
+        assert_eq!(Some(a.clone()), Matrix::identity(2).checked_mul(&a));
+        assert_eq!(2, a.rows());
+        assert_eq!(3, a.cols());
+
+        let big = Matrix::from_rows(vec![vec![u8::MAX / 2 + 1, 1]]);
+        assert_eq!(None, big.checked_mul(&Matrix::from_rows(vec![vec![2], vec![1]])));
+        assert_eq!(Some(vec![u8::MAX]), big.checked_mul_vec(&[1, 127]));
+    }
+
+    #[test]
+    fn test_pow() {
+        let fib = Matrix::<u64>::transitions(2, [(0, 0), (0, 1), (1, 0)]);
+        assert_eq!(Some(Matrix::identity(2)), fib.checked_pow(0));
+        assert_eq!(Some(vec![12200160415121876738, 7540113804746346429]),
+                   fib.checked_pow(92).and_then(|m| m.checked_mul_vec(&[1, 0])));
+        assert_eq!(None, fib.checked_pow(93).and_then(|m| m.checked_mul_vec(&[1, 0])));
+        assert!(Matrix::<u128>::transitions(2, [(0, 0), (0, 1), (1, 0)]).checked_pow(93).is_some());
+
+        let m = 1_000_000_007;
+        let naive = (0..50).fold(Matrix::identity(2), |acc, _| acc.mul_mod(&fib, m));
+        assert_eq!(naive, fib.pow_mod(50, m));
+        assert_eq!(Matrix::zeros(2, 2), fib.pow_mod(0, 1));
+        assert_eq!(vec![1, 1], fib.pow_mod(u64::MAX, 2).mul_vec_mod(&[1, 1], 2));
+
+        let cycle = Matrix::<u8>::transitions(3, [(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(Some(cycle.clone()), cycle.checked_pow(1_000_000_000_000 * 3 + 1));
+    }
+
+    #[test]
+    fn test_mod() {
+        // lanternfish: timers count down, and one at 0 resets to 6 and spawns one at 8
+        let step = Matrix::<u64>::transitions(9, (1..9).map(|t| (t, t - 1)).chain([(0, 6), (0, 8)]));
+        let fish = [0, 1, 1, 2, 1, 0, 0, 0, 0];
+        let m = 1_000_000_007;
+        let total_mod = |days| step.pow_mod(days, m).mul_vec_mod(&fish, m).iter().fold(0, |acc, &n| (acc + n) % m);
+        let total = |days| step.checked_pow(days).and_then(|p| p.checked_mul_vec(&fish)).map(|v| v.iter().sum::<u64>());
+
+        assert_eq!(Some(26984457539), total(256));
+        assert_eq!(26984457539 % m, total_mod(256));
+        assert_eq!(None, total(1_000_000_000_000));
+        assert_eq!(995077479, total_mod(1_000_000_000_000));
+        assert_eq!(vec![0; 9], step.pow_mod(5, 1).mul_vec_mod(&fish, 1));
+    }
+}
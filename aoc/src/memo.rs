@@ -0,0 +1,313 @@
+//!
+//! Caches for recursive counting, and tables for filling in the same answers bottom-up
+//!
+//! A `Memo` remembers the value of each state it has solved, so a recursion that reaches the same
+//! state many ways solves it once. The recursion gets a callback for its subproblems, which can
+//! capture whatever the closure needs:
+//!
+//! ```
+//! use aoc::memo::{self, Memo};
+//!
+//! // the ways to climb n stairs one or two at a time
+//! let mut memo = Memo::new();
+//! let ways = memo.solve(80_u64, &|&n, ways: &mut dyn FnMut(u64) -> u64| {
+//!     if n < 2 { 1 } else { ways(n - 1) + ways(n - 2) }
+//! });
+//! assert_eq!(37889062373143906, ways);
+//! assert_eq!((81, 78), (memo.stats().misses, memo.stats().hits));
+//!
+//! let table = memo::table(81, |n, ways: &[u64]| if n < 2 { 1 } else { ways[n - 1] + ways[n - 2] });
+//! assert_eq!(ways, table[80]);
+//! ```
+//!
+//! When the states don't fit in memory, `Memo::bounded` stops remembering new states once full,
+//! and `Memo::lru` forgets the least recently used state to make room.
+//!
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+
+///
+/// Which states a `Memo` keeps
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Policy {
+    Unbounded,
+    /// keep the first states up to a capacity
+    Bounded(usize),
+    /// keep the most recently used states up to a capacity
+    Lru(usize),
+}
+
+///
+/// How well a `Memo` has been doing
+///
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl Stats {
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} hits, {} misses, {} evictions ({:.1}% hit rate)",
+               self.hits, self.misses, self.evictions, 100.0 * self.hit_rate())
+    }
+}
+
+///
+/// Values remembered by state
+///
+pub struct Memo<K, V> {
+    // each value with when it was last used
+    entries: HashMap<K, (V, u64)>,
+    // the states by when they were last used, kept for `Policy::Lru` only
+    order: BTreeMap<u64, K>,
+    policy: Policy,
+    tick: u64,
+    stats: Stats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo::with_policy(Policy::Unbounded)
+    }
+
+    pub fn bounded(capacity: usize) -> Memo<K, V> {
+        Memo::with_policy(Policy::Bounded(capacity))
+    }
+
+    pub fn lru(capacity: usize) -> Memo<K, V> {
+        Memo::with_policy(Policy::Lru(capacity))
+    }
+
+    pub fn with_policy(policy: Policy) -> Memo<K, V> {
+        Memo { entries: HashMap::new(), order: BTreeMap::new(), policy, tick: 0, stats: Stats::default() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    ///
+    /// The value remembered for `key`, counted as a hit or a miss
+    ///
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        match self.entries.get_mut(key) {
+            Some((value, used)) => {
+                self.stats.hits += 1;
+                if let Policy::Lru(_) = self.policy {
+                    let key = self.order.remove(used).unwrap();
+                    self.order.insert(self.tick, key);
+                }
+                *used = self.tick;
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    ///
+    /// Remember `value` for `key`, if the policy has room for it
+    ///
+    pub fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        if let Some((old, used)) = self.entries.get_mut(&key) {
+            *old = value;
+            if let Policy::Lru(_) = self.policy {
+                self.order.remove(used);
+                self.order.insert(self.tick, key);
+            }
+            *used = self.tick;
+            return;
+        }
+        match self.policy {
+            Policy::Unbounded => {}
+            Policy::Bounded(capacity) => {
+                if self.entries.len() >= capacity {
+                    return;
+                }
+            }
+            Policy::Lru(capacity) => {
+                if capacity == 0 {
+                    return;
+                }
+                if self.entries.len() >= capacity {
+                    let (_, oldest) = self.order.pop_first().unwrap();
+                    self.entries.remove(&oldest);
+                    self.stats.evictions += 1;
+                }
+                self.order.insert(self.tick, key.clone());
+            }
+        }
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    ///
+    /// The value for `key`, from `f` if it isn't remembered
+    ///
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f();
+        self.insert(key, value.clone());
+        value
+    }
+
+    ///
+    /// The value of `key` by the recursion `f`, which is given the state and a callback for the
+    /// value of any other state, each solved at most once while it's remembered
+    ///
+    /// The recursion must reach a base case without the callback, and goes as deep on the stack as
+    /// the longest chain of states not yet remembered.
+    ///
+    pub fn solve<F>(&mut self, key: K, f: &F) -> V
+        where F: Fn(&K, &mut dyn FnMut(K) -> V) -> V
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(&key, &mut |next| self.solve(next, f));
+        self.insert(key, value.clone());
+        value
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+///
+/// The first `len` values, each from `f` given its index and the values before it
+///
+pub fn table<V, F>(len: usize, mut f: F) -> Vec<V>
+    where F: FnMut(usize, &[V]) -> V
+{
+    let mut table = Vec::with_capacity(len);
+    for i in 0..len {
+        let value = f(i, &table);
+        table.push(value);
+    }
+    table
+}
+
+///
+/// The values of `keys` taken in order, each from `f` given the key and the values before it
+///
+pub fn table_by_key<K, V, I, F>(keys: I, mut f: F) -> HashMap<K, V>
+    where
+        K: Hash + Eq,
+        I: IntoIterator<Item=K>,
+        F: FnMut(&K, &HashMap<K, V>) -> V,
+{
+    let mut table = HashMap::new();
+    for key in keys {
+        let value = f(&key, &table);
+        table.insert(key, value);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        // binomial coefficients, each solved once
+        let calls = Cell::new(0);
+        let choose = |&(n, k): &(u64, u64), choose: &mut dyn FnMut((u64, u64)) -> u64| {
+            calls.set(calls.get() + 1);
+            if k == 0 || k == n { 1 } else { choose((n - 1, k - 1)) + choose((n - 1, k)) }
+        };
+        let mut memo = Memo::new();
+        assert_eq!(155117520, memo.solve((30, 15), &choose));
+        assert_eq!(memo.len() as u64, calls.get());
+        assert_eq!(memo.len() as u64, memo.stats().misses);
+
+        let before = memo.stats();
+        assert_eq!(10, memo.solve((5, 2), &choose));
+        assert_eq!(Stats { hits: before.hits + 1, ..before }, memo.stats());
+
+        // forgetting states costs more calls, but not the answer
+        let mut small = Memo::lru(8);
+        calls.set(0);
+        assert_eq!(155117520, small.solve((30, 15), &choose));
+        assert_eq!(8, small.len());
+        assert!(calls.get() > memo.len() as u64);
+        assert!(small.stats().evictions > 0);
+
+        let mut none = Memo::bounded(0);
+        assert_eq!(252, none.solve((10, 5), &choose));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_policy() {
+        let mut lru = Memo::lru(2);
+        lru.insert('a', 1);
+        lru.insert('b', 2);
+        assert_eq!(Some(1), lru.get(&'a'));
+        lru.insert('c', 3);
+        assert_eq!(None, lru.get(&'b'));
+        assert_eq!(Some(1), lru.get(&'a'));
+        assert_eq!(Some(3), lru.get(&'c'));
+        lru.insert('a', 4);
+        lru.insert('d', 5);
+        assert_eq!((Some(4), None), (lru.get(&'a'), lru.get(&'c')));
+        assert_eq!(Stats { hits: 4, misses: 2, evictions: 2 }, lru.stats());
+        assert_eq!("4 hits, 2 misses, 2 evictions (66.7% hit rate)", lru.stats().to_string());
+
+        let mut bounded = Memo::bounded(2);
+        for (i, c) in "abc".chars().enumerate() {
+            bounded.insert(c, i);
+        }
+        assert_eq!((Some(0), Some(1), None), (bounded.get(&'a'), bounded.get(&'b'), bounded.get(&'c')));
+        assert_eq!(2, bounded.get_or_insert_with('z', || 2));
+        assert_eq!(2, bounded.len());
+    }
+
+    #[test]
+    fn test_table() {
+        // the ways to make change for 100 from 1, 5, 10, 25 and 50
+        let mut ways = table(101, |i, _: &[u64]| if i == 0 { 1 } else { 0 });
+        for coin in [1, 5, 10, 25, 50] {
+            ways = table(101, |i, prev| ways[i] + if i >= coin { prev[i - coin] } else { 0 });
+        }
+        assert_eq!(292, ways[100]);
+
+        let fib = table_by_key(0..=90, |&n: &u64, fib| {
+            if n < 2 { n } else { fib[&(n - 1)] + fib[&(n - 2)] }
+        });
+        assert_eq!(2880067194370816120, fib[&90]);
+        assert_eq!(Vec::<u8>::new(), table(0, |_, _| unreachable!()));
+    }
+}
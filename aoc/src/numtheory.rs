@@ -0,0 +1,327 @@
+//!
+//! Divisibility and modular arithmetic
+//!
+//! The Chinese Remainder Theorem finds when a set of cycles line up, such as buses leaving at
+//! offsets from each other:
+//!
+//! ```
+//! use aoc::numtheory;
+//!
+//! // t = 0 (mod 7), t + 1 = 0 (mod 13)
+//! assert_eq!(Some((77, 91)), numtheory::crt([(0, 7), (-1, 13)]));
+//! assert_eq!(None, numtheory::crt([(0, 4), (1, 6)]));
+//! ```
+//!
+//! `discrete_log` undoes `powmod`, splitting the problem by the factors of the order of the base
+//! (Pohlig–Hellman) and solving each with baby-step giant-step:
+//!
+//! ```
+//! use aoc::numtheory;
+//!
+//! assert_eq!(5764801, numtheory::powmod(7, 8, 20201227));
+//! assert_eq!(Some(8), numtheory::discrete_log(7, 5764801, 20201227));
+//! ```
+//!
+
+use std::collections::HashMap;
+
+use num::{Integer, Signed};
+
+///
+/// The greatest common divisor, which is never negative and is 0 only when both are
+///
+pub fn gcd<T: Integer + Copy>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != T::zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+///
+/// The least common multiple, which is never negative and is 0 when either is
+///
+pub fn lcm<T: Integer + Copy>(a: T, b: T) -> T {
+    if a == T::zero() || b == T::zero() {
+        return T::zero();
+    }
+    abs(a / gcd(a, b) * b)
+}
+
+///
+/// `(g, x, y)` where `g = gcd(a, b)` and `ax + by = g`
+///
+pub fn ext_gcd<T: Integer + Signed + Copy>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::one(), T::zero());
+    let (mut y0, mut y1) = (T::zero(), T::one());
+    while r1 != T::zero() {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0.is_negative() {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+///
+/// The `x` in `0..m` with `ax = 1 (mod m)`, if `a` and `m` are coprime
+///
+pub fn mod_inv<T: Integer + Signed + Copy>(a: T, m: T) -> Option<T> {
+    if !m.is_positive() {
+        return None;
+    }
+    let (g, x, _) = ext_gcd(a.mod_floor(&m), m);
+    (g == T::one()).then(|| x.mod_floor(&m))
+}
+
+///
+/// `a * b (mod m)` without overflowing
+///
+/// Panics if `m` is zero.
+///
+pub fn mulmod(a: u64, b: u64, m: u64) -> u64 {
+    assert!(m > 0, "mulmod by a zero modulus");
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+///
+/// `base ^ exp (mod m)` by repeated squaring
+///
+/// Panics if `m` is zero.
+///
+pub fn powmod(base: u64, mut exp: u64, m: u64) -> u64 {
+    assert!(m > 0, "powmod by a zero modulus");
+    let mut res = 1 % m;
+    let mut base = base % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = mulmod(res, base, m);
+        }
+        base = mulmod(base, base, m);
+        exp >>= 1;
+    }
+    res
+}
+
+///
+/// The `x` with `x = a (mod m)` for every `(a, m)`, as `(x, lcm)` where the solutions are
+/// `x + k * lcm` and `0 <= x < lcm`
+///
+/// The moduli needn't be coprime. `None` if the congruences contradict each other, or a modulus
+/// isn't positive.
+///
+pub fn crt<T, I>(congruences: I) -> Option<(T, T)>
+    where
+        T: Integer + Signed + Copy,
+        I: IntoIterator<Item=(T, T)>,
+{
+    congruences.into_iter().try_fold((T::zero(), T::one()), |(a1, m1), (a2, m2)| {
+        if !m2.is_positive() {
+            return None;
+        }
+        let (g, p, _) = ext_gcd(m1, m2);
+        let diff = a2 - a1;
+        if diff % g != T::zero() {
+            return None;
+        }
+        // m1 * k = diff (mod m2), where m1 / g is invertible mod m2 / g
+        let m2_g = m2 / g;
+        let k = ((diff / g).mod_floor(&m2_g) * p.mod_floor(&m2_g)).mod_floor(&m2_g);
+        let lcm = m1 * m2_g;
+        Some(((a1 + m1 * k).mod_floor(&lcm), lcm))
+    })
+}
+
+///
+/// The prime factors of `n` with their multiplicities, in increasing order, by trial division
+///
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut res = vec![];
+    let mut p = 2;
+    while p <= n / p {
+        if n.is_multiple_of(p) {
+            let mut e = 0;
+            while n.is_multiple_of(p) {
+                n /= p;
+                e += 1;
+            }
+            res.push((p, e));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        res.push((n, 1));
+    }
+    res
+}
+
+///
+/// The number of `1..=n` coprime to `n`
+///
+pub fn totient(n: u64) -> u64 {
+    factorize(n).iter().fold(n, |acc, &(p, _)| acc / p * (p - 1))
+}
+
+///
+/// The smallest `k > 0` with `g^k = 1 (mod m)`, if `g` and `m` are coprime and `m` is positive
+///
+pub fn order(g: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    if m == 1 {
+        return Some(1);
+    }
+    if gcd(g, m) != 1 {
+        return None;
+    }
+    let mut order = totient(m);
+    for (p, _) in factorize(order) {
+        while order.is_multiple_of(p) && powmod(g, order / p, m) == 1 {
+            order /= p;
+        }
+    }
+    Some(order)
+}
+
+///
+/// The smallest `x < n` with `g^x = h (mod m)` by baby-step giant-step, in `O(sqrt(n))` time and
+/// space, where `g` is invertible and `m` is positive
+///
+pub fn bsgs(g: u64, h: u64, m: u64, n: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let step = (n as f64).sqrt().ceil() as u64;
+    let mut baby = HashMap::with_capacity(step as usize);
+    let mut x = 1 % m;
+    for j in 0..step {
+        baby.entry(x).or_insert(j);
+        x = mulmod(x, g, m);
+    }
+    let g_inv = mod_inv(g as i128, m as i128)? as u64;
+    let giant = powmod(g_inv, step, m);
+    let mut y = h % m;
+    for i in 0..step {
+        if let Some(&j) = baby.get(&y) {
+            return Some(i * step + j).filter(|&x| x < n);
+        }
+        y = mulmod(y, giant, m);
+    }
+    None
+}
+
+///
+/// The smallest `x` with `g^x = h (mod m)`, if there is one and `g` and `m` are coprime
+///
+/// Fast when the order of `g` has only small prime factors, as the work is the square root of
+/// the largest. `m` is factorized by trial division.
+///
+pub fn discrete_log(g: u64, h: u64, m: u64) -> Option<u64> {
+    let n = order(g, m)?;
+    let congruences = factorize(n).into_iter().map(|(q, e)| {
+        // g_q has order q^e, and its x is found a base-q digit at a time
+        let qe = q.pow(e);
+        let (g_q, h_q) = (powmod(g, n / qe, m), powmod(h, n / qe, m));
+        let gamma = powmod(g_q, qe / q, m);
+        let g_q_inv = mod_inv(g_q as i128, m as i128)? as u64;
+        let mut x = 0;
+        for k in 0..e {
+            let h_k = powmod(mulmod(powmod(g_q_inv, x, m), h_q, m), qe / q.pow(k + 1), m);
+            let d = bsgs(gamma, h_k, m, q)?;
+            x += d * q.pow(k);
+        }
+        Some((x as i128, qe as i128))
+    }).collect::<Option<Vec<_>>>()?;
+    let (x, _) = crt(congruences)?;
+    Some(x as u64).filter(|&x| powmod(g, x, m) == h % m)
+}
+
+fn abs<T: Integer>(a: T) -> T {
+    if a < T::zero() { T::zero() - a } else { a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(12, -18));
+        assert_eq!(5, gcd(0, 5_u64));
+        assert_eq!(0, gcd(0, 0));
+        assert_eq!(36, lcm(-12, 18));
+        assert_eq!(0, lcm(0, 18_u8));
+
+        for (a, b) in [(240, 46), (-7, 13), (0, -4), (17, 0)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(gcd(a, b), g);
+            assert_eq!(g, a * x + b * y);
+        }
+    }
+
+    #[test]
+    fn test_mod() {
+        assert_eq!(Some(5), mod_inv(3, 7));
+        assert_eq!(Some(2), mod_inv(-3, 7));
+        assert_eq!(Some(0), mod_inv(5, 1));
+        assert_eq!(None, mod_inv(4, 6));
+        assert_eq!(None, mod_inv(4, 0));
+
+        let m = 119315717514047;
+        let inv = mod_inv(2020_i128, m as i128).unwrap() as u64;
+        assert_eq!(1, mulmod(2020, inv, m));
+        assert_eq!(inv, powmod(2020, m - 2, m));
+        assert_eq!(1, powmod(0, 0, 7));
+        assert_eq!(0, powmod(3, 5, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "zero modulus")]
+    fn test_powmod_zero() {
+        powmod(3, 5, 0);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((0, 1)), crt::<i64, _>([]));
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt([(4, 6), (2, 4)]));
+        assert_eq!(Some((3, 6)), crt([(3, 6), (-3, 6)]));
+        assert_eq!(None, crt([(1, 6), (2, 4)]));
+        assert_eq!(None, crt([(1, 6), (2, 0)]));
+
+        assert_eq!(Some((3417, 4199)), crt([(0_i128, 17), (-2, 13), (-3, 19)]));
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(vec![(2, 3), (3, 1), (5, 2)], factorize(600));
+        assert_eq!(vec![(2, 1), (3, 1), (29, 1), (116099, 1)], factorize(20201226));
+        assert_eq!(160, totient(600));
+        assert_eq!(Some(6), order(3, 7));
+        assert_eq!(Some(3), order(2, 7));
+        assert_eq!(None, order(2, 8));
+        assert_eq!(None, order(1, 0));
+
+        assert_eq!(Some(4), bsgs(3, 4, 7, 6));
+        assert_eq!(None, bsgs(2, 3, 7, 3));
+        assert_eq!(None, bsgs(1, 1, 0, 3));
+
+        assert_eq!(Some(11), discrete_log(7, 17807724, 20201227));
+        assert_eq!(Some(2), discrete_log(2, 4, 7));
+        assert_eq!(None, discrete_log(2, 3, 7));
+        assert_eq!(Some(0), discrete_log(3, 1, 7));
+        assert_eq!(None, discrete_log(3, 1, 0));
+        // 2^x mod 3^10, whose group order 2 * 3^9 needs several digits per factor
+        let m = 3_u64.pow(10);
+        assert_eq!(Some(12345), discrete_log(2, powmod(2, 12345, m), m));
+        let m = 1_000_000_007;
+        assert_eq!(Some(123456789), discrete_log(5, powmod(5, 123456789, m), m));
+    }
+}
@@ -0,0 +1,289 @@
+//!
+//! Recognition of the block-letter answers that some puzzles render
+//!
+//! Two fonts are known: the 6 row font (2019 day 8, 2019 day 11, 2021 day 13) and the
+//! 10 row font used by older puzzles. The font is chosen from the height of the image, and
+//! characters are separated by blank columns.
+//!
+
+use std::fmt;
+
+type Glyph = (char, &'static [&'static str]);
+
+const SMALL_FONT: &[Glyph] = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE_FONT: &[Glyph] = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum OcrError {
+    Empty,
+    UnsupportedHeight(usize),
+    Unrecognized {
+        /// The recognised text, with `?` in place of each unknown glyph
+        text: String,
+        /// Position in `text` and rendering of each unknown glyph
+        glyphs: Vec<(usize, String)>,
+    },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no lit pixels to recognise"),
+            OcrError::UnsupportedHeight(h) =>
+                write!(f, "no font with height {} (expected 6 or 10)", h),
+            OcrError::Unrecognized { text, glyphs } => {
+                write!(f, "unrecognised glyphs in {:?}", text)?;
+                for (i, glyph) in glyphs {
+                    write!(f, "\n\nat position {}:\n{}", i, glyph)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+///
+/// Recognise the text in a grid, where `is_lit` picks out the pixels that are drawn
+///
+pub fn recognize_grid<R, T, F>(grid: &[R], is_lit: F) -> Result<String, OcrError>
+    where
+        R: AsRef<[T]>,
+        F: Fn(&T) -> bool,
+{
+    recognize(grid_pixels(grid, is_lit))
+}
+
+///
+/// Recognise the text drawn by a set of lit (x, y) points
+///
+pub fn recognize_points<I>(points: I) -> Result<String, OcrError>
+    where
+        I: IntoIterator<Item=(i64, i64)>,
+{
+    recognize(point_pixels(points)?)
+}
+
+///
+/// Draw a grid as text, with `#` for lit pixels and `.` for the rest
+///
+pub fn draw_grid<R, T, F>(grid: &[R], is_lit: F) -> String
+    where
+        R: AsRef<[T]>,
+        F: Fn(&T) -> bool,
+{
+    draw(&grid_pixels(grid, is_lit))
+}
+
+///
+/// Draw the bounding box of a set of lit (x, y) points as text, like `draw_grid`
+///
+pub fn draw_points<I>(points: I) -> Result<String, OcrError>
+    where
+        I: IntoIterator<Item=(i64, i64)>,
+{
+    Ok(draw(&point_pixels(points)?))
+}
+
+fn draw(pixels: &[Vec<bool>]) -> String {
+    pixels.iter()
+        .map(|row| row.iter().map(|&b| if b { '#' } else { '.' }).collect::<String>() + "\n")
+        .collect()
+}
+
+fn grid_pixels<R, T, F>(grid: &[R], is_lit: F) -> Vec<Vec<bool>>
+    where
+        R: AsRef<[T]>,
+        F: Fn(&T) -> bool,
+{
+    grid.iter()
+        .map(|row| row.as_ref().iter().map(&is_lit).collect())
+        .collect()
+}
+
+fn point_pixels<I>(points: I) -> Result<Vec<Vec<bool>>, OcrError>
+    where
+        I: IntoIterator<Item=(i64, i64)>,
+{
+    let points: Vec<(i64, i64)> = points.into_iter().collect();
+    let min_x = points.iter().map(|p| p.0).min().ok_or(OcrError::Empty)?;
+    let min_y = points.iter().map(|p| p.1).min().ok_or(OcrError::Empty)?;
+    let max_x = points.iter().map(|p| p.0).max().ok_or(OcrError::Empty)?;
+    let max_y = points.iter().map(|p| p.1).max().ok_or(OcrError::Empty)?;
+
+    let width = (max_x - min_x + 1) as usize;
+    let height = (max_y - min_y + 1) as usize;
+    let mut pixels = vec![vec![false; width]; height];
+    for (x, y) in points {
+        pixels[(y - min_y) as usize][(x - min_x) as usize] = true;
+    }
+    Ok(pixels)
+}
+
+///
+/// Recognise the text in a string where `#` is lit and anything else is not
+///
+pub fn recognize_str(s: &str) -> Result<String, OcrError> {
+    let pixels = s.lines()
+        .map(|line| line.chars().map(|c| c == '#').collect())
+        .collect();
+    recognize(pixels)
+}
+
+fn recognize(mut pixels: Vec<Vec<bool>>) -> Result<String, OcrError> {
+    let width = pixels.iter().map(|row| row.len()).max().unwrap_or(0);
+    for row in pixels.iter_mut() {
+        row.resize(width, false);
+    }
+
+    let is_blank_row = |row: &Vec<bool>| !row.iter().any(|&b| b);
+    let top = pixels.iter().position(|row| !is_blank_row(row)).ok_or(OcrError::Empty)?;
+    let bottom = pixels.iter().rposition(|row| !is_blank_row(row)).unwrap();
+    let pixels = &pixels[top..=bottom];
+
+    let font = match pixels.len() {
+        6 => SMALL_FONT,
+        10 => LARGE_FONT,
+        h => return Err(OcrError::UnsupportedHeight(h)),
+    };
+
+    let mut text = String::new();
+    let mut unknown = Vec::new();
+    let is_blank_col = |x: usize| !pixels.iter().any(|row| row[x]);
+    let mut x = 0;
+    while x < width {
+        if is_blank_col(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && !is_blank_col(x) {
+            x += 1;
+        }
+        let glyph = pixels.iter()
+            .map(|row| row[start..x].iter().map(|&b| if b { '#' } else { '.' }).collect::<String>())
+            .collect::<Vec<_>>();
+
+        match font.iter().find(|(_, rows)| *rows == glyph.as_slice()) {
+            Some(&(c, _)) => text.push(c),
+            None => {
+                unknown.push((text.chars().count(), glyph.join("\n")));
+                text.push('?');
+            }
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::Unrecognized { text, glyphs: unknown })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(text: &str, font: &[Glyph], spacing: usize) -> String {
+        let glyphs = text.chars()
+            .map(|c| font.iter().find(|(g, _)| *g == c).unwrap().1)
+            .collect::<Vec<_>>();
+        (0..glyphs[0].len())
+            .map(|y| glyphs.iter()
+                .map(|rows| rows[y])
+                .collect::<Vec<_>>()
+                .join(&".".repeat(spacing)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_small_font() {
+        let s = r"
+         ##   ##  ###  #  # #
+        #  # #  # #  # #  # #
+        #    #    #  # #### #
+        # ## #    ###  #  # #
+        #  # #  # #    #  # #
+         ###  ##  #    #  # ####
+        ";
+        assert_eq!(Ok("GCPHL".to_string()), recognize_str(s));
+
+        let alphabet = SMALL_FONT.iter().map(|(c, _)| *c).collect::<String>();
+        assert_eq!(Ok(alphabet.clone()), recognize_str(&render(&alphabet, SMALL_FONT, 1)));
+    }
+
+    #[test]
+    fn test_large_font() {
+        let alphabet = LARGE_FONT.iter().map(|(c, _)| *c).collect::<String>();
+        assert_eq!(Ok(alphabet.clone()), recognize_str(&render(&alphabet, LARGE_FONT, 2)));
+    }
+
+    #[test]
+    fn test_points_and_grid() {
+        let points = [(10, 5), (10, 6), (10, 7), (10, 8), (10, 9), (10, 10), (11, 10), (12, 10), (13, 10)];
+        assert_eq!(Ok("L".to_string()), recognize_points(points));
+        assert_eq!(Ok("#...\n#...\n#...\n#...\n#...\n####\n".to_string()), draw_points(points));
+
+        let grid = [[0, 1, 1, 0], [1, 0, 0, 1], [1, 0, 0, 1], [1, 0, 0, 1], [1, 0, 0, 1], [0, 1, 1, 0]];
+        assert_eq!(Ok("O".to_string()), recognize_grid(&grid, |&p| p == 1));
+        assert_eq!(".##.\n#..#\n", draw_grid(&grid[..2], |&p| p == 1));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Err(OcrError::Empty), recognize_str("   \n  "));
+        assert_eq!(Err(OcrError::UnsupportedHeight(3)), recognize_str("#\n#\n#"));
+
+        let s = r"
+        #### #  ##
+        #    #   #
+        ###   # #
+        #      #
+        #      #
+        #      #
+        ";
+        let err = OcrError::Unrecognized {
+            text: "F?".to_string(),
+            glyphs: vec![(1, "#..##\n#...#\n.#.#.\n..#..\n..#..\n..#..".to_string())],
+        };
+        assert_eq!(Err(err), recognize_str(s));
+    }
+}
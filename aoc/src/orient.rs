@@ -0,0 +1,277 @@
+//!
+//! Orientations of squares (D4) and cubes (Rot3)
+//!
+//! Both act on points about the origin, with y pointing down for D4 so that clockwise matches
+//! how grids are printed. Grids are indexed through an orientation rather than rebuilt.
+//!
+
+use std::ops::Neg;
+
+///
+/// One of the 8 symmetries of a square: an optional flip in x followed by clockwise quarter turns
+///
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
+pub struct D4 {
+    num_cw_rotations: u8,
+    is_flipped: bool,
+}
+
+impl D4 {
+    pub const IDENTITY: D4 = D4 { num_cw_rotations: 0, is_flipped: false };
+    pub const ROTATE_CW: D4 = D4 { num_cw_rotations: 1, is_flipped: false };
+    pub const FLIP_X: D4 = D4 { num_cw_rotations: 0, is_flipped: true };
+
+    pub fn new(num_cw_rotations: usize, is_flipped: bool) -> D4 {
+        D4 { num_cw_rotations: (num_cw_rotations % 4) as u8, is_flipped }
+    }
+
+    ///
+    /// All 8 orientations, the 4 rotations unflipped and then the 4 rotations flipped
+    ///
+    pub fn all() -> impl Iterator<Item=D4> {
+        [false, true].into_iter()
+            .flat_map(|is_flipped| (0..4).map(move |r| D4::new(r, is_flipped)))
+    }
+
+    pub fn num_cw_rotations(&self) -> usize {
+        self.num_cw_rotations as usize
+    }
+
+    pub fn is_flipped(&self) -> bool {
+        self.is_flipped
+    }
+
+    ///
+    /// The orientation equivalent to applying `self` and then `other`
+    ///
+    pub fn then(&self, other: D4) -> D4 {
+        // flipping reverses the direction of the rotations before it
+        let r = if other.is_flipped {
+            other.num_cw_rotations + 4 - self.num_cw_rotations
+        } else {
+            other.num_cw_rotations + self.num_cw_rotations
+        };
+        D4 {
+            num_cw_rotations: r % 4,
+            is_flipped: self.is_flipped != other.is_flipped,
+        }
+    }
+
+    pub fn inverse(&self) -> D4 {
+        if self.is_flipped {
+            *self
+        } else {
+            D4::new(4 - self.num_cw_rotations as usize, false)
+        }
+    }
+
+    pub fn apply<T>(&self, [x, y]: [T; 2]) -> [T; 2]
+        where
+            T: Neg<Output=T> + Copy,
+    {
+        let (mut x, mut y) = if self.is_flipped { (-x, y) } else { (x, y) };
+        for _ in 0..self.num_cw_rotations {
+            (x, y) = (-y, x);
+        }
+        [x, y]
+    }
+
+    ///
+    /// (width, height) of a width x height grid after applying this orientation
+    ///
+    pub fn dims(&self, width: usize, height: usize) -> (usize, usize) {
+        if self.num_cw_rotations.is_multiple_of(2) {
+            (width, height)
+        } else {
+            (height, width)
+        }
+    }
+
+    ///
+    /// Where (x, y) of a width x height grid ends up after applying this orientation
+    ///
+    pub fn map_index(&self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        self.index_map(width, height)(x, y)
+    }
+
+    ///
+    /// The (x, y) of the original width x height grid that is shown at (x, y) after applying
+    /// this orientation
+    ///
+    pub fn source_index(&self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        self.source_map(width, height)(x, y)
+    }
+
+    ///
+    /// `map_index` for a fixed grid size, worked out once to use on every cell
+    ///
+    pub fn index_map(&self, width: usize, height: usize) -> impl Fn(usize, usize) -> (usize, usize) {
+        // where the unit steps in x and y go, and the shift bringing the grid back to the origin
+        let [ex, ey] = [self.apply([1, 0]), self.apply([0, 1])];
+        let (w, h) = (width as i64 - 1, height as i64 - 1);
+        let min_x = (ex[0] * w).min(0) + (ey[0] * h).min(0);
+        let min_y = (ex[1] * w).min(0) + (ey[1] * h).min(0);
+        move |x, y| {
+            let (x, y) = (x as i64, y as i64);
+            ((ex[0] * x + ey[0] * y - min_x) as usize, (ex[1] * x + ey[1] * y - min_y) as usize)
+        }
+    }
+
+    ///
+    /// `source_index` for a fixed grid size, worked out once to use on every cell
+    ///
+    pub fn source_map(&self, width: usize, height: usize) -> impl Fn(usize, usize) -> (usize, usize) {
+        let (width, height) = self.dims(width, height);
+        self.inverse().index_map(width, height)
+    }
+
+    pub fn apply_grid<T: Clone>(&self, grid: &[Vec<T>]) -> Vec<Vec<T>> {
+        let (height, width) = (grid.len(), grid.first().map_or(0, |row| row.len()));
+        let (new_width, new_height) = self.dims(width, height);
+        let source = self.source_map(width, height);
+        (0..new_height)
+            .map(|y| (0..new_width)
+                .map(|x| {
+                    let (x, y) = source(x, y);
+                    grid[y][x].clone()
+                })
+                .collect())
+            .collect()
+    }
+}
+
+///
+/// One of the 24 rotations of a cube, as a signed permutation of the axes
+///
+/// Component i of a rotated point is `signs[i]` times component `axes[i]` of the original.
+///
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub struct Rot3 {
+    axes: [u8; 3],
+    signs: [i8; 3],
+}
+
+impl Rot3 {
+    pub const IDENTITY: Rot3 = Rot3 { axes: [0, 1, 2], signs: [1, 1, 1] };
+    /// Quarter turn about the x axis, taking y to z
+    pub const ROTATE_X: Rot3 = Rot3 { axes: [0, 2, 1], signs: [1, -1, 1] };
+    /// Quarter turn about the y axis, taking z to x
+    pub const ROTATE_Y: Rot3 = Rot3 { axes: [2, 1, 0], signs: [1, 1, -1] };
+    /// Quarter turn about the z axis, taking x to y
+    pub const ROTATE_Z: Rot3 = Rot3 { axes: [1, 0, 2], signs: [-1, 1, 1] };
+
+    pub fn all() -> impl Iterator<Item=Rot3> {
+        const PERMUTATIONS: [([u8; 3], i8); 6] = [
+            ([0, 1, 2], 1), ([1, 2, 0], 1), ([2, 0, 1], 1),
+            ([0, 2, 1], -1), ([2, 1, 0], -1), ([1, 0, 2], -1),
+        ];
+        PERMUTATIONS.into_iter()
+            .flat_map(|(axes, parity)| (0..8).map(move |bits| {
+                let mut signs = [1, 1, 1];
+                for (i, sign) in signs.iter_mut().enumerate() {
+                    if bits & (1 << i) != 0 { *sign = -1; }
+                }
+                Rot3 { axes, signs }
+            }).filter(move |rot| rot.signs.iter().product::<i8>() == parity))
+    }
+
+    ///
+    /// The rotation equivalent to applying `self` and then `other`
+    ///
+    pub fn then(&self, other: Rot3) -> Rot3 {
+        let mut res = Rot3::IDENTITY;
+        for i in 0..3 {
+            let j = other.axes[i] as usize;
+            res.axes[i] = self.axes[j];
+            res.signs[i] = other.signs[i] * self.signs[j];
+        }
+        res
+    }
+
+    pub fn inverse(&self) -> Rot3 {
+        let mut res = Rot3::IDENTITY;
+        for i in 0..3 {
+            let j = self.axes[i] as usize;
+            res.axes[j] = i as u8;
+            res.signs[j] = self.signs[i];
+        }
+        res
+    }
+
+    pub fn apply<T>(&self, v: [T; 3]) -> [T; 3]
+        where
+            T: Neg<Output=T> + Copy,
+    {
+        [0, 1, 2].map(|i| {
+            let c = v[self.axes[i] as usize];
+            if self.signs[i] < 0 { -c } else { c }
+        })
+    }
+}
+
+impl Default for Rot3 {
+    fn default() -> Self {
+        Rot3::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_d4() {
+        let all = D4::all().collect::<Vec<_>>();
+        assert_eq!(8, all.iter().collect::<HashSet<_>>().len());
+        assert_eq!([0, 1], D4::ROTATE_CW.apply([1, 0]));
+        assert_eq!([-1, 0], D4::FLIP_X.apply([1, 0]));
+
+        let p = [2, 5];
+        for &a in all.iter() {
+            assert_eq!(D4::IDENTITY, a.then(a.inverse()));
+            for &b in all.iter() {
+                assert_eq!(b.apply(a.apply(p)), a.then(b).apply(p));
+            }
+        }
+    }
+
+    #[test]
+    fn test_d4_grid() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(vec![vec![4, 1], vec![5, 2], vec![6, 3]], D4::ROTATE_CW.apply_grid(&grid));
+        assert_eq!(vec![vec![3, 2, 1], vec![6, 5, 4]], D4::FLIP_X.apply_grid(&grid));
+
+        for o in D4::all() {
+            let rotated = o.apply_grid(&grid);
+            assert_eq!(grid, o.inverse().apply_grid(&rotated));
+            for (y, row) in grid.iter().enumerate() {
+                for (x, &c) in row.iter().enumerate() {
+                    let (nx, ny) = o.map_index(x, y, 3, 2);
+                    assert_eq!(c, rotated[ny][nx]);
+                    assert_eq!((x, y), o.source_index(nx, ny, 3, 2));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rot3() {
+        let all = Rot3::all().collect::<Vec<_>>();
+        assert_eq!(24, all.iter().collect::<HashSet<_>>().len());
+        assert_eq!([0, 0, 1], Rot3::ROTATE_X.apply([0, 1, 0]));
+        assert_eq!([1, 0, 0], Rot3::ROTATE_Y.apply([0, 0, 1]));
+        assert_eq!([0, 1, 0], Rot3::ROTATE_Z.apply([1, 0, 0]));
+
+        let p = [1, 2, 3];
+        let images = all.iter().map(|r| r.apply(p)).collect::<HashSet<_>>();
+        assert_eq!(24, images.len());
+        for &a in all.iter() {
+            assert_eq!(Rot3::IDENTITY, a.then(a.inverse()));
+            for &b in all.iter() {
+                assert_eq!(b.apply(a.apply(p)), a.then(b).apply(p));
+            }
+        }
+    }
+}
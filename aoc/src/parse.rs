@@ -0,0 +1,422 @@
+//!
+//! Parser combinators for puzzle input
+//!
+//! A parser takes what's left of the input and returns a value with what's left after it. A
+//! failure keeps the suffix of the input it happened at, which `all` turns into an
+//! `AocError::Parse` pointing at the line and column:
+//!
+//! ```
+//! use aoc::error::AocError;
+//! use aoc::parse::{self, int, lines, tag, Parser};
+//!
+//! assert_eq!(vec![3, -1, 4], parse::ints::<i32>("3,-1,4\n").unwrap());
+//!
+//! let moves = lines(tag("up ").map(|_| 0).or(tag("down ").map(|_| 1)).and(int::<i32>()));
+//! assert_eq!(vec![(0, 3), (1, 5)], parse::all("up 3\ndown 5", moves.by_ref()).unwrap());
+//! assert!(matches!(parse::all("up 3\nleft 5", moves), Err(AocError::Parse { line: 2, column: 1, .. })));
+//! ```
+//!
+//! Lines are trimmed and blank lines skipped, as puzzle examples are usually indented raw strings.
+//! Sections of the input, such as the rules then the messages, are separated by blank lines and
+//! parsed with `block`.
+//!
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::error::AocError;
+
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+///
+/// What was `expected` at `at`, which is a suffix of the input
+///
+#[derive(Debug, Clone)]
+pub struct Failure<'a> {
+    pub at: &'a str,
+    pub expected: Cow<'static, str>,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new<E: Into<Cow<'static, str>>>(at: &'a str, expected: E) -> Failure<'a> {
+        Failure { at, expected: expected.into() }
+    }
+
+    /// The failure that got further, or both expectations if they failed at the same place
+    fn or(self, other: Failure<'a>) -> Failure<'a> {
+        match self.at.len().cmp(&other.at.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => Failure::new(self.at, format!("{} or {}", self.expected, other.expected)),
+        }
+    }
+
+    pub fn into_error(self, input: &str) -> AocError {
+        AocError::parse(input, self.at, format!("expected {}", self.expected))
+    }
+}
+
+pub trait Parser<'a, T> {
+    fn parse(&self, s: &'a str) -> PResult<'a, T>;
+
+    fn by_ref(&self) -> impl Parser<'a, T> + '_ {
+        move |s: &'a str| self.parse(s)
+    }
+
+    fn map<U, F: Fn(T) -> U>(self, f: F) -> impl Parser<'a, U>
+        where Self: Sized
+    {
+        move |s: &'a str| self.parse(s).map(|(t, rest)| (f(t), rest))
+    }
+
+    ///
+    /// Map with `f`, failing with `expected` at the start of the parse if it returns `None`
+    ///
+    fn map_opt<U, F: Fn(T) -> Option<U>>(self, expected: &'static str, f: F) -> impl Parser<'a, U>
+        where Self: Sized
+    {
+        move |s: &'a str| {
+            let (t, rest) = self.parse(s)?;
+            f(t).map(|u| (u, rest)).ok_or_else(|| Failure::new(s, expected))
+        }
+    }
+
+    fn and<U, P: Parser<'a, U>>(self, next: P) -> impl Parser<'a, (T, U)>
+        where Self: Sized
+    {
+        move |s: &'a str| {
+            let (t, rest) = self.parse(s)?;
+            let (u, rest) = next.parse(rest)?;
+            Ok(((t, u), rest))
+        }
+    }
+
+    /// Parse `next` after this, keeping only this
+    fn skip<U, P: Parser<'a, U>>(self, next: P) -> impl Parser<'a, T>
+        where Self: Sized
+    {
+        move |s: &'a str| {
+            let (t, rest) = self.parse(s)?;
+            let (_, rest) = next.parse(rest)?;
+            Ok((t, rest))
+        }
+    }
+
+    /// Parse `next` after this, keeping only `next`
+    fn then<U, P: Parser<'a, U>>(self, next: P) -> impl Parser<'a, U>
+        where Self: Sized
+    {
+        move |s: &'a str| {
+            let (_, rest) = self.parse(s)?;
+            next.parse(rest)
+        }
+    }
+
+    fn or<P: Parser<'a, T>>(self, other: P) -> impl Parser<'a, T>
+        where Self: Sized
+    {
+        move |s: &'a str| self.parse(s).or_else(|e1| other.parse(s).map_err(|e2| e1.or(e2)))
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+    where F: Fn(&'a str) -> PResult<'a, T>
+{
+    fn parse(&self, s: &'a str) -> PResult<'a, T> {
+        self(s)
+    }
+}
+
+///
+/// Run `parser` over all of `input` bar surrounding whitespace
+///
+pub fn all<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, AocError> {
+    let (t, rest) = parser.parse(input.trim()).map_err(|e| e.into_error(input))?;
+    if !rest.is_empty() {
+        return Err(Failure::new(rest, "the end of the input").into_error(input));
+    }
+    Ok(t)
+}
+
+///
+/// A comma-separated list of integers, such as an Intcode program
+///
+pub fn ints<T: FromStr>(input: &str) -> Result<Vec<T>, AocError> {
+    all(input, sep_by1(int(), tag(",").skip(spaces())))
+}
+
+pub fn tag<'a>(tag: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| match s.strip_prefix(tag) {
+        Some(rest) => Ok((&s[..tag.len()], rest)),
+        None => Err(Failure::new(s, format!("{:?}", tag))),
+    }
+}
+
+///
+/// The longest prefix whose chars match `pred`, which can be empty
+///
+pub fn take_while<'a>(pred: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        let end = s.find(|c| !pred(c)).unwrap_or(s.len());
+        Ok(s.split_at(end))
+    }
+}
+
+///
+/// The longest prefix whose chars match `pred`, failing with `expected` if it's empty
+///
+pub fn take_while1<'a>(expected: &'static str, pred: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| match take_while(&pred).parse(s)? {
+        ("", _) => Err(Failure::new(s, expected)),
+        res => Ok(res),
+    }
+}
+
+/// Spaces and tabs, but not newlines
+pub fn spaces<'a>() -> impl Parser<'a, &'a str> {
+    take_while(|c| c == ' ' || c == '\t')
+}
+
+///
+/// An integer with an optional sign, parsed as a `T`
+///
+pub fn int<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+        let end = s.len() - digits.len() + digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+        let (num, rest) = s.split_at(end);
+        if num.len() == s.len() - digits.len() {
+            return Err(Failure::new(s, "a number"));
+        }
+        match num.parse() {
+            Ok(t) => Ok((t, rest)),
+            Err(_) => Err(Failure::new(s, format!("a number that fits in {}", std::any::type_name::<T>()))),
+        }
+    }
+}
+
+///
+/// A letter or underscore followed by letters, digits and underscores
+///
+pub fn ident<'a>() -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        if !s.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return Err(Failure::new(s, "a name"));
+        }
+        take_while(|c| c.is_alphanumeric() || c == '_').parse(s)
+    }
+}
+
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |s: &'a str| match parser.parse(s) {
+        Ok((t, rest)) => Ok((Some(t), rest)),
+        Err(_) => Ok((None, s)),
+    }
+}
+
+///
+/// `parser` repeated zero or more times, until it fails or stops consuming input
+///
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut s: &'a str| {
+        let mut res = vec![];
+        while let Ok((t, rest)) = parser.parse(s) {
+            if rest.len() == s.len() {
+                break;
+            }
+            res.push(t);
+            s = rest;
+        }
+        Ok((res, s))
+    }
+}
+
+///
+/// Zero or more of `item` separated by `sep`
+///
+pub fn sep_by<'a, T, U>(item: impl Parser<'a, T>, sep: impl Parser<'a, U>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| match item.parse(s) {
+        Ok((first, s)) => sep_by_rest(first, s, &item, &sep),
+        Err(_) => Ok((vec![], s)),
+    }
+}
+
+///
+/// One or more of `item` separated by `sep`
+///
+pub fn sep_by1<'a, T, U>(item: impl Parser<'a, T>, sep: impl Parser<'a, U>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let (first, s) = item.parse(s)?;
+        sep_by_rest(first, s, &item, &sep)
+    }
+}
+
+fn sep_by_rest<'a, T, U>(first: T, mut s: &'a str, item: &impl Parser<'a, T>, sep: &impl Parser<'a, U>)
+                         -> PResult<'a, Vec<T>> {
+    let mut res = vec![first];
+    while let Ok((_, rest)) = sep.parse(s) {
+        let (t, rest) = item.parse(rest)?;
+        res.push(t);
+        s = rest;
+    }
+    Ok((res, s))
+}
+
+///
+/// `item` on each non-blank line to the end of the input, which it has to use all of
+///
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |s: &'a str| {
+        let items = s.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| whole(&item, line, "the end of the line"))
+            .collect::<Result<_, _>>()?;
+        Ok((items, &s[s.len()..]))
+    }
+}
+
+///
+/// `parser` on the lines up to the next blank line, which it has to use all of, skipping the
+/// blank lines after them
+///
+pub fn block<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |s: &'a str| {
+        let (block, rest) = split_block(s.trim_start());
+        Ok((whole(&parser, block.trim_end(), "the end of the section")?, rest.trim_start()))
+    }
+}
+
+///
+/// `record` on each group of lines between blank lines, to the end of the input
+///
+pub fn records<'a, T>(record: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let record = block(record);
+    move |mut s: &'a str| {
+        let mut res = vec![];
+        while !s.trim().is_empty() {
+            let (t, rest) = record.parse(s)?;
+            res.push(t);
+            s = rest;
+        }
+        Ok((res, s))
+    }
+}
+
+///
+/// Whitespace-separated `key<sep>value` fields, such as `ecl:gry pid:860033327`
+///
+pub fn key_values<'a, K, V>(key: impl Parser<'a, K>, sep: &'static str, value: impl Parser<'a, V>)
+                            -> impl Parser<'a, Vec<(K, V)>> {
+    let field = key.skip(tag(sep)).and(value);
+    sep_by1(field, take_while1("whitespace", char::is_whitespace))
+}
+
+///
+/// A rectangle of chars, one row per line, each turned into a cell by `cell`
+///
+pub fn grid<'a, T>(expected: &'static str, cell: impl Fn(char) -> Option<T>) -> impl Parser<'a, Vec<Vec<T>>> {
+    let row = move |s: &'a str| -> PResult<'a, Vec<T>> {
+        let mut res = vec![];
+        for (i, c) in s.char_indices() {
+            match cell(c) {
+                Some(t) => res.push(t),
+                None => return Ok((res, &s[i..])),
+            }
+        }
+        Ok((res, &s[s.len()..]))
+    };
+    move |s: &'a str| {
+        let (block, rest) = split_block(s);
+        let rows = block.lines().map(str::trim).filter(|line| !line.is_empty()).collect::<Vec<_>>();
+        let mut res = Vec::with_capacity(rows.len());
+        for line in rows {
+            let cells = whole(&row, line, expected)?;
+            if let Some(first) = res.first().map(Vec::len).filter(|&width| width != cells.len()) {
+                return Err(Failure::new(line, format!("a row of {} like the first", first)));
+            }
+            res.push(cells);
+        }
+        if res.is_empty() {
+            return Err(Failure::new(s, "a grid"));
+        }
+        Ok((res, rest))
+    }
+}
+
+/// Run `parser` on `s`, failing with `expected` where it stops if that's before the end
+fn whole<'a, T>(parser: &impl Parser<'a, T>, s: &'a str, expected: &'static str) -> Result<T, Failure<'a>> {
+    match parser.parse(s)? {
+        (t, "") => Ok(t),
+        (_, rest) => Err(Failure::new(rest, expected)),
+    }
+}
+
+/// Split `s` before its first blank line that isn't at the start
+fn split_block(s: &str) -> (&str, &str) {
+    let mut end = 0;
+    for line in s.split_inclusive('\n') {
+        if end > 0 && line.trim().is_empty() {
+            break;
+        }
+        end += line.len();
+    }
+    s.split_at(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(vec![1, -2, 3], ints::<i64>("1,-2, +3").unwrap());
+        assert!(matches!(ints::<u8>("1,256"), Err(AocError::Parse { line: 1, column: 3, .. })));
+        assert!(matches!(ints::<i32>("1,,2"), Err(AocError::Parse { line: 1, column: 3, .. })));
+
+        let pos = tag("<x=").then(int::<i32>()).and(tag(", y=").then(int::<i32>())).skip(tag(">"));
+        assert_eq!(vec![(1, -2), (3, 4)], all("\n  <x=1, y=-2>\n  <x=3, y=4>\n", lines(pos.by_ref())).unwrap());
+        assert!(matches!(all("<x=1, y=2>\n<x=3, y=4>>", lines(pos)), Err(AocError::Parse { line: 2, column: 11, .. })));
+
+        let e = all("a-b\nc+d", lines(ident().skip(tag("-").or(tag("="))).and(ident()))).unwrap_err();
+        assert_eq!("line 2, column 2: expected \"-\" or \"=\"\n  c+d\n   ^", e.to_string());
+
+        assert_eq!((None, "x"), opt(int::<i32>()).parse("x").unwrap());
+        assert_eq!((vec!["ab", "c"], ""), sep_by(ident(), tag(" ")).parse("ab c").unwrap());
+        assert_eq!((vec![], "1"), sep_by(ident(), tag(" ")).parse("1").unwrap());
+        assert_eq!((vec!["a", "b"], "1"), many(ident().skip(spaces())).parse("a b 1").unwrap());
+    }
+
+    #[test]
+    fn test_records() {
+        let input = "
+        ecl:gry pid:8600
+        hcl:#fffffd
+
+        iyr:2013
+        ";
+        let fields = key_values(ident(), ":", take_while1("a value", |c| !c.is_whitespace()));
+        let pps = all(input, records(fields)).unwrap();
+        assert_eq!(vec![vec![("ecl", "gry"), ("pid", "8600"), ("hcl", "#fffffd")], vec![("iyr", "2013")]], pps);
+
+        let groups = all("ab\nc\n\n\nd\n", records(lines(ident()))).unwrap();
+        assert_eq!(vec![vec!["ab", "c"], vec!["d"]], groups);
+
+        let e = all("a:1\n\nb:1 c\n", records(key_values(ident(), ":", int::<u8>()))).unwrap_err();
+        assert!(matches!(e, AocError::Parse { line: 3, column: 6, .. }));
+
+        let (nums, groups) = all("1,2\n\n  a\n  b\n\n\nc", block(sep_by1(int::<u8>(), tag(","))).and(records(lines(ident())))).unwrap();
+        assert_eq!((vec![1, 2], vec![vec!["a", "b"], vec!["c"]]), (nums, groups));
+    }
+
+    #[test]
+    fn test_grid() {
+        let digits = || grid("a digit", |c| c.to_digit(10));
+        assert_eq!(vec![vec![1, 2], vec![3, 4]], all("12\n34\n", digits()).unwrap());
+        assert!(matches!(all("12\n3x\n", digits()), Err(AocError::Parse { line: 2, column: 2, .. })));
+        assert!(matches!(all("12\n345\n", digits()), Err(AocError::Parse { line: 2, column: 1, .. })));
+
+        let (g, rest) = digits().parse("12\n34\n\n56").unwrap();
+        assert_eq!((2, "\n56"), (g.len(), rest));
+    }
+}
@@ -0,0 +1,291 @@
+//!
+//! A work-stealing thread pool for running many independent jobs
+//!
+//! Jobs are dealt round-robin onto one queue per worker. A worker takes from the front of its own
+//! queue and, once that is empty, steals from the back of the others. Outcomes are reported in job
+//! order as soon as every earlier job has finished, so output is the same however the jobs are
+//! scheduled.
+//!
+//! Threads can't be killed, so a job that runs past the timeout is abandoned rather than stopped:
+//! its worker is replaced and its eventual result is ignored. Cancelling works the same way for
+//! running jobs, and jobs that haven't started are never run.
+//!
+
+use std::collections::{BTreeMap, VecDeque};
+use std::iter;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often running jobs are checked against the timeout and reported
+const TICK: Duration = Duration::from_millis(100);
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Config {
+    pub threads: usize,
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Config { threads, timeout: None }
+    }
+}
+
+///
+/// A handle to stop a run early, which can be cloned into the jobs themselves
+///
+#[derive(Clone, Default, Debug)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Outcome<T> {
+    Done(T),
+    Panicked(String),
+    TimedOut(Duration),
+    Cancelled,
+}
+
+pub enum Report<'a, T> {
+    /// The outcome of job `index`, reported in job order
+    Finished(usize, &'a Outcome<T>),
+    /// Every so often, the jobs still running and how long they have been running, by index
+    Running(&'a [(usize, Duration)]),
+}
+
+enum Event<T> {
+    Started(usize, usize),
+    Finished(usize, Outcome<T>),
+}
+
+struct Shared<F> {
+    queues: Vec<Mutex<VecDeque<(usize, F)>>>,
+    /// Bumped when the worker on a queue is abandoned, to tell it to stop once its job is done
+    generations: Vec<AtomicUsize>,
+}
+
+impl<F> Shared<F> {
+    fn next_job(&self, slot: usize) -> Option<(usize, F)> {
+        if let Some(job) = self.queues[slot].lock().unwrap().pop_front() {
+            return Some(job);
+        }
+        (1..self.queues.len())
+            .map(|i| (slot + i) % self.queues.len())
+            .find_map(|victim| self.queues[victim].lock().unwrap().pop_back())
+    }
+
+    fn drain(&self) -> Vec<usize> {
+        self.queues.iter()
+            .flat_map(|queue| queue.lock().unwrap().drain(..).map(|(i, _)| i).collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+///
+/// Run every job, calling `report` as outcomes arrive, and return the outcomes in job order
+///
+pub fn run<T, F, R>(jobs: Vec<F>, config: &Config, cancel: &Cancel, mut report: R) -> Vec<Outcome<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+        R: FnMut(Report<'_, T>),
+{
+    let num_jobs = jobs.len();
+    let threads = config.threads.clamp(1, num_jobs.max(1));
+    let shared = Arc::new(Shared {
+        queues: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+        generations: (0..threads).map(|_| AtomicUsize::new(0)).collect(),
+    });
+    for (i, job) in jobs.into_iter().enumerate() {
+        shared.queues[i % threads].lock().unwrap().push_back((i, job));
+    }
+    let (tx, rx) = mpsc::channel();
+    for slot in 0..threads {
+        spawn_worker(&shared, slot, &tx, cancel);
+    }
+
+    let mut outcomes: Vec<Option<Outcome<T>>> = (0..num_jobs).map(|_| None).collect();
+    let mut running = BTreeMap::new();
+    let mut num_done = 0;
+    let mut num_reported = 0;
+    let mut last_tick = Instant::now();
+    while num_reported < num_jobs {
+        let first = match rx.recv_timeout(TICK) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => unreachable!("a sender is kept to replace workers"),
+        };
+        for event in first.into_iter().chain(iter::from_fn(|| rx.try_recv().ok())) {
+            match event {
+                Event::Started(i, slot) => {
+                    if outcomes[i].is_none() {
+                        running.insert(i, (Instant::now(), slot));
+                    }
+                }
+                Event::Finished(i, outcome) => {
+                    running.remove(&i);
+                    finish(&mut outcomes, &mut num_done, i, outcome);
+                }
+            }
+        }
+
+        if cancel.is_cancelled() {
+            for i in shared.drain() {
+                finish(&mut outcomes, &mut num_done, i, Outcome::Cancelled);
+            }
+            for (i, (_, slot)) in std::mem::take(&mut running) {
+                shared.generations[slot].fetch_add(1, Ordering::SeqCst);
+                finish(&mut outcomes, &mut num_done, i, Outcome::Cancelled);
+            }
+        }
+        if let Some(timeout) = config.timeout {
+            let timed_out = running.iter()
+                .filter(|(_, (start, _))| start.elapsed() > timeout)
+                .map(|(&i, &(_, slot))| (i, slot))
+                .collect::<Vec<_>>();
+            for (i, slot) in timed_out {
+                running.remove(&i);
+                finish(&mut outcomes, &mut num_done, i, Outcome::TimedOut(timeout));
+                shared.generations[slot].fetch_add(1, Ordering::SeqCst);
+                spawn_worker(&shared, slot, &tx, cancel);
+            }
+        }
+
+        while num_reported < num_jobs {
+            match &outcomes[num_reported] {
+                Some(outcome) => report(Report::Finished(num_reported, outcome)),
+                None => break,
+            }
+            num_reported += 1;
+        }
+        if last_tick.elapsed() >= TICK && num_done < num_jobs {
+            let elapsed = running.iter()
+                .map(|(&i, (start, _))| (i, start.elapsed()))
+                .collect::<Vec<_>>();
+            report(Report::Running(&elapsed));
+            last_tick = Instant::now();
+        }
+    }
+    outcomes.into_iter().map(Option::unwrap).collect()
+}
+
+fn finish<T>(outcomes: &mut [Option<Outcome<T>>], num_done: &mut usize, i: usize, outcome: Outcome<T>) {
+    if outcomes[i].is_none() {
+        outcomes[i] = Some(outcome);
+        *num_done += 1;
+    }
+}
+
+fn spawn_worker<T, F>(shared: &Arc<Shared<F>>, slot: usize, tx: &Sender<Event<T>>, cancel: &Cancel)
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+{
+    let shared = Arc::clone(shared);
+    let tx = tx.clone();
+    let cancel = cancel.clone();
+    let generation = shared.generations[slot].load(Ordering::SeqCst);
+    thread::spawn(move || {
+        while shared.generations[slot].load(Ordering::SeqCst) == generation && !cancel.is_cancelled() {
+            let (i, job) = match shared.next_job(slot) {
+                Some(job) => job,
+                None => return,
+            };
+            if tx.send(Event::Started(i, slot)).is_err() {
+                return;
+            }
+            let outcome = match panic::catch_unwind(AssertUnwindSafe(job)) {
+                Ok(res) => Outcome::Done(res),
+                Err(e) => Outcome::Panicked(panic_message(&*e)),
+            };
+            if tx.send(Event::Finished(i, outcome)).is_err() {
+                return;
+            }
+        }
+    });
+}
+
+fn panic_message(e: &(dyn std::any::Any + Send)) -> String {
+    match (e.downcast_ref::<&str>(), e.downcast_ref::<String>()) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sleep_then(ms: u64, value: u64) -> impl FnOnce() -> u64 {
+        move || {
+            thread::sleep(Duration::from_millis(ms));
+            value
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        let jobs = (0..20).map(|i| sleep_then((20 - i) * 2, i)).collect::<Vec<_>>();
+        let config = Config { threads: 4, timeout: None };
+        let mut reported = vec![];
+        let outcomes = run(jobs, &config, &Cancel::default(), |report| {
+            if let Report::Finished(i, _) = report {
+                reported.push(i as u64);
+            }
+        });
+        assert_eq!((0..20).map(Outcome::Done).collect::<Vec<_>>(), outcomes);
+        assert_eq!((0..20).collect::<Vec<_>>(), reported);
+    }
+
+    #[test]
+    fn test_timeout_and_panic() {
+        let jobs: Vec<Box<dyn FnOnce() -> u64 + Send>> = vec![
+            Box::new(sleep_then(5000, 0)),
+            Box::new(|| panic!("boom")),
+            Box::new(sleep_then(1, 2)),
+            Box::new(sleep_then(1, 3)),
+        ];
+        let timeout = Duration::from_millis(200);
+        let config = Config { threads: 1, timeout: Some(timeout) };
+        let start = Instant::now();
+        let outcomes = run(jobs, &config, &Cancel::default(), |_| ());
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert_eq!(vec![Outcome::TimedOut(timeout), Outcome::Panicked("boom".to_string()),
+                        Outcome::Done(2), Outcome::Done(3)], outcomes);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let cancel = Cancel::default();
+        let jobs = (0..4u64)
+            .map(|i| {
+                let cancel = cancel.clone();
+                move || {
+                    if i == 1 {
+                        cancel.cancel();
+                    }
+                    i
+                }
+            })
+            .collect::<Vec<_>>();
+        let config = Config { threads: 1, timeout: None };
+        let outcomes = run(jobs, &config, &cancel, |_| ());
+        assert_eq!(Outcome::Done(0), outcomes[0]);
+        assert_eq!(vec![Outcome::Cancelled, Outcome::Cancelled], outcomes[2..]);
+    }
+}
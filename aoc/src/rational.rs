@@ -0,0 +1,83 @@
+//!
+//! Directions ordered by angle without floating point
+//!
+//! For exact fractions, use `num::rational::Ratio`.
+//!
+//! ```
+//! use aoc::rational::Angle;
+//!
+//! // clockwise from north on the screen, where y increases to the south
+//! let mut dirs = vec![Angle::new(-1, 0), Angle::new(2, 1), Angle::new(0, -3), Angle::new(4, 2)];
+//! dirs.sort();
+//! assert_eq!(vec![Angle::new(0, -1), Angle::new(2, 1), Angle::new(2, 1), Angle::new(-1, 0)], dirs);
+//! ```
+//!
+
+use std::cmp::Ordering;
+
+use crate::numtheory;
+
+///
+/// The direction of a nonzero `(dx, dy)` on the screen, so that vectors pointing the same way
+/// are equal, ordered clockwise starting from north
+///
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Angle {
+    dx: i64,
+    dy: i64,
+}
+
+impl Angle {
+    pub fn new(dx: i64, dy: i64) -> Angle {
+        assert!(dx != 0 || dy != 0, "the zero vector has no direction");
+        let g = numtheory::gcd(dx, dy);
+        Angle { dx: dx / g, dy: dy / g }
+    }
+
+    pub fn dx(&self) -> i64 {
+        self.dx
+    }
+
+    pub fn dy(&self) -> i64 {
+        self.dy
+    }
+
+    /// 0 from north up to south, and 1 from south up to north
+    fn half(&self) -> u8 {
+        if self.dx > 0 || (self.dx == 0 && self.dy < 0) { 0 } else { 1 }
+    }
+}
+
+impl Ord for Angle {
+    fn cmp(&self, other: &Angle) -> Ordering {
+        // within a half turn, `other` is clockwise of `self` when the cross product is positive
+        let cross = self.dx as i128 * other.dy as i128 - self.dy as i128 * other.dx as i128;
+        self.half().cmp(&other.half()).then(0.cmp(&cross))
+    }
+}
+
+impl PartialOrd for Angle {
+    fn partial_cmp(&self, other: &Angle) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle() {
+        let compass = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)]
+            .map(|(dx, dy)| Angle::new(dx, dy));
+        for (i, a) in compass.iter().enumerate() {
+            for (j, b) in compass.iter().enumerate() {
+                assert_eq!(i.cmp(&j), a.cmp(b));
+            }
+        }
+        assert_eq!(Angle::new(3, -6), Angle::new(1, -2));
+        assert_ne!(Angle::new(3, -6), Angle::new(-1, 2));
+        assert!(Angle::new(1_000_000_000, -999_999_999) < Angle::new(999_999_999, -999_999_998));
+        assert_eq!((1, -2), (Angle::new(3, -6).dx(), Angle::new(3, -6).dy()));
+    }
+}
@@ -0,0 +1,298 @@
+//!
+//! Opt-in animation of grid simulations
+//!
+//! Animations are configured through the environment so that solutions run unchanged by default:
+//!
+//! - `AOC_ANIMATE=term` redraws each frame in place on stderr
+//! - `AOC_ANIMATE=file:<path>` writes plain-text frames to `<path>` without delay
+//! - `AOC_ANIMATE_FPS=<n>` sets the terminal frame rate (default 30)
+//!
+
+use std::{env, fmt, thread};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const DEFAULT_FPS: u32 = 30;
+
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Colour::Black => 30,
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 97,
+            Colour::Grey => 90,
+        }
+    }
+}
+
+///
+/// Colours for each tile character, anything not listed is drawn uncoloured
+///
+#[derive(Clone, Default, Debug)]
+pub struct Palette {
+    colours: Vec<(char, Colour)>,
+}
+
+impl Palette {
+    pub fn new() -> Palette {
+        Palette::default()
+    }
+
+    pub fn with(mut self, tile: char, colour: Colour) -> Palette {
+        self.colours.retain(|&(c, _)| c != tile);
+        self.colours.push((tile, colour));
+        self
+    }
+
+    pub fn colour(&self, tile: char) -> Option<Colour> {
+        self.colours.iter()
+            .find(|&&(c, _)| c == tile)
+            .map(|&(_, colour)| colour)
+    }
+}
+
+enum Output {
+    Terminal(io::Stderr),
+    File(BufWriter<File>),
+}
+
+///
+/// Sink for animation frames, which does nothing unless enabled
+///
+/// Write errors disable the animation and are reported by `finish`, so that drawing a frame
+/// never interrupts the simulation itself.
+///
+pub struct Animation {
+    output: Option<Output>,
+    palette: Palette,
+    frame_delay: Duration,
+    last_frame: Option<Instant>,
+    num_frames: usize,
+    error: Option<io::Error>,
+}
+
+impl Animation {
+    fn new(output: Option<Output>, frame_delay: Duration) -> Animation {
+        Animation {
+            output,
+            palette: Palette::default(),
+            frame_delay,
+            last_frame: None,
+            num_frames: 0,
+            error: None,
+        }
+    }
+
+    pub fn disabled() -> Animation {
+        Animation::new(None, Duration::ZERO)
+    }
+
+    pub fn terminal(fps: u32) -> Animation {
+        let output = Output::Terminal(io::stderr());
+        Animation::new(Some(output), Duration::from_secs(1) / fps.max(1))
+    }
+
+    pub fn headless<P: AsRef<Path>>(path: P) -> io::Result<Animation> {
+        let output = Output::File(BufWriter::new(File::create(path)?));
+        Ok(Animation::new(Some(output), Duration::ZERO))
+    }
+
+    pub fn from_env() -> io::Result<Animation> {
+        let mode = match env::var("AOC_ANIMATE") {
+            Ok(mode) if !mode.is_empty() => mode,
+            _ => return Ok(Animation::disabled()),
+        };
+        if let Some(path) = mode.strip_prefix("file:") {
+            return Animation::headless(path);
+        }
+        if mode != "term" {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("invalid AOC_ANIMATE {:?}, expected term or file:<path>", mode)));
+        }
+        let fps = match env::var("AOC_ANIMATE_FPS") {
+            Ok(fps) => fps.parse().map_err(|_| io::Error::new(
+                io::ErrorKind::InvalidInput, format!("invalid AOC_ANIMATE_FPS {:?}", fps)))?,
+            Err(_) => DEFAULT_FPS,
+        };
+        Ok(Animation::terminal(fps))
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Animation {
+        self.palette = palette;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.output.is_some()
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    ///
+    /// Draw a frame, `frame` is only evaluated when the animation is enabled
+    ///
+    pub fn frame<F, D>(&mut self, frame: F)
+        where
+            F: FnOnce() -> D,
+            D: Display,
+    {
+        if !self.is_enabled() {
+            return;
+        }
+        let frame = frame().to_string();
+        if let Err(e) = self.write_frame(&frame) {
+            self.output = None;
+            self.error = Some(e);
+        }
+    }
+
+    ///
+    /// Restore the terminal and report the first write error, if any
+    ///
+    pub fn finish(mut self) -> io::Result<()> {
+        self.restore()?;
+        self.output = None;
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn write_frame(&mut self, frame: &str) -> io::Result<()> {
+        match self.output.as_mut().unwrap() {
+            Output::Terminal(out) => {
+                if let Some(last_frame) = self.last_frame {
+                    if let Some(wait) = self.frame_delay.checked_sub(last_frame.elapsed()) {
+                        thread::sleep(wait);
+                    }
+                }
+                let mut buf = String::new();
+                if self.num_frames == 0 {
+                    buf.push_str("\x1b[?25l\x1b[2J");
+                }
+                buf.push_str("\x1b[H");
+                colourize(&mut buf, frame, &self.palette).unwrap();
+                buf.push_str("\x1b[J");
+
+                let mut out = out.lock();
+                out.write_all(buf.as_bytes())?;
+                out.flush()?;
+                self.last_frame = Some(Instant::now());
+            }
+            Output::File(out) => {
+                writeln!(out, "--- frame {} ---", self.num_frames)?;
+                write!(out, "{}", frame)?;
+                if !frame.ends_with('\n') {
+                    writeln!(out)?;
+                }
+            }
+        }
+        self.num_frames += 1;
+        Ok(())
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        match self.output.as_mut() {
+            Some(Output::Terminal(out)) if self.num_frames > 0 => {
+                out.write_all(b"\x1b[0m\x1b[?25h")?;
+                out.flush()
+            }
+            Some(Output::File(out)) => out.flush(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation::disabled()
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+fn colourize(buf: &mut String, frame: &str, palette: &Palette) -> fmt::Result {
+    use std::fmt::Write;
+
+    for line in frame.lines() {
+        let mut current = None;
+        for c in line.chars() {
+            let colour = palette.colour(c);
+            if colour != current {
+                match colour {
+                    Some(colour) => write!(buf, "\x1b[{}m", colour.ansi_code())?,
+                    None => buf.push_str("\x1b[0m"),
+                }
+                current = colour;
+            }
+            buf.push(c);
+        }
+        buf.push_str("\x1b[0m\x1b[K\n");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colourize() {
+        let palette = Palette::new()
+            .with('#', Colour::Red)
+            .with('O', Colour::Yellow);
+        let mut buf = String::new();
+        colourize(&mut buf, "##.\n.O", &palette).unwrap();
+        assert_eq!("\x1b[31m##\x1b[0m.\x1b[0m\x1b[K\n.\x1b[33mO\x1b[0m\x1b[K\n", buf);
+    }
+
+    #[test]
+    fn test_headless() -> io::Result<()> {
+        let path = env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        let mut anim = Animation::headless(&path)?;
+        anim.frame(|| "ab\ncd");
+        anim.frame(|| "ef\ngh\n");
+        assert_eq!(2, anim.num_frames());
+        anim.finish()?;
+
+        let frames = std::fs::read_to_string(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!("--- frame 0 ---\nab\ncd\n--- frame 1 ---\nef\ngh\n", frames);
+        Ok(())
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut anim = Animation::disabled();
+        anim.frame(|| -> &str { panic!("frame evaluated while disabled") });
+        assert_eq!(0, anim.num_frames());
+        assert!(anim.finish().is_ok());
+    }
+}
@@ -0,0 +1,431 @@
+//!
+//! Command line runner for a set of solutions
+//!
+//! ```text
+//! [--year <year>] [--day <day>] [--part <1|2>] [--input <path|-> | --variant <name>] [--verify]
+//!     [--bench [--iterations <n>] [--warmup <n>] [--compare]] [--json]
+//!     [--jobs <n>] [--timeout <secs>] [--fail-fast]
+//! ```
+//!
+//! Without filters every solution is run. Inputs default to `input/aoc<year>/day<day>` under the
+//! workspace root, see `input`. `--input` and `--variant` are only allowed when a single day is
+//! selected, with `--input -` reading from stdin and `--variant b` reading `day<day>b`.
+//!
+//! Each selected part is a job on a work-stealing pool of `--jobs` threads, one per core by default,
+//! and answers are printed in order as they come in. A part running for over `--timeout` seconds
+//! fails, and `--fail-fast` cancels the remaining parts after the first failure.
+//!
+//! `--json` prints one JSON object per line instead, for each part with its `year`, `day`, `part`,
+//! `answer`, `elapsed_ns` and `artifact`, or for each failed part with its `year`, `day`, `part` and
+//! `error`.
+//! The answer is a number when it is an integer that JSON readers hold exactly, else a string,
+//! and the artifact is a picture of the solution for the days that draw one, else `null`.
+//!
+//! `--verify` prints a table comparing each answer with the one recorded in the day's source.
+//!
+//! `--bench` times each phase instead of printing answers, one day at a time, and appends the timings to
+//! `bench-history.csv` in the workspace root. `--compare` also shows the change from the last
+//! saved timing of each phase.
+//!
+//! `new --year <year> --day <day> [--puzzle <path>]` generates a new day instead, see `scaffold`.
+//!
+
+use std::{env, fs};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::bench::{self, Record};
+use crate::input::{self, Source};
+use crate::json::Json;
+use crate::pool::{self, Cancel, Outcome, Report};
+use crate::scaffold;
+use crate::solution::{Answer, Entry};
+use crate::verify::{self, Status};
+
+const USAGE: &str = "usage: [--year <year>] [--day <day>] [--part <1|2>] [--input <path|-> | --variant <name>] [--verify] \
+                     [--bench [--iterations <n>] [--warmup <n>] [--compare]] [--json] \
+                     [--jobs <n>] [--timeout <secs>] [--fail-fast]";
+
+const BENCH_HISTORY: &str = "bench-history.csv";
+
+/// How long a part runs before it is shown as running
+const SHOW_RUNNING_AFTER: Duration = Duration::from_secs(1);
+
+#[derive(Eq, PartialEq, Clone, Default, Debug)]
+pub struct Args {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub input: Option<String>,
+    pub variant: Option<String>,
+    pub verify: bool,
+    pub bench: bool,
+    pub iterations: Option<usize>,
+    pub warmup: Option<usize>,
+    pub compare: bool,
+    pub json: bool,
+    pub jobs: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub fail_fast: bool,
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item=String>>(args: I) -> Result<Args> {
+        let mut res = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| anyhow!("missing value for {}\n{}", arg, USAGE));
+            match arg.as_str() {
+                "--year" => res.year = Some(parse_num(&value()?, "year")?),
+                "--day" => res.day = Some(parse_num(&value()?, "day")?),
+                "--part" => res.part = match parse_num(&value()?, "part")? {
+                    part @ (1 | 2) => Some(part),
+                    part => bail!("invalid part {}, expected 1 or 2", part),
+                },
+                "--input" => res.input = Some(value()?),
+                "--variant" => res.variant = Some(value()?),
+                "--verify" => res.verify = true,
+                "--bench" => res.bench = true,
+                "--iterations" => res.iterations = Some(parse_num(&value()?, "iterations")?),
+                "--warmup" => res.warmup = Some(parse_num(&value()?, "warmup")?),
+                "--compare" => res.compare = true,
+                "--json" => res.json = true,
+                "--jobs" => res.jobs = match parse_num(&value()?, "jobs")? {
+                    0 => bail!("--jobs must be at least 1"),
+                    jobs => Some(jobs),
+                },
+                "--timeout" => res.timeout = Some(Duration::try_from_secs_f64(parse_num(&value()?, "timeout")?)?),
+                "--fail-fast" => res.fail_fast = true,
+                "-h" | "--help" => bail!("{}", USAGE),
+                _ => bail!("unexpected argument {:?}\n{}", arg, USAGE),
+            }
+        }
+        if res.input.is_some() && res.variant.is_some() {
+            bail!("--input and --variant can't be used together\n{}", USAGE);
+        }
+        if res.json && (res.verify || res.bench) {
+            bail!("--json can't be used with --verify or --bench\n{}", USAGE);
+        }
+        if res.bench && (res.jobs.is_some() || res.timeout.is_some() || res.fail_fast) {
+            bail!("--jobs, --timeout and --fail-fast can't be used with --bench\n{}", USAGE);
+        }
+        if !res.bench && (res.iterations.is_some() || res.warmup.is_some() || res.compare) {
+            bail!("--iterations, --warmup and --compare need --bench\n{}", USAGE);
+        }
+        Ok(res)
+    }
+
+    pub fn select<'a>(&self, solutions: &'a [Entry]) -> Vec<&'a Entry> {
+        solutions.iter()
+            .filter(|e| self.year.is_none_or(|year| year == e.year))
+            .filter(|e| self.day.is_none_or(|day| day == e.day))
+            .collect()
+    }
+
+    pub fn source(&self) -> Source {
+        match (self.input.as_deref(), &self.variant) {
+            (Some("-"), _) => Source::Stdin,
+            (Some(path), _) => Source::File(PathBuf::from(path)),
+            (None, Some(variant)) => Source::Puzzle { variant: variant.clone() },
+            (None, None) => Source::default(),
+        }
+    }
+
+    pub fn pool_config(&self) -> pool::Config {
+        let default = pool::Config::default();
+        pool::Config {
+            threads: self.jobs.unwrap_or(default.threads),
+            timeout: self.timeout,
+        }
+    }
+
+    pub fn bench_config(&self) -> bench::Config {
+        let default = bench::Config::default();
+        bench::Config {
+            warmup: self.warmup.unwrap_or(default.warmup),
+            iterations: self.iterations.unwrap_or(default.iterations),
+        }
+    }
+
+    pub fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+fn parse_num<T: std::str::FromStr>(s: &str, name: &str) -> Result<T> {
+    s.parse().map_err(|_| anyhow!("invalid {} {:?}", name, s))
+}
+
+///
+/// Run the solutions selected by the command line arguments, printing one answer per line
+///
+pub fn main(solutions: &[Entry]) -> Result<()> {
+    if env::args().nth(1).as_deref() == Some("new") {
+        return scaffold::main(env::args().skip(2));
+    }
+    let args = Args::parse(env::args().skip(1))?;
+    let selected = args.select(solutions);
+    if selected.is_empty() {
+        bail!("no solutions match {:?}", args);
+    }
+    if (args.input.is_some() || args.variant.is_some()) && selected.len() > 1 {
+        bail!("--input and --variant need a single day, but {} match", selected.len());
+    }
+
+    let root = input::workspace_root();
+    if args.bench {
+        return bench(&root, &selected, &args);
+    }
+    if args.verify {
+        return verify(&root, &selected, &args);
+    }
+
+    let mut num_failed = 0;
+    solve_all(&root, &selected, &args, |i, part, res| {
+        let entry = selected[i];
+        if res.is_err() {
+            num_failed += 1;
+        }
+        match res {
+            Ok(answer) if args.json => println!("{}", Json::Object(vec![
+                ("year", Json::Int(entry.year.into())),
+                ("day", Json::Int(entry.day.into())),
+                ("part", Json::Int(part.into())),
+                ("answer", Json::answer(&answer.value)),
+                ("elapsed_ns", Json::Int(answer.elapsed.as_nanos() as i64)),
+                ("artifact", Json::from(answer.artifact.clone())),
+            ])),
+            Ok(answer) => println!("{} day {:>2} part {}: {}", entry.year, entry.day, part, answer),
+            Err(e) if args.json => println!("{}", Json::Object(vec![
+                ("year", Json::Int(entry.year.into())),
+                ("day", Json::Int(entry.day.into())),
+                ("part", Json::Int(part.into())),
+                ("error", Json::Str(e)),
+            ])),
+            Err(e) => eprintln!("{} day {:>2} part {}: {}", entry.year, entry.day, part, e),
+        }
+    });
+    if num_failed > 0 {
+        bail!("{} failed", num_failed);
+    }
+    Ok(())
+}
+
+fn verify(root: &Path, selected: &[&Entry], args: &Args) -> Result<()> {
+    let recorded = selected.iter()
+        .map(|entry| {
+            let path = root.join(entry.source_path());
+            fs::read_to_string(&path)
+                .map(|code| verify::recorded_answers(&code))
+                .map_err(|e| format!("reading {}: {}", path.display(), e))
+        })
+        .collect::<Vec<_>>();
+
+    let (mut num_passed, mut num_failed, mut num_missing) = (0, 0, 0);
+    println!("{:<4}  {:>3}  {:>4}  {:<7}  answer", "year", "day", "part", "status");
+    solve_all(root, selected, args, |i, part, res| {
+        let entry = selected[i];
+        let check = res.and_then(|answer| {
+            let recorded = recorded[i].as_ref().map_err(Clone::clone)?;
+            Ok(verify::check(vec![answer.clone()], recorded).remove(0))
+        });
+        let check = match check {
+            Ok(check) => check,
+            Err(e) => {
+                println!("{:<4}  {:>3}  {:>4}  {:<7}  {}", entry.year, entry.day, part, "error", e);
+                num_failed += 1;
+                return;
+            }
+        };
+        print!("{:<4}  {:>3}  {:>4}  {:<7}  {}", entry.year, entry.day, part, check.status, check.answer);
+        match check.status {
+            Status::Pass => num_passed += 1,
+            Status::Fail { expected } => {
+                print!(" (expected {})", expected);
+                num_failed += 1;
+            }
+            Status::Missing => num_missing += 1,
+        }
+        println!();
+    });
+    println!("{} passed, {} failed, {} missing", num_passed, num_failed, num_missing);
+    if num_failed > 0 {
+        bail!("{} failed", num_failed);
+    }
+    Ok(())
+}
+
+///
+/// Solve each selected part as a job on the pool, calling `on_result` with the index of its entry
+/// and its part, in order
+///
+/// Inputs are read up front, so that stdin is only read once. Parts that have been running for a
+/// while are shown on stderr when it is a terminal.
+///
+fn solve_all<F>(root: &Path, selected: &[&Entry], args: &Args, mut on_result: F)
+    where
+        F: FnMut(usize, u8, std::result::Result<&Answer, String>),
+{
+    let source = args.source();
+    let cancel = Cancel::default();
+    let mut labels = vec![];
+    let mut jobs = vec![];
+    for (i, &entry) in selected.iter().enumerate() {
+        let input = input::load(root, entry, &source).map(Arc::new).map_err(|e| e.to_string());
+        for part in args.parts() {
+            let (input, cancel, json, fail_fast) = (input.clone(), cancel.clone(), args.json, args.fail_fast);
+            let entry = *entry;
+            labels.push((i, part));
+            jobs.push(move || {
+                let res = input.map_err(|e| anyhow!(e)).and_then(|input| match json {
+                    true => entry.solve_rendered(&input, &[part]),
+                    false => entry.solve(&input, &[part]),
+                }).map(|mut answers| answers.remove(0));
+                if res.is_err() && fail_fast {
+                    cancel.cancel();
+                }
+                res
+            });
+        }
+    }
+
+    let live = io::stderr().is_terminal();
+    let mut status = String::new();
+    pool::run(jobs, &args.pool_config(), &cancel, |report| match report {
+        Report::Finished(i, outcome) => {
+            if !status.is_empty() {
+                eprint!("\r\x1b[K");
+                status.clear();
+            }
+            let (entry, part) = labels[i];
+            let res = match outcome {
+                Outcome::Done(Ok(answer)) => Ok(answer),
+                Outcome::Done(Err(e)) => Err(format!("{:#}", e)),
+                Outcome::Panicked(message) => Err(format!("panicked: {}", message)),
+                Outcome::TimedOut(timeout) => Err(format!("timed out after {:?}", timeout)),
+                Outcome::Cancelled => Err("cancelled".to_string()),
+            };
+            if res.is_err() && args.fail_fast {
+                cancel.cancel();
+            }
+            on_result(entry, part, res);
+        }
+        Report::Running(running) if live => {
+            let slow = running.iter()
+                .filter(|(_, elapsed)| *elapsed >= SHOW_RUNNING_AFTER)
+                .map(|&(i, elapsed)| {
+                    let (entry, part) = (selected[labels[i].0], labels[i].1);
+                    format!("{} day {} part {} ({:.1}s)", entry.year, entry.day, part, elapsed.as_secs_f64())
+                })
+                .collect::<Vec<_>>();
+            if !slow.is_empty() || !status.is_empty() {
+                status = if slow.is_empty() { String::new() } else { format!("running: {}", slow.join(", ")) };
+                eprint!("\r\x1b[K{}", status);
+            }
+        }
+        Report::Running(_) => (),
+    });
+}
+
+fn bench(root: &Path, selected: &[&Entry], args: &Args) -> Result<()> {
+    let parts = args.parts();
+    let source = args.source();
+    let config = args.bench_config();
+    let history = root.join(BENCH_HISTORY);
+    let past = bench::load_history(&history)?;
+    let last_timings = if args.compare {
+        Some(bench::last_timings(&past))
+    } else {
+        None
+    };
+    let run = bench::next_run(&past);
+    let commit = bench::commit().unwrap_or_else(|_| "unknown".to_string());
+
+    let mut records = vec![];
+    let mut num_failed = 0;
+    print!("{:<4}  {:>3}  {:<5}  {:>10}  {:>10}", "year", "day", "phase", "median", "min");
+    println!("{}", if args.compare { "    change" } else { "" });
+    for entry in selected {
+        let timings = input::load(root, entry, &source)
+            .map_err(anyhow::Error::from)
+            .and_then(|input| entry.bench(&input, &parts, &config));
+        let timings = match timings {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("{} day {:>2}: {:#}", entry.year, entry.day, e);
+                num_failed += 1;
+                continue;
+            }
+        };
+        for (phase, timing) in timings {
+            print!("{:<4}  {:>3}  {:<5}  {:>10.2?}  {:>10.2?}", entry.year, entry.day, phase, timing.median, timing.min);
+            if let Some(last_timings) = &last_timings {
+                match last_timings.get(&(entry.year, entry.day, phase.to_string())) {
+                    Some(last) => {
+                        let change = timing.median.as_secs_f64() / last.median.as_secs_f64() - 1.0;
+                        print!("  {:>+7.1}%", change * 100.0);
+                    }
+                    None => print!("  {:>8}", "-"),
+                }
+            }
+            println!();
+            records.push(Record { run, commit: commit.clone(), year: entry.year, day: entry.day, phase: phase.to_string(), timing });
+        }
+    }
+    bench::append_history(&history, &records)?;
+    if num_failed > 0 {
+        bail!("{} failed", num_failed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args> {
+        Args::parse(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_args() {
+        let expected = Args {
+            year: Some(2021),
+            day: Some(5),
+            part: Some(2),
+            input: Some("-".to_string()),
+            verify: true,
+            ..Args::default()
+        };
+        assert_eq!(expected, args("--year 2021 --day 5 --part 2 --input - --verify").unwrap());
+        let bench = args("--bench --iterations 3 --compare").unwrap();
+        assert_eq!(bench::Config { warmup: 1, iterations: 3 }, bench.bench_config());
+        assert!(bench.compare);
+        assert!(args("--compare").is_err());
+        assert_eq!(Source::Puzzle { variant: "b".to_string() }, args("--variant b").unwrap().source());
+        assert_eq!(Source::File(PathBuf::from("in.txt")), args("--input in.txt").unwrap().source());
+        assert_eq!(Source::Stdin, expected.source());
+        assert!(args("--input - --variant b").is_err());
+        assert!(args("--json").unwrap().json);
+        assert!(args("--json --verify").is_err());
+        let pool = args("--jobs 3 --timeout 1.5 --fail-fast").unwrap();
+        assert_eq!(pool::Config { threads: 3, timeout: Some(Duration::from_millis(1500)) }, pool.pool_config());
+        assert!(pool.fail_fast);
+        assert!(args("--jobs 0").is_err());
+        assert!(args("--timeout -1").is_err());
+        assert!(args("--bench --jobs 2").is_err());
+        assert_eq!(Args::default(), args("").unwrap());
+        assert_eq!(vec![1, 2], Args::default().parts());
+        assert!(args("--part 3").is_err());
+        assert!(args("--day").is_err());
+        assert!(args("--day x").is_err());
+        assert!(args("--bogus").is_err());
+    }
+}
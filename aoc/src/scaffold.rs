@@ -0,0 +1,331 @@
+//!
+//! Generating the skeleton of a new day
+//!
+//! ```text
+//! new --year <year> --day <day> [--puzzle <path>]
+//! ```
+//!
+//! Creates `aoc<year>/src/bin/day<day>.rs`, registers it in the year's `SOLUTIONS` and creates an
+//! empty `input/aoc<year>/day<day>` to paste the input into. The puzzle text, as copied from the
+//! puzzle page into a local file, becomes the day's header comment. A year without a crate yet
+//! gets one, added to the workspace and the runner.
+//!
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::input;
+
+const USAGE: &str = "usage: new --year <year> --day <day> [--puzzle <path>]";
+
+///
+/// Parse the arguments after `new` and generate the day under the workspace root
+///
+pub fn main<I: IntoIterator<Item=String>>(args: I) -> Result<()> {
+    let (mut year, mut day, mut puzzle) = (None, None, None);
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!("missing value for {}\n{}", arg, USAGE));
+        match arg.as_str() {
+            "--year" => year = Some(value()?.parse().context("invalid year")?),
+            "--day" => day = Some(value()?.parse().context("invalid day")?),
+            "--puzzle" => puzzle = Some(PathBuf::from(value()?)),
+            _ => bail!("unexpected argument {:?}\n{}", arg, USAGE),
+        }
+    }
+    let (year, day) = match (year, day) {
+        (Some(year), Some(day @ 1..=25)) => (year, day),
+        (Some(_), Some(day)) => bail!("invalid day {}, expected 1 to 25", day),
+        _ => bail!("--year and --day are required\n{}", USAGE),
+    };
+    let puzzle = match puzzle {
+        Some(path) => Some(fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?),
+        None => None,
+    };
+    for path in generate(&input::workspace_root(), year, day, puzzle.as_deref())? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+///
+/// Create the day and its placeholder input, returning every file written
+///
+pub fn generate(root: &Path, year: u16, day: u8, puzzle: Option<&str>) -> Result<Vec<PathBuf>> {
+    let krate = root.join(format!("aoc{}", year));
+    let source = krate.join(format!("src/bin/day{}.rs", day));
+    if source.exists() {
+        bail!("{} already exists", source.display());
+    }
+    let mut written = vec![];
+    if !krate.exists() {
+        written.extend(new_year(root, year)?);
+    }
+
+    let lib = krate.join("src/lib.rs");
+    let registry = fs::read_to_string(&lib).with_context(|| format!("reading {}", lib.display()))?;
+    let registry = register(&registry, day).with_context(|| format!("registering in {}", lib.display()))?;
+    fs::create_dir_all(source.parent().unwrap())?;
+    fs::write(&source, template(year, day, puzzle))?;
+    written.push(source);
+    fs::write(&lib, registry)?;
+    if !written.contains(&lib) {
+        written.push(lib);
+    }
+
+    let input = root.join(format!("input/aoc{}/day{}", year, day));
+    if !input.exists() {
+        fs::create_dir_all(input.parent().unwrap())?;
+        fs::write(&input, "")?;
+        written.push(input);
+    }
+    Ok(written)
+}
+
+///
+/// The puzzle text as a comment, headed by the puzzle's URL
+///
+pub fn header(year: u16, day: u8, puzzle: Option<&str>) -> String {
+    let default = format!("--- Day {} ---", day);
+    let text = puzzle.map(str::trim).filter(|text| !text.is_empty()).unwrap_or(&default);
+    let mut header = format!("// https://adventofcode.com/{}/day/{}\n//\n", year, day);
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            header.push_str("//\n");
+        } else {
+            header.push_str("// ");
+            header.push_str(line);
+            header.push('\n');
+        }
+    }
+    header
+}
+
+pub fn template(year: u16, day: u8, puzzle: Option<&str>) -> String {
+    format!(r#"{header}
+use std::fmt::Display;
+
+use anyhow::Result;
+
+use aoc::solution::{{Entry, Solution}};
+
+struct Day{day};
+
+pub const SOLUTION: Entry = Entry::new::<Day{day}>();
+
+impl Solution for Day{day} {{
+    const YEAR: u16 = {year};
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {{
+        Ok(parse(input))
+    }}
+
+    fn part1(lines: &Self::Input) -> Result<impl Display> {{
+        Ok(part1(lines))
+    }}
+
+    fn part2(lines: &Self::Input) -> Result<impl Display> {{
+        Ok(part2(lines))
+    }}
+}}
+
+fn main() -> Result<()> {{
+    aoc::runner::main(&[SOLUTION])
+}}
+
+fn parse(s: &str) -> Vec<String> {{
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}}
+
+fn part1(lines: &[String]) -> usize {{
+    lines.len()
+}}
+
+fn part2(lines: &[String]) -> usize {{
+    lines.len()
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test() {{
+        let s = r"
+        ";
+        let lines = parse(s);
+        assert_eq!(0, part1(&lines));
+        assert_eq!(0, part2(&lines));
+    }}
+}}
+"#, header = header(year, day, puzzle), year = year, day = day)
+}
+
+///
+/// Add `day`'s module and entry to the `SOLUTIONS` registry in `lib`, keeping the days in order
+///
+pub fn register(lib: &str, day: u8) -> Result<String> {
+    let days = lib.lines()
+        .filter_map(|line| line.strip_prefix("pub mod day")?.strip_suffix(';')?.parse::<u8>().ok())
+        .collect::<Vec<_>>();
+    if days.contains(&day) {
+        bail!("day {} is already registered", day);
+    }
+    let next = days.iter().filter(|&&d| d > day).min();
+    let prev = days.iter().filter(|&&d| d < day).max();
+
+    let module = format!("#[allow(dead_code)]\n#[path = \"bin/day{0}.rs\"]\npub mod day{0};\n", day);
+    let entry = format!("    day{}::SOLUTION,\n", day);
+    let (module_at, entry_at) = match (next, prev) {
+        (Some(next), _) => (
+            format!("#[allow(dead_code)]\n#[path = \"bin/day{}.rs\"]\n", next),
+            format!("    day{}::SOLUTION,\n", next),
+        ),
+        (None, Some(prev)) => (format!("pub mod day{};\n\n", prev), "];".to_string()),
+        (None, None) => ("pub const SOLUTIONS".to_string(), "];".to_string()),
+    };
+    let module_at = lib.find(&module_at).ok_or_else(|| anyhow!("no {:?}", module_at))?;
+    let module_at = match (next, prev) {
+        (None, Some(prev)) => module_at + format!("pub mod day{};\n", prev).len(),
+        _ => module_at,
+    };
+    let solutions = lib.find("pub const SOLUTIONS").ok_or_else(|| anyhow!("no SOLUTIONS"))?;
+    let entry_at = solutions + lib[solutions..].find(&entry_at).ok_or_else(|| anyhow!("no {:?}", entry_at))?;
+
+    let mut res = String::new();
+    res.push_str(&lib[..module_at]);
+    res.push_str(&module);
+    if days.is_empty() {
+        res.push('\n');
+    }
+    res.push_str(&lib[module_at..entry_at]);
+    res.push_str(&entry);
+    res.push_str(&lib[entry_at..]);
+    Ok(res)
+}
+
+///
+/// Create the crate for `year` with an empty registry, and add it to the workspace and the runner
+///
+fn new_year(root: &Path, year: u16) -> Result<Vec<PathBuf>> {
+    let name = format!("aoc{}", year);
+    let edits = [
+        ("Cargo.toml", "\"runner\"", format!("\"{}\", \"runner\"", name)),
+        ("runner/Cargo.toml", "anyhow = ", format!("{0} = {{ path = \"../{0}\" }}\nanyhow = ", name)),
+        ("runner/src/main.rs", "].concat()", format!(", {}::SOLUTIONS].concat()", name)),
+    ];
+    let mut edited = vec![];
+    for (file, at, insert) in edits.iter() {
+        let path = root.join(file);
+        let s = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+        if !s.contains(at) {
+            bail!("can't add {} to {}, expected {:?} in it", name, path.display(), at);
+        }
+        edited.push((path, s.replacen(at, insert, 1)));
+    }
+
+    let krate = root.join(&name);
+    fs::create_dir_all(krate.join("src/bin"))?;
+    let files = [
+        ("Cargo.toml", format!(r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc = {{ path = "../aoc" }}
+anyhow = "1.0"
+"#, name)),
+        ("src/lib.rs", "\
+use aoc::solution::Entry;
+
+// Each day's binary, also compiled here so that the days can be run together
+
+pub const SOLUTIONS: &[Entry] = &[
+];
+".to_string()),
+        ("src/main.rs", format!(r#"use anyhow::Result;
+
+fn main() -> Result<()> {{
+    aoc::runner::main({}::SOLUTIONS)
+}}
+"#, name)),
+    ];
+    let mut written = vec![];
+    for (file, contents) in files.iter() {
+        let path = krate.join(file);
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+    for (path, s) in edited {
+        fs::write(&path, s)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() -> Result<()> {
+        let lib = "\
+use aoc::solution::Entry;
+
+// Each day's binary, also compiled here so that the days can be run together
+
+pub const SOLUTIONS: &[Entry] = &[
+];
+";
+        let lib = register(lib, 2)?;
+        let lib = register(&lib, 1)?;
+        let lib = register(&lib, 3)?;
+        assert_eq!("\
+use aoc::solution::Entry;
+
+// Each day's binary, also compiled here so that the days can be run together
+
+#[allow(dead_code)]
+#[path = \"bin/day1.rs\"]
+pub mod day1;
+#[allow(dead_code)]
+#[path = \"bin/day2.rs\"]
+pub mod day2;
+#[allow(dead_code)]
+#[path = \"bin/day3.rs\"]
+pub mod day3;
+
+pub const SOLUTIONS: &[Entry] = &[
+    day1::SOLUTION,
+    day2::SOLUTION,
+    day3::SOLUTION,
+];
+", lib);
+        assert!(register(&lib, 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_header() {
+        let puzzle = "--- Day 1: Sonar Sweep ---\n\nThe sweep.   \n    199\n";
+        assert_eq!("\
+// https://adventofcode.com/2021/day/1
+//
+// --- Day 1: Sonar Sweep ---
+//
+// The sweep.
+//     199
+", header(2021, 1, Some(puzzle)));
+        assert_eq!("// https://adventofcode.com/2021/day/2\n//\n// --- Day 2 ---\n", header(2021, 2, None));
+    }
+}
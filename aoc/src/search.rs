@@ -0,0 +1,229 @@
+//!
+//! Binary searches for where a monotonic predicate changes
+//!
+//! A predicate that is false up to some point and true from then on has a first true value,
+//! which bisection finds in `O(log n)` calls. When there's no natural upper bound, the `_from`
+//! searches find one by doubling their step:
+//!
+//! ```
+//! use aoc::search;
+//!
+//! assert_eq!(Some(32), search::first_true(0, 100, |x: i64| x * x >= 1000));
+//! assert_eq!(Some(31), search::last_true_from(0_u64, |x| x * x < 1000));
+//! assert_eq!(None, search::first_true(0, 100, |x: i64| x > 100));
+//! ```
+//!
+//! Nothing checks the predicate everywhere, so the searches can only give `None` for one that is
+//! seen to be on the wrong side at an end of the range. With the predicate wrapped in `memoize`,
+//! a value is never evaluated twice.
+//!
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num::PrimInt;
+
+///
+/// The smallest `x` in `lo..=hi` with `pred(x)`, where `pred` is false then true, or `None` if
+/// `pred(hi)` is false
+///
+pub fn first_true<T, F>(lo: T, hi: T, mut pred: F) -> Option<T>
+    where
+        T: PrimInt,
+        F: FnMut(T) -> bool,
+{
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    Some(bisect(lo, hi, &mut pred))
+}
+
+///
+/// The largest `x` in `lo..=hi` with `pred(x)`, where `pred` is true then false, or `None` if
+/// `pred(lo)` is false
+///
+pub fn last_true<T, F>(lo: T, hi: T, mut pred: F) -> Option<T>
+    where
+        T: PrimInt,
+        F: FnMut(T) -> bool,
+{
+    if lo > hi || !pred(lo) {
+        return None;
+    }
+    match first_true(lo, hi, |x| !pred(x)) {
+        Some(x) => Some(x - T::one()),
+        None => Some(hi),
+    }
+}
+
+///
+/// The smallest `x >= lo` with `pred(x)`, where `pred` is false then true, or `None` if it's
+/// still false when the steps reach the top of `T`
+///
+/// Takes `O(log d)` calls where `d` is the distance from `lo` to the answer.
+///
+pub fn first_true_from<T, F>(lo: T, mut pred: F) -> Option<T>
+    where
+        T: PrimInt,
+        F: FnMut(T) -> bool,
+{
+    // probe lo, lo + 1, lo + 3, lo + 7, ... until one is true
+    let mut below = lo;
+    let mut step = T::one();
+    let mut probe = lo;
+    loop {
+        if pred(probe) {
+            return Some(if probe == lo { lo } else { bisect(below, probe, &mut pred) });
+        }
+        if probe == T::max_value() {
+            return None;
+        }
+        below = probe + T::one();
+        probe = probe.checked_add(&step).unwrap_or_else(T::max_value);
+        step = step.checked_add(&step).unwrap_or(step);
+    }
+}
+
+///
+/// The largest `x >= lo` with `pred(x)`, where `pred` is true then false, or `None` if `pred(lo)`
+/// is false or it's still true at the top of `T`
+///
+pub fn last_true_from<T, F>(lo: T, mut pred: F) -> Option<T>
+    where
+        T: PrimInt,
+        F: FnMut(T) -> bool,
+{
+    if !pred(lo) {
+        return None;
+    }
+    first_true_from(lo, |x| !pred(x)).map(|x| x - T::one())
+}
+
+///
+/// The point in `lo..=hi` where `pred` turns from false to true, to within `tolerance`, or `None`
+/// if `pred(hi)` is false
+///
+/// The result always satisfies `pred`, and stops improving once the floats between the bounds
+/// run out.
+///
+pub fn first_true_f64<F>(mut lo: f64, mut hi: f64, tolerance: f64, mut pred: F) -> Option<f64>
+    where F: FnMut(f64) -> bool
+{
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    if pred(lo) {
+        return Some(lo);
+    }
+    while hi - lo > tolerance {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        if pred(mid) { hi = mid } else { lo = mid }
+    }
+    Some(hi)
+}
+
+///
+/// `f`, remembering its result for every argument it has seen
+///
+pub fn memoize<T, R, F>(mut f: F) -> impl FnMut(T) -> R
+    where
+        T: Hash + Eq + Clone,
+        R: Clone,
+        F: FnMut(T) -> R,
+{
+    let mut cache = HashMap::new();
+    move |x: T| {
+        if let Some(r) = cache.get(&x) {
+            return R::clone(r);
+        }
+        let r = f(x.clone());
+        cache.insert(x, r.clone());
+        r
+    }
+}
+
+/// The first true in `lo..=hi`, given `pred(hi)`
+fn bisect<T, F>(mut lo: T, mut hi: T, pred: &mut F) -> T
+    where
+        T: PrimInt,
+        F: FnMut(T) -> bool,
+{
+    while lo < hi {
+        // the floor of the average, without overflowing
+        let mid = (lo & hi) + ((lo ^ hi) >> 1);
+        if pred(mid) { hi = mid } else { lo = mid + T::one() }
+    }
+    hi
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_int() {
+        assert_eq!(Some(-5), first_true(-10, 10, |x: i64| x >= -5));
+        assert_eq!(Some(-10), first_true(-10, 10, |_: i64| true));
+        assert_eq!(None, first_true(-10, 10, |_: i64| false));
+        assert_eq!(None, first_true(10, -10, |_: i64| true));
+        // a predicate that turns false again is caught at the end
+        assert_eq!(None, first_true(0, 10, |x: i64| x == 5));
+
+        assert_eq!(Some(i64::MAX - 1), first_true(i64::MIN, i64::MAX, |x| x >= i64::MAX - 1));
+        assert_eq!(Some(u8::MAX), first_true(0, u8::MAX, |x| x == u8::MAX));
+
+        assert_eq!(Some(4), last_true(0, 10, |x: u32| x * x < 20));
+        assert_eq!(Some(10), last_true(0, 10, |_: u32| true));
+        assert_eq!(None, last_true(0, 10, |_: u32| false));
+    }
+
+    #[test]
+    fn test_from() {
+        for target in [0, 1, 2, 3, 1000, 123456789] {
+            let mut calls = 0;
+            let found = first_true_from(0_i64, |x| {
+                calls += 1;
+                x >= target
+            });
+            assert_eq!(Some(target), found);
+            assert!(calls <= 2 * 64);
+        }
+        assert_eq!(Some(u64::MAX), first_true_from(0_u64, |x| x == u64::MAX));
+        assert_eq!(None, first_true_from(0_u8, |_| false));
+        assert_eq!(Some(-3), first_true_from(-100, |x: i32| x >= -3));
+
+        assert_eq!(Some(1_000_000_000_000 / 13312), last_true_from(1, |x: i64| x * 13312 <= 1_000_000_000_000));
+        assert_eq!(None, last_true_from(5, |x: i64| x < 5));
+        assert_eq!(None, last_true_from(0_u16, |_| true));
+    }
+
+    #[test]
+    fn test_f64() {
+        let root2 = first_true_f64(0.0, 2.0, 1e-12, |x| x * x >= 2.0).unwrap();
+        assert!((root2 - 2_f64.sqrt()).abs() <= 1e-12);
+        assert!(root2 * root2 >= 2.0);
+        // runs out of precision before reaching the tolerance
+        assert_eq!(Some(1.0), first_true_f64(0.0, 1e20, 0.0, |x| x >= 1.0));
+        assert_eq!(None, first_true_f64(0.0, 1.0, 1e-9, |x| x > 1.0));
+    }
+
+    #[test]
+    fn test_memoize() {
+        let calls = Cell::new(0);
+        let mut square = memoize(|x: i64| {
+            calls.set(calls.get() + 1);
+            x * x
+        });
+        assert_eq!(Some(32), first_true(0, 100, |x| square(x) >= 1000));
+        let first_calls = calls.get();
+        assert_eq!(Some(32), first_true(0, 100, |x| square(x) >= 1000));
+        assert_eq!(1024, square(32));
+        assert_eq!(first_calls, calls.get());
+        assert_eq!(Some(31), last_true(0, 100, |x| square(x) < 1000));
+    }
+}
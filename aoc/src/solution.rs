@@ -0,0 +1,172 @@
+//!
+//! The shape shared by every day's solution
+//!
+//! Each day implements `Solution` on a private type and exports it as a `SOLUTION` entry, which
+//! each year collects into its `SOLUTIONS` registry.
+//!
+
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::bench::{self, Timings};
+
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<impl Display>;
+    fn part2(input: &Self::Input) -> Result<impl Display>;
+
+    ///
+    /// A picture of how `part` was solved, such as the grid an OCR answer was read from
+    ///
+    fn render(_input: &Self::Input, _part: u8) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+///
+/// The answer to one part, as displayed by the solution
+///
+/// `elapsed` covers solving the part, but not parsing or rendering.
+///
+#[derive(Eq, PartialEq, Clone, Default, Debug)]
+pub struct Answer {
+    pub part: u8,
+    pub value: String,
+    pub elapsed: Duration,
+    pub artifact: Option<String>,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+///
+/// A `Solution` with its types erased, so that days can be listed together
+///
+#[derive(Copy, Clone)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    solve: fn(&str, &[u8], bool) -> Result<Vec<Answer>>,
+    bench: fn(&str, &[u8], &bench::Config) -> Result<Timings>,
+}
+
+impl Entry {
+    pub const fn new<S: Solution>() -> Entry {
+        Entry { year: S::YEAR, day: S::DAY, solve: solve::<S>, bench: bench::run::<S> }
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("input/aoc{}/day{}", self.year, self.day)
+    }
+
+    pub fn examples_path(&self) -> String {
+        format!("input/aoc{}/examples/day{}", self.year, self.day)
+    }
+
+    pub fn source_path(&self) -> String {
+        format!("aoc{}/src/bin/day{}.rs", self.year, self.day)
+    }
+
+    ///
+    /// Parse `input` once and then solve each of `parts`, in order
+    ///
+    pub fn solve(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>> {
+        (self.solve)(input, parts, false)
+    }
+
+    ///
+    /// Like `solve`, but also render the artifact of each part that has one
+    ///
+    pub fn solve_rendered(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>> {
+        (self.solve)(input, parts, true)
+    }
+
+    ///
+    /// Time parsing `input` and then each of `parts`, see `bench::run`
+    ///
+    pub fn bench(&self, input: &str, parts: &[u8], config: &bench::Config) -> Result<Timings> {
+        (self.bench)(input, parts, config)
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entry({} day {})", self.year, self.day)
+    }
+}
+
+fn solve<S: Solution>(input: &str, parts: &[u8], render: bool) -> Result<Vec<Answer>> {
+    let input = S::parse(input).context("parse")?;
+    parts.iter()
+        .map(|&part| solve_part::<S>(&input, part, render).with_context(|| format!("part {}", part)))
+        .collect()
+}
+
+fn solve_part<S: Solution>(input: &S::Input, part: u8, render: bool) -> Result<Answer> {
+    let start = Instant::now();
+    let value = match part {
+        1 => S::part1(input)?.to_string(),
+        2 => S::part2(input)?.to_string(),
+        _ => bail!("no part {}", part),
+    };
+    let elapsed = start.elapsed();
+    let artifact = if render { S::render(input, part)? } else { None };
+    Ok(Answer { part, value, elapsed, artifact })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Input = Vec<i32>;
+
+        fn parse(input: &str) -> Result<Vec<i32>> {
+            Ok(input.split_whitespace().map(|s| s.parse()).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(nums: &Vec<i32>) -> Result<impl Display> {
+            Ok(nums.iter().sum::<i32>())
+        }
+
+        fn part2(nums: &Vec<i32>) -> Result<impl Display> {
+            nums.iter().max().ok_or_else(|| anyhow!("empty"))
+        }
+
+        fn render(nums: &Vec<i32>, part: u8) -> Result<Option<String>> {
+            Ok((part == 2).then(|| "#".repeat(*nums.iter().max().unwrap() as usize)))
+        }
+    }
+
+    #[test]
+    fn test_entry() {
+        let entry = Entry::new::<Sum>();
+        assert_eq!("input/aoc2000/day1", entry.input_path());
+        let values = |answers: Vec<Answer>| answers.into_iter().map(|a| a.value).collect::<Vec<_>>();
+        assert_eq!(vec!["6", "3"], values(entry.solve("1 2 3", &[1, 2]).unwrap()));
+        assert_eq!("3", entry.solve("1 2 3", &[2]).unwrap()[0].value);
+        let artifacts = |answers: Vec<Answer>| answers.into_iter().map(|a| a.artifact).collect::<Vec<_>>();
+        assert_eq!(vec![None, None], artifacts(entry.solve("1 2 3", &[1, 2]).unwrap()));
+        assert_eq!(vec![None, Some("###".to_string())], artifacts(entry.solve_rendered("1 2 3", &[1, 2]).unwrap()));
+        assert!(entry.solve("1 x 3", &[1]).is_err());
+        assert!(entry.solve("", &[2]).is_err());
+        assert!(entry.solve("1", &[3]).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+//!
+//! Checking answers against the ones recorded in each day's source
+//!
+//! Every day's header quotes the puzzle text, including a `// Your puzzle answer was ...` line for
+//! each part that has been solved. Those lines are taken in order as the answers to parts 1 and 2.
+//!
+
+use std::fmt;
+
+use crate::solution::Answer;
+
+const RECORDED: &str = "// Your puzzle answer was ";
+
+pub fn recorded_answers(source: &str) -> Vec<String> {
+    source.lines()
+        .filter_map(|line| line.trim().strip_prefix(RECORDED))
+        .map(|answer| answer.strip_suffix('.').unwrap_or(answer).to_string())
+        .collect()
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail { .. } => "FAIL",
+            Status::Missing => "missing",
+        })
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Check {
+    pub answer: Answer,
+    pub status: Status,
+}
+
+///
+/// Compare each answer with the recorded answer for its part, if there is one
+///
+pub fn check(answers: Vec<Answer>, recorded: &[String]) -> Vec<Check> {
+    answers.into_iter()
+        .map(|answer| {
+            let status = match recorded.get(answer.part as usize - 1) {
+                None => Status::Missing,
+                Some(expected) if *expected == answer.value => Status::Pass,
+                Some(expected) => Status::Fail { expected: expected.clone() },
+            };
+            Check { answer, status }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let source = "\
+// What is the sum?
+//
+// Your puzzle answer was 2890527621.
+// --- Part Two ---
+//
+// Your puzzle answer was GCPHL.
+
+fn main() {}
+";
+        let recorded = recorded_answers(source);
+        assert_eq!(vec!["2890527621", "GCPHL"], recorded);
+
+        let answer = |part, value: &str| Answer { part, value: value.to_string(), ..Answer::default() };
+        let statuses = check(vec![answer(1, "2890527621"), answer(2, "GCPH")], &recorded).into_iter()
+            .map(|c| c.status)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Status::Pass, Status::Fail { expected: "GCPHL".to_string() }], statuses);
+        assert_eq!(Status::Missing, check(vec![answer(2, "1")], &recorded[..1])[0].status);
+    }
+}
@@ -40,23 +40,42 @@
 //
 // Your puzzle answer was 4839845.
 
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::error;
+use aoc::solution::{Entry, Solution};
 
-fn main() -> Result<(), anyhow::Error> {
-    let input = fs::read_to_string("input/aoc2019/day1")?;
-    let nums: Vec<i32> =
+type Result<T> = std::result::Result<T, anyhow::Error>;
+
+struct Day1;
+
+pub const SOLUTION: Entry = Entry::new::<Day1>();
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
         input.trim()
             .lines()
-            .enumerate()
-            .map(|(i, s)| s.parse()
-                .with_context(|| format!("Failed to parse {} at line {}", s, i + 1)))
-            .collect::<Result<Vec<_>, _>>()?;
-
-    println!("{}", part1(&nums));
-    println!("{}", part2(&nums));
-    Ok(())
+            .map(|s| error::parse_at(input, s))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(Into::into)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        Ok(part1(nums))
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        Ok(part2(nums))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn part1(nums: &[i32]) -> i32 {
@@ -76,62 +76,70 @@
 //
 // Your puzzle answer was PCKRLPUK.
 
-use std::{fs, iter, thread};
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::sync::mpsc;
+use std::{iter, thread};
 
-use anyhow::Context;
+use aoc::geom::{Direction, Vector2};
+use aoc::ocr;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::geom::{Direction, Point};
-use crate::prog::{Prog, Reply};
+use self::prog::{Prog, Reply};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day11")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-
-    let (_, painted) = paint(&nums, 0)?;
-    println!("{}", painted.len());
-
-    let (points, _) = paint(&nums, 1)?;
-    for point in points.keys() { // check
-        assert!(point.y >= 0);
-        assert!(point.x >= 0);
+struct Day11;
+
+pub const SOLUTION: Entry = Entry::new::<Day11>();
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 11;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
     }
-    let max_y = points.keys().map(|p| p.y).max().unwrap();
-    let max_x = points.keys().map(|p| p.x).max().unwrap();
-    pprint(points, max_y as usize, max_x as usize);
-    Ok(())
-}
 
-fn pprint(points: HashMap<Point, i64>,
-          max_y: usize,
-          max_x: usize) {
-    let mut grid = vec![vec![b' '; max_x + 1]; max_y + 1];
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        let (_, painted) = paint(nums, 0)?;
+        Ok(painted.len())
+    }
 
-    let white_tiles = points.iter().filter(|(_, &t)| t == 1);
-    for (&point, _) in white_tiles {
-        grid[point.y as usize][point.x as usize] = b'#';
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        Ok(ocr::recognize_points(white_tiles(nums)?)?)
     }
 
-    grid.into_iter()
-        .map(|row| String::from_utf8(row).unwrap())
-        .for_each(|s| println!("{}", s));
+    fn render(nums: &Self::Input, part: u8) -> Result<Option<String>> {
+        if part != 2 {
+            return Ok(None);
+        }
+        Ok(Some(ocr::draw_points(white_tiles(nums)?)?))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
+}
+
+fn white_tiles(nums: &[i64]) -> Result<Vec<(i64, i64)>> {
+    let (points, _) = paint(nums, 1)?;
+    Ok(points.iter()
+        .filter(|(_, &t)| t == 1)
+        .map(|(p, _)| (p.x() as i64, p.y() as i64))
+        .collect())
 }
 
-fn paint(nums: &Vec<i64>,
+fn paint(nums: &[i64],
          initial_tile: i64)
-         -> Result<(HashMap<Point, i64>, HashSet<Point>)> {
+         -> Result<(HashMap<Vector2<i32>, i64>, HashSet<Vector2<i32>>)> {
     let (input, from_input) = mpsc::sync_channel(16);
     let (output, from_output) = mpsc::sync_channel(0);
 
-    let nums = nums.clone();
+    let nums = nums.to_vec();
     let thread = thread::spawn(move || -> Result<()> {
         Prog::new(nums, from_input, [output].to_vec()).exec()?;
         Ok(())
@@ -139,7 +147,7 @@ fn paint(nums: &Vec<i64>,
 
     let mut grid = HashMap::new();
     let mut painted = HashSet::new();
-    let mut pos = Point::new(0, 0);
+    let mut pos = Vector2::zero();
     let mut direction = Direction::N;
     grid.insert(pos, initial_tile);
 
@@ -161,90 +169,13 @@ fn paint(nums: &Vec<i64>,
         grid.insert(pos, new_tile);
         painted.insert(pos);
 
-        direction = match direction {
-            Direction::N => if turn == 0 { Direction::W } else { Direction::E },
-            Direction::E => if turn == 0 { Direction::N } else { Direction::S },
-            Direction::S => if turn == 0 { Direction::E } else { Direction::W },
-            Direction::W => if turn == 0 { Direction::S } else { Direction::N },
-        };
-        pos += direction.dydx();
+        direction = if turn == 0 { direction.ccw(2) } else { direction.cw(2) };
+        pos += direction.dxdy();
     }
     thread.join().unwrap()?;
     Ok((grid, painted))
 }
 
-mod geom {
-    use std::ops::{Add, AddAssign};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Point {
-        pub y: i32,
-        pub x: i32,
-    }
-
-    impl Point {
-        pub fn new(y: i32, x: i32) -> Point {
-            Point { y, x }
-        }
-
-        #[allow(dead_code)]
-        pub fn add(&mut self, v: Vector) {
-            self.y += v.dy;
-            self.x += v.dx;
-        }
-    }
-
-    impl Add<Vector> for Point {
-        type Output = Self;
-
-        fn add(self, vector: Vector) -> Self {
-            Self {
-                y: self.y + vector.dy,
-                x: self.x + vector.dx,
-            }
-        }
-    }
-
-    impl AddAssign<Vector> for Point {
-        fn add_assign(&mut self, vector: Vector) {
-            *self = Self {
-                y: self.y + vector.dy,
-                x: self.x + vector.dx,
-            };
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector {
-        pub dy: i32,
-        pub dx: i32,
-    }
-
-    impl Vector {
-        pub fn new(dy: i32, dx: i32) -> Vector {
-            Vector { dy, dx }
-        }
-    }
-
-    pub enum Direction {
-        N,
-        E,
-        S,
-        W,
-    }
-
-    impl Direction {
-        pub fn dydx(&self) -> Vector {
-            match self {
-                Direction::N => Vector::new(-1, 0),
-                Direction::E => Vector::new(0, 1),
-                Direction::S => Vector::new(1, 0),
-                Direction::W => Vector::new(0, -1),
-            }
-        }
-    }
-}
-
 mod prog {
     use std::convert::TryFrom;
     use std::iter;
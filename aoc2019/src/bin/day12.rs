@@ -230,46 +230,60 @@
 //
 // Your puzzle answer was 281691380235984.
 
-use std::cmp::Ordering;
-use std::collections::HashSet;
-use std::fs;
+use std::fmt::Display;
 
-use lazy_static::lazy_static;
-use regex::Regex;
-
-use crate::geom::Vector3;
+use aoc::cycle;
+use aoc::geom::Vector3;
+use aoc::numtheory;
+use aoc::parse::{self, int, lines, tag, Parser};
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day12")?;
-    let mut bodies = parse(&input)?;
-    let bodies_clone = bodies.clone();
-    for _ in 0..1000 {
-        sim(&mut bodies);
+struct Day12;
+
+pub const SOLUTION: Entry = Entry::new::<Day12>();
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 12;
+
+    type Input = Vec<Body>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(bodies: &Self::Input) -> Result<impl Display> {
+        let mut bodies = bodies.clone();
+        for _ in 0..1000 {
+            sim(&mut bodies);
+        }
+        Ok(energy(bodies))
+    }
+
+    fn part2(bodies: &Self::Input) -> Result<impl Display> {
+        Ok(seen_steps(bodies.clone()))
     }
-    println!("{}", energy(bodies));
-    println!("{}", seen_steps(bodies_clone));
-    Ok(())
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn parse(s: &str) -> Result<Vec<Body>> {
-    lazy_static! { static ref RE: Regex = Regex::new(r"<x=(-?\d+), y=(-?\d+), z=(-?\d+)>").unwrap(); }
-    let mut res = Vec::new();
-    for cap in RE.captures_iter(s) {
-        let x = cap[1].parse::<i32>()?;
-        let y = cap[2].parse::<i32>()?;
-        let z = cap[3].parse::<i32>()?;
-        let body = Body::new(Vector3::new(x, y, z));
-        res.push(body);
-    }
-    Ok(res)
+    let pos = tag("<x=").then(int())
+        .and(tag(", y=").then(int()))
+        .and(tag(", z=").then(int()))
+        .skip(tag(">"));
+    let body = pos.map(|((x, y), z)| Body::new(Vector3::new([x, y, z])));
+    Ok(parse::all(s, lines(body))?)
 }
 
 fn sim(bodies: &mut Vec<Body>) {
     for i in 0..bodies.len() {
         for j in i + 1..bodies.len() {
-            let del = delta_vec3(bodies[i].pos, bodies[j].pos);
+            let del = (bodies[j].pos - bodies[i].pos).signum();
             bodies[i].vel += del;
             bodies[j].vel -= del;
         }
@@ -280,83 +294,27 @@ fn sim(bodies: &mut Vec<Body>) {
 }
 
 fn energy(bodies: Vec<Body>) -> i32 {
-    let pots = bodies.iter().map(|b|
-        b.pos.x.abs() + b.pos.y.abs() + b.pos.z.abs());
-    let kins = bodies.iter().map(|b|
-        b.vel.x.abs() + b.vel.y.abs() + b.vel.z.abs());
-    pots.zip(kins).map(|(p, k)| p * k).sum()
-}
-
-fn delta_vec3(from: Vector3, to: Vector3) -> Vector3 {
-    Vector3 {
-        x: delta_i32(from.x, to.x),
-        y: delta_i32(from.y, to.y),
-        z: delta_i32(from.z, to.z),
-    }
-}
-
-fn delta_i32(from: i32, to: i32) -> i32 {
-    match from.cmp(&to) {
-        Ordering::Less => { 1 }
-        Ordering::Equal => { 0 }
-        Ordering::Greater => { -1 }
-    }
+    bodies.iter().map(|b| b.pos.manhattan() * b.vel.manhattan()).sum()
 }
 
+///
+/// Each axis moves independently and every step can be undone, so each loops back to the start on
+/// its own period and all of them together on the least common multiple
+///
 fn seen_steps(bodies: Vec<Body>) -> u64 {
-    fn count_steps(mut bodies: Vec<Body>) -> i32 {
-        let mut seen = HashSet::new();
-        let mut steps = 0;
-        while !seen.contains(&bodies) {
-            seen.insert(bodies.clone());
-            sim(&mut bodies);
-            steps += 1;
-        }
-        steps
-    }
-    let bodies_x: Vec<_> = bodies.iter().map(|body| {
-        let mut pos = Vector3::ZERO;
-        let vel = Vector3::ZERO;
-        pos.x = body.pos.x;
-        Body { pos, vel }
-    }).collect();
-
-    let bodies_y: Vec<_> = bodies.iter().map(|body| {
-        let mut pos = Vector3::ZERO;
-        let vel = Vector3::ZERO;
-        pos.y = body.pos.y;
-        Body { pos, vel }
-    }).collect();
-
-    let bodies_z: Vec<_> = bodies.iter().map(|body| {
-        let mut pos = Vector3::ZERO;
-        let vel = Vector3::ZERO;
-        pos.z = body.pos.z;
-        Body { pos, vel }
-    }).collect();
-
-    let steps_x = count_steps(bodies_x) as u64;
-    let steps_y = count_steps(bodies_y) as u64;
-    let steps_z = count_steps(bodies_z) as u64;
-
-    lcm(lcm(steps_x, steps_y), steps_z)
-}
-
-fn gcd(mut a: u64, mut b: u64) -> u64 {
-    let mut t;
-    while b != 0 {
-        t = b;
-        b = a % b;
-        a = t;
-    }
-    a
-}
-
-fn lcm(a: u64, b: u64) -> u64 {
-    if a == 0 && b == 0 {
-        return 0;
-    }
-    (a * b) / gcd(a, b)
+    let step = |bodies: &Vec<Body>| {
+        let mut bodies = bodies.clone();
+        sim(&mut bodies);
+        bodies
+    };
+    (0..3).map(|axis| {
+        let bodies_axis = bodies.iter().map(|body| {
+            let mut pos = Vector3::zero();
+            pos[axis] = body.pos[axis];
+            Body { pos, vel: Vector3::zero() }
+        }).collect();
+        cycle::detect_cycle(bodies_axis, step).len as u64
+    }).fold(1, numtheory::lcm)
 }
 
 #[cfg(test)]
@@ -396,66 +354,16 @@ mod tests {
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
 struct Body {
-    pos: Vector3,
-    vel: Vector3,
+    pos: Vector3<i32>,
+    vel: Vector3<i32>,
 }
 
 impl Body {
-    pub fn new(pos: Vector3) -> Body {
+    pub fn new(pos: Vector3<i32>) -> Body {
         Body {
             pos,
-            vel: Vector3::ZERO,
+            vel: Vector3::zero(),
         }
     }
 }
 
-mod geom {
-    use std::ops::{AddAssign, Neg, SubAssign};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector3 {
-        pub x: i32,
-        pub y: i32,
-        pub z: i32,
-    }
-
-    impl Vector3 {
-        pub const ZERO: Vector3 = Vector3 { x: 0, y: 0, z: 0 };
-
-        pub fn new(x: i32, y: i32, z: i32) -> Vector3 {
-            Vector3 { x, y, z }
-        }
-    }
-
-    impl AddAssign for Vector3 {
-        fn add_assign(&mut self, other: Vector3) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-                z: self.z + other.z,
-            };
-        }
-    }
-
-    impl SubAssign for Vector3 {
-        fn sub_assign(&mut self, other: Vector3) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-                z: self.z - other.z,
-            };
-        }
-    }
-
-    impl Neg for Vector3 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector3 {
-                x: -self.x,
-                y: -self.y,
-                z: -self.z,
-            }
-        }
-    }
-}
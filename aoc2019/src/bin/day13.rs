@@ -35,37 +35,71 @@
 //
 // Your puzzle answer was 21415.
 
-use std::{fs, thread};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::sync::mpsc;
+use std::thread;
 
-use anyhow::Context;
+use aoc::geom::Vector2;
+use aoc::parse;
+use aoc::render::{Animation, Colour, Palette};
+use aoc::solution::{Entry, Solution};
 
-use crate::geom::Vector2;
-use crate::prog::{Prog, Reply};
+use self::prog::{Prog, Reply};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day13;
+
+pub const SOLUTION: Entry = Entry::new::<Day13>();
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 13;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        let mut anim = animation()?;
+        let (num_blocks, _) = play(nums, false, &mut anim)?;
+        anim.finish()?;
+        Ok(num_blocks)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        let mut anim = animation()?;
+        let (_, score) = play(nums, true, &mut anim)?;
+        anim.finish()?;
+        Ok(score)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day13")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-
-    play(&nums, false)?;
-    play(&nums, true)?;
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn play(nums: &Vec<i64>, play_for_free: bool) -> Result<()> {
+fn animation() -> Result<Animation> {
+    let palette = Palette::new()
+        .with('#', Colour::Grey)
+        .with('.', Colour::Magenta)
+        .with('_', Colour::Cyan)
+        .with('O', Colour::Yellow);
+    Ok(Animation::from_env()?.with_palette(palette))
+}
+
+///
+/// Play until the program stops, returning the number of blocks drawn and the final score
+///
+fn play(nums: &[i64], play_for_free: bool, anim: &mut Animation) -> Result<(i32, i64)> {
     let (input, from_input) = mpsc::sync_channel(0);
     let (output, from_output) = mpsc::sync_channel(0);
 
-    let mut nums = nums.clone();
+    let mut nums = nums.to_vec();
     if play_for_free { nums[0] = 2; }
     let thread = thread::spawn(move || -> Result<()> {
         Prog::new(nums, from_input, output).exec()?;
@@ -89,11 +123,11 @@ fn play(nums: &Vec<i64>, play_for_free: bool) -> Result<()> {
                     i += 1;
                 }
                 Reply::Stopped => {
-                    pprint(&grid);
+                    anim.frame(|| format!("{}score: {}", render(&grid), score));
                     break 'outer;
                 }
                 Reply::Blocked => {
-                    // pprint(&grid);
+                    anim.frame(|| format!("{}score: {}", render(&grid), score));
                     input.send(
                         match paddle_pos_x.cmp(&ball_pos_x) {
                             Ordering::Less => 1,
@@ -104,43 +138,41 @@ fn play(nums: &Vec<i64>, play_for_free: bool) -> Result<()> {
             }
         }
 
-        let pos = Vector2::new(outputs[0], outputs[1]);
+        let pos = Vector2::new([outputs[0], outputs[1]]);
         let id = outputs[2];
         if id == 2 { num_blocks += 1; }
 
-        if pos.x >= 0 {
+        if pos.x() >= 0 {
             grid.insert(pos, id_to_tile(id)?);
-            if id == 4 { ball_pos_x = pos.x; }
-            if id == 3 { paddle_pos_x = pos.x; }
-        } else if pos.x == -1 && pos.y == 0 {
+            if id == 4 { ball_pos_x = pos.x(); }
+            if id == 3 { paddle_pos_x = pos.x(); }
+        } else if pos.x() == -1 && pos.y() == 0 {
             score = id;
         } else {
             anyhow::bail!("invalid pos value: {:?}", pos);
         }
     }
     thread.join().unwrap()?;
-    println!("num blocks: {}", num_blocks);
-    println!("score: {}", score);
-
-    Ok(())
+    Ok((num_blocks, score))
 }
 
-fn pprint(grid: &HashMap<Vector2, u8>) {
-    for point in grid.keys() { // check
-        assert!(point.y >= 0);
-        assert!(point.x >= 0);
+fn render(grid: &HashMap<Vector2, u8>) -> String {
+    if grid.is_empty() {
+        return String::new();
     }
-    let max_y = grid.keys().map(|p| p.y).max().unwrap() as usize;
-    let max_x = grid.keys().map(|p| p.x).max().unwrap() as usize;
-    let mut screen = vec![vec![b' '; max_x + 1]; max_y + 1];
+    let min_y = grid.keys().map(|p| p.y()).min().unwrap();
+    let min_x = grid.keys().map(|p| p.x()).min().unwrap();
+    let max_y = grid.keys().map(|p| p.y()).max().unwrap();
+    let max_x = grid.keys().map(|p| p.x()).max().unwrap();
+    let mut screen = vec![vec![b' '; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
 
     for (&point, &tile) in grid {
-        screen[point.y as usize][point.x as usize] = tile;
+        screen[(point.y() - min_y) as usize][(point.x() - min_x) as usize] = tile;
     }
 
     screen.into_iter()
-        .map(|row| String::from_utf8(row).unwrap())
-        .for_each(|s| println!("{}", s));
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
 }
 
 fn id_to_tile(id: i64) -> Result<u8> {
@@ -300,20 +332,3 @@ mod prog {
         }
     }
 }
-
-mod geom {
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new(x: i64, y: i64) -> Vector2 {
-            Vector2 { x, y }
-        }
-    }
-}
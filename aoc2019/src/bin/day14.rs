@@ -107,123 +107,98 @@
 //
 // Your puzzle answer was 12039407.
 
-use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::hash::Hash;
+use std::collections::HashMap;
+use std::fmt::Display;
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use aoc::error::AocError;
+use aoc::graph::Graph;
+use aoc::parse::{self, ident, int, lines, sep_by1, tag, Parser};
+use aoc::search;
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day14")?;
+struct Day14;
 
-    let recipes = parse(&input)?;
-    let min_ore_per_fuel = get_required_ore(&recipes, 1);
-    let ore = 1000000000000i64;
-    let max_fuel = max_fuel(&recipes, ore, min_ore_per_fuel);
+pub const SOLUTION: Entry = Entry::new::<Day14>();
 
-    println!("{}", min_ore_per_fuel);
-    println!("{}", max_fuel);
+impl Solution for Day14 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 14;
 
-    Ok(())
-}
+    type Input = HashMap<String, Recipe>;
 
-fn parse(s: &str) -> Result<HashMap<String, Recipe>> {
-    fn parse_recipe(s: &str) -> Result<Recipe> {
-        const LINE_RE_STR: &str = r"(\d+ [A-Z]+, )*\d+ [A-Z]+ => \d+ [A-Z]+";
-        const CHEM_RE_STR: &str = r"(\d+) ([A-Z]+)";
-        lazy_static! {
-            static ref LINE_RE: Regex = Regex::new(LINE_RE_STR).unwrap();
-            static ref CHEM_RE: Regex = Regex::new(CHEM_RE_STR).unwrap();
-        }
-        if !LINE_RE.is_match(s) { anyhow::bail!("invalid line: {}", s); }
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
 
-        let mut chems = Vec::new();
-        for cap in CHEM_RE.captures_iter(s) {
-            let num = cap[1].parse::<i64>()?;
-            let name = cap[2].to_string();
-            let chem = Chem { num, name };
-            chems.push(chem);
-        }
-        let output = chems.pop().unwrap();
-        Ok(Recipe { inputs: chems, output })
+    fn part1(recipes: &Self::Input) -> Result<impl Display> {
+        Ok(get_required_ore(recipes, 1))
+    }
+
+    fn part2(recipes: &Self::Input) -> Result<impl Display> {
+        max_fuel(recipes, 1000000000000).ok_or_else(|| AocError::no_solution("not enough ore for any fuel").into())
     }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
+}
+
+fn parse(s: &str) -> Result<HashMap<String, Recipe>> {
+    let chem = int().skip(tag(" ")).and(ident())
+        .map(|(num, name)| Chem { num, name: name.to_string() });
+    let recipe = sep_by1(chem.by_ref(), tag(", ")).skip(tag(" => ")).and(chem.by_ref())
+        .map(|(inputs, output)| Recipe { inputs, output });
     let mut res = HashMap::new();
-    let recipes = s.lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| parse_recipe(line));
-    for recipe in recipes {
-        let recipe = recipe?;
+    for recipe in parse::all(s, lines(recipe))? {
         res.insert(recipe.output.name.clone(), recipe);
     }
+    if !res.contains_key("FUEL") {
+        return Err(AocError::invalid("no reaction makes FUEL").into());
+    }
+    for recipe in res.values() {
+        if let Some(chem) = recipe.inputs.iter().find(|chem| chem.name != "ORE" && !res.contains_key(&chem.name)) {
+            return Err(AocError::invalid(format!("no reaction makes {}", chem.name)).into());
+        }
+    }
+    if let Err(err) = reaction_graph(&res).topo_sort() {
+        return Err(AocError::invalid(format!("reactions go round in a {}", err)).into());
+    }
     Ok(res)
 }
 
-fn get_required_ore(recipes: &HashMap<String, Recipe>, required_fuel: i64) -> i64 {
-    let mut supplies = HashMap::new();
-    let mut to_craft = HashSet::new();
-
-    supplies.insert("FUEL", -required_fuel);
-    to_craft.insert("FUEL");
-    while !to_craft.is_empty() {
-        let next_output = take_next_elem(&mut to_craft);
-        let recipe = recipes.get(next_output).unwrap();
-        let deficitp = supplies.entry(next_output).or_insert(0);
-        let deficit = *deficitp;
-
-        let crafts_required =
-            (deficit.abs() / recipe.output.num) +
-                if deficit % recipe.output.num != 0 { 1 } else { 0 };
-        *deficitp += crafts_required * recipe.output.num;
-
+///
+/// The graph from each chemical to the inputs of the reaction making it, weighted by how much of
+/// each it takes
+///
+fn reaction_graph(recipes: &HashMap<String, Recipe>) -> Graph<&str, i64> {
+    let mut graph = Graph::new();
+    for (name, recipe) in recipes {
+        graph.add_node(name.as_str());
         for input in recipe.inputs.iter() {
-            let chem_name = &*input.name;
-            let chem_supply = supplies.entry(chem_name).or_insert(0);
-
-            *chem_supply -= crafts_required * input.num;
-            if *chem_supply < 0 && chem_name != "ORE" {
-                to_craft.insert(chem_name);
-            }
+            graph.add_edge(name.as_str(), input.name.as_str(), input.num);
         }
     }
-    -supplies["ORE"]
+    graph
 }
 
-fn take_next_elem<'a, T>(set: &mut HashSet<&'a T>) -> &'a T
-    where
-        &'a T: Eq + Hash,
-        T: Eq + Hash + ?Sized {
-    let elem = *set.iter().next().unwrap();
-    set.take(elem).unwrap()
-}
-
-fn search<F>(func: F, mut left: i64, mut right: i64, target: i64) -> i64
-    where F: Fn(i64) -> i64 {
-    assert!(left < right);
-    assert!(func(left) <= target); // invariant
-    assert!(func(right) >= target); // invariant
-
-    while left <= right {
-        let mid = (left + right) / 2;
-        match func(mid).cmp(&target) {
-            Ordering::Less => left = mid + 1,
-            Ordering::Equal => return mid,
-            Ordering::Greater => right = mid - 1,
-        }
-    }
-    right
+fn get_required_ore(recipes: &HashMap<String, Recipe>, required_fuel: i64) -> i64 {
+    // all of a chemical's uses are counted before the reactions making it are run
+    let required = reaction_graph(recipes)
+        .propagate([("FUEL", required_fuel)], |&chem, &required, &num| {
+            let makes = recipes[chem].output.num;
+            (required + makes - 1) / makes * num
+        })
+        .expect("reactions are checked for cycles when parsed");
+    required.get("ORE").copied().unwrap_or(0)
 }
 
-fn max_fuel(recipes: &HashMap<String, Recipe>,
-            ore: i64,
-            min_ore_per_fuel: i64) -> i64 {
-    let fuel = ore / min_ore_per_fuel;
-    let func = |i| get_required_ore(&recipes, i);
-    search(func, fuel, fuel * 2, ore)
+///
+/// The most fuel `ore` can make, or `None` if it can't make any
+///
+fn max_fuel(recipes: &HashMap<String, Recipe>, ore: i64) -> Option<i64> {
+    search::last_true_from(1, |fuel| get_required_ore(recipes, fuel) <= ore)
 }
 
 #[cfg(test)]
@@ -260,7 +235,7 @@ mod tests {
         let recipes = parse(s)?;
         let min_ore_per_fuel = get_required_ore(&recipes, 1);
         assert_eq!(13312, min_ore_per_fuel);
-        assert_eq!(82892753, max_fuel(&recipes, ore, min_ore_per_fuel));
+        assert_eq!(Some(82892753), max_fuel(&recipes, ore));
 
         let s = r"
             2 VPVL, 7 FWMGM, 2 CXFTF, 11 MNCFX => 1 STKFG
@@ -279,7 +254,7 @@ mod tests {
         let recipes = parse(s)?;
         let min_ore_per_fuel = get_required_ore(&recipes, 1);
         assert_eq!(180697, min_ore_per_fuel);
-        assert_eq!(5586022, max_fuel(&recipes, ore, min_ore_per_fuel));
+        assert_eq!(Some(5586022), max_fuel(&recipes, ore));
 
         let s = r"
             171 ORE => 8 CNZTR
@@ -303,7 +278,12 @@ mod tests {
         let recipes = parse(s)?;
         let min_ore_per_fuel = get_required_ore(&recipes, 1);
         assert_eq!(2210736, min_ore_per_fuel);
-        assert_eq!(460664, max_fuel(&recipes, ore, min_ore_per_fuel));
+        assert_eq!(Some(460664), max_fuel(&recipes, ore));
+
+        assert!(parse("1 ORE, 1 B => 1 A\n1 A => 1 B\n1 A => 1 FUEL").is_err());
+        assert!(parse("1 ORE, 1 B => 1 A\n1 A => 1 FUEL").is_err());
+        assert!(parse("7 A 1 ORE => 1 FUEL").is_err());
+        assert!(parse("1 ORE => 1 FUEL x").is_err());
         Ok(())
     }
 }
@@ -73,34 +73,61 @@
 //
 // Your puzzle answer was 334.
 
-use std::{cmp, fs};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::cmp;
 
-use anyhow::Context;
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::parse;
+use aoc::render::{Animation, Colour, Palette};
+use aoc::solution::{Entry, Solution};
 
-use crate::geom::{Direction, Vector2};
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day15;
+
+pub const SOLUTION: Entry = Entry::new::<Day15>();
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 15;
+
+    type Input = (HashMap<Vector2, u8>, Vector2, u32);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        explore(&parse::ints(input)?)
+    }
+
+    fn part1((_, _, oxy_depth): &Self::Input) -> Result<impl Display> {
+        Ok(*oxy_depth)
+    }
+
+    fn part2((grid, oxy_pos, _): &Self::Input) -> Result<impl Display> {
+        let palette = Palette::new()
+            .with('#', Colour::Grey)
+            .with('@', Colour::Green)
+            .with('O', Colour::Cyan);
+        let mut anim = Animation::from_env()?.with_palette(palette);
+        let max_depth = oxyfill(grid, *oxy_pos, &mut anim);
+        anim.finish()?;
+        Ok(max_depth)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day15")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-
-    let (grid, oxy_pos) = explore(&nums)?;
-    oxyfill(grid, oxy_pos);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn explore(nums: &Vec<i64>) -> Result<(HashMap<Vector2, u8>, Vector2)> {
-    let pos = Vector2::ZERO;
+///
+/// Map the whole area, returning it along with the position of the oxygen system and its depth
+///
+fn explore(nums: &[i64]) -> Result<(HashMap<Vector2, u8>, Vector2, u32)> {
+    let pos = Vector2::zero();
     let mut oxy_pos = None;
-    let prog = Prog::new(nums.clone());
+    let prog = Prog::new(nums.to_vec());
     let depth = 0u32;
 
     let mut grid = HashMap::new();
@@ -115,7 +142,7 @@ fn explore(nums: &Vec<i64>) -> Result<(HashMap<Vector2, u8>, Vector2)> {
     while !to_process.is_empty() {
         let (pos, prog, depth) = to_process.pop_front().unwrap();
 
-        for dir in Direction::VALUES.iter() {
+        for dir in Direction::VALUES_4D.iter() {
             let new_pos = pos + dir.dxdy();
             if !visited.contains(&new_pos) {
                 visited.insert(new_pos);
@@ -126,9 +153,11 @@ fn explore(nums: &Vec<i64>) -> Result<(HashMap<Vector2, u8>, Vector2)> {
                     Direction::E => 4,
                     Direction::S => 2,
                     Direction::W => 3,
+                    _ => unreachable!("the droid only moves orthogonally"),
                 })?;
 
-                let tile_id = new_prog.recv_iter().next().unwrap();
+                let tile_id = new_prog.recv_iter().next()
+                    .ok_or_else(|| AocError::invalid("the droid stopped without reporting where it moved"))?;
                 grid.insert(new_pos, id_to_tile(tile_id)?);
 
                 match tile_id {
@@ -144,24 +173,25 @@ fn explore(nums: &Vec<i64>) -> Result<(HashMap<Vector2, u8>, Vector2)> {
         }
     }
 
-    let screen = grid_to_screen(&grid);
-    pprint(&screen);
-
     let oxy_pos = oxy_pos.ok_or(anyhow::anyhow!("oxygen not found"))?;
-    println!("{}", depths.get(&oxy_pos).unwrap());
-    Ok((grid, oxy_pos))
+    Ok((grid, oxy_pos, depths[&oxy_pos]))
 }
 
-fn oxyfill(grid: HashMap<Vector2, u8>, pos: Vector2) {
+fn oxyfill(grid: &HashMap<Vector2, u8>, pos: Vector2, anim: &mut Animation) -> u32 {
     let mut max_depth = 0;
+    let mut drawn_depth = None;
     let mut visited = HashSet::new();
     let mut to_process = VecDeque::new();
     visited.insert(pos);
     to_process.push_back((pos, 0));
     while !to_process.is_empty() {
         let (pos, depth) = to_process.pop_front().unwrap();
+        if drawn_depth != Some(depth) {
+            drawn_depth = Some(depth);
+            anim.frame(|| render_fill(grid, &visited));
+        }
 
-        for dir in Direction::VALUES.iter() {
+        for dir in Direction::VALUES_4D.iter() {
             let new_pos = pos + dir.dxdy();
             if grid[&new_pos] != b'#' && !visited.contains(&new_pos) {
                 visited.insert(new_pos);
@@ -170,33 +200,37 @@ fn oxyfill(grid: HashMap<Vector2, u8>, pos: Vector2) {
             }
         }
     }
-    println!("{}", max_depth);
+    max_depth
+}
+
+fn render_fill(grid: &HashMap<Vector2, u8>, filled: &HashSet<Vector2>) -> String {
+    let mut grid = grid.clone();
+    for &point in filled {
+        grid.insert(point, b'O');
+    }
+    grid_to_screen(&grid).into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
 }
 
 fn grid_to_screen(grid: &HashMap<Vector2, u8>) -> Vec<Vec<u8>> {
-    let offset_y = -grid.keys().map(|p| p.y).min().unwrap();
-    let offset_x = -grid.keys().map(|p| p.x).min().unwrap();
-    let max_y = grid.keys().map(|p| p.y).max().unwrap();
-    let max_x = grid.keys().map(|p| p.x).max().unwrap();
+    let offset_y = -grid.keys().map(|p| p.y()).min().unwrap();
+    let offset_x = -grid.keys().map(|p| p.x()).min().unwrap();
+    let max_y = grid.keys().map(|p| p.y()).max().unwrap();
+    let max_x = grid.keys().map(|p| p.x()).max().unwrap();
     let screen_y = (max_y + offset_y) as usize;
     let screen_x = (max_x + offset_x) as usize;
 
     let mut screen = vec![vec![b' '; screen_x + 1]; screen_y + 1];
 
     for (&point, &tile) in grid {
-        let y = (point.y + offset_y) as usize;
-        let x = (point.x + offset_x) as usize;
+        let y = (point.y() + offset_y) as usize;
+        let x = (point.x() + offset_x) as usize;
         screen[y][x] = tile;
     }
     screen
 }
 
-fn pprint(screen: &Vec<Vec<u8>>) {
-    screen.into_iter()
-        .map(|row| std::str::from_utf8(row).unwrap())
-        .for_each(|s| println!("{}", s));
-}
-
 fn id_to_tile(id: i64) -> Result<u8> {
     let res = match id {
         0 => b'#',
@@ -363,107 +397,3 @@ mod prog {
         }
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Neg, SubAssign};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new(x: i64, y: i64) -> Vector2 {
-            Vector2 { x, y }
-        }
-    }
-
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    pub enum Direction {
-        N,
-        E,
-        S,
-        W,
-    }
-
-    impl Direction {
-        pub const VALUES: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::S => Vector2::new(0, 1),
-                Direction::W => Vector2::new(-1, 0),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::E,
-                Direction::E => Direction::S,
-                Direction::S => Direction::W,
-                Direction::W => Direction::N,
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::W,
-                Direction::E => Direction::N,
-                Direction::S => Direction::E,
-                Direction::W => Direction::S,
-            }
-        }
-    }
-}
@@ -91,20 +91,46 @@
 //
 // Your puzzle answer was 14288025.
 
-use std::fs;
+use std::fmt::Display;
+
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day16")?;
-    let input = input.trim();
-    let res = phase_dp(input, 100);
-    println!("{}", &res[0..8]);
+struct Day16;
+
+pub const SOLUTION: Entry = Entry::new::<Day16>();
+
+impl Solution for Day16 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 16;
 
-    let msg = phase_dp(&input.repeat(10000), 100); // ~10s per phase
-    let res = offset(input, &msg);
-    println!("{}", res);
-    Ok(())
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let signal = input.trim();
+        if let Some(i) = signal.find(|c: char| !c.is_ascii_digit()) {
+            return Err(AocError::parse(input, &signal[i..], "expected a digit").into());
+        }
+        if signal.len() < 8 {
+            return Err(AocError::parse(input, signal, "expected at least 8 digits").into());
+        }
+        Ok(signal.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(phase_dp(input, 100)[0..8].to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        let msg = phase_dp(&input.repeat(10000), 100); // ~10s per phase
+        Ok(offset(input, &msg)?.to_string())
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn phase_dp(input: &str, num_phases: u32) -> String {
@@ -147,9 +173,10 @@ fn phase_dp(input: &str, num_phases: u32) -> String {
         .collect()
 }
 
-fn offset<'a>(inp: &str, out: &'a str) -> &'a str {
-    let offset = inp[0..7].parse::<usize>().unwrap();
-    &out[offset..offset + 8]
+fn offset<'a>(inp: &str, out: &'a str) -> Result<&'a str> {
+    let offset = inp[0..7].parse::<usize>()?;
+    out.get(offset..offset + 8)
+        .ok_or_else(|| AocError::invalid(format!("message offset {} is past the end", offset)).into())
 }
 
 // 0000000000000000000000000000000000000000000000000+   0
@@ -284,17 +311,17 @@ mod tests {
     fn test_long() {
         let inp = "03036732577212944063491565474664";
         let msg = phase_dp(&inp.repeat(10000), 100);
-        let res = offset(inp, &msg);
+        let res = offset(inp, &msg).unwrap();
         assert_eq!("84462026", res);
 
         let inp = "02935109699940807407585447034323";
         let msg = phase_dp(&inp.repeat(10000), 100);
-        let res = offset(inp, &msg);
+        let res = offset(inp, &msg).unwrap();
         assert_eq!("78725270", res);
 
         let inp = "03081770884921959731165446850517";
         let msg = phase_dp(&inp.repeat(10000), 100);
-        let res = offset(inp, &msg);
+        let res = offset(inp, &msg).unwrap();
         assert_eq!("53553731", res);
     }
 }
@@ -127,30 +127,47 @@
 // Your puzzle answer was 1063081.
 
 use std::collections::HashSet;
-use std::fs;
+use std::convert::TryFrom;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::geom::{Direction, Vector2};
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day17;
+
+pub const SOLUTION: Entry = Entry::new::<Day17>();
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 17;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day17")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-    part1(&nums)?;
-    part2(&nums)?;
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(nums: &Vec<i64>) -> Result<()> {
-    let mut prog = Prog::new(nums.clone());
+fn part1(nums: &[i64]) -> Result<i64> {
+    let mut prog = Prog::new(nums.to_vec());
     prog.resume()?;
     let chars = prog.recv_iter()
         .map(|i| i as u8)
@@ -159,39 +176,31 @@ fn part1(nums: &Vec<i64>) -> Result<()> {
         .filter(|cs| cs.len() > 0)
         .map(|cs| cs.to_vec())
         .collect::<Vec<_>>();
-
-    for line in &chars {
-        println!("{}", String::from_utf8(line.clone())?);
+    if chars.is_empty() {
+        return Err(AocError::invalid("the camera showed nothing").into());
     }
 
+    // rows can differ in length, so anything off the view isn't scaffold
+    let is_scaffold = |pos: Vector2| {
+        let cell = usize::try_from(pos.x()).ok().zip(usize::try_from(pos.y()).ok())
+            .and_then(|(x, y)| chars.get(y)?.get(x));
+        cell == Some(&b'#')
+    };
     let mut intersections = HashSet::new();
-    for y in 1..(chars.len() - 1) {
-        let row = &chars[y];
-        for x in 1..(row.len() - 1) {
-            if chars[y][x] == b'#' {
-                let pos = Vector2::new(x as i64, y as i64);
-                let mut is_intersection = true;
-                for dir in Direction::VALUES.iter() {
-                    let new_pos = pos + dir.dxdy();
-                    if chars[new_pos.y as usize][new_pos.x as usize] != b'#' {
-                        is_intersection = false;
-                        break;
-                    }
-                }
-                if is_intersection {
-                    intersections.insert(pos);
-                }
+    for (y, row) in chars.iter().enumerate() {
+        for x in 0..row.len() {
+            let pos = Vector2::new([x as i64, y as i64]);
+            if is_scaffold(pos) && Direction::VALUES_4D.iter().all(|dir| is_scaffold(pos + dir.dxdy())) {
+                intersections.insert(pos);
             }
         }
     }
 
     let res = intersections.into_iter()
         .fold(0, |sum, pt| {
-            sum + (pt.y * pt.x)
+            sum + (pt.y() * pt.x())
         });
-    println!("{}", res);
-
-    Ok(())
+    Ok(res)
 }
 
 // ..............................................###########......
@@ -241,9 +250,9 @@ fn part1(nums: &Vec<i64>) -> Result<()> {
 // R12 L10 L10 L12 R6 L8 L12
 // R6 L12 R6
 
-fn part2(nums: &Vec<i64>) -> Result<()> {
-    let mut nums = nums.clone();
-    nums[0] = 2;
+fn part2(nums: &[i64]) -> Result<i64> {
+    let mut nums = nums.to_vec();
+    *nums.first_mut().ok_or_else(|| AocError::invalid("empty program"))? = 2;
     let mut prog = Prog::new(nums);
 
     let s0 = "A,A,B,C,B,C,B,C,B,A\n";
@@ -258,15 +267,8 @@ fn part2(nums: &Vec<i64>) -> Result<()> {
     send_str_to_prog(s4, &mut prog);
     prog.resume()?;
 
-    let mut out = prog.recv_iter().collect::<Vec<_>>();
-    let score = out.pop().unwrap();
-
-    let chars = out.into_iter().map(|i| i as u8).collect::<Vec<_>>();
-    let s = String::from_utf8(chars)?;
-    println!("{}", s);
-    println!("{}", score);
-
-    Ok(())
+    let score = prog.recv_iter().next_back();
+    score.ok_or_else(|| anyhow::anyhow!("no output"))
 }
 
 fn send_str_to_prog(s: &str, prog: &mut Prog) {
@@ -432,108 +434,3 @@ mod prog {
         }
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Neg, SubAssign};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new(x: i64, y: i64) -> Vector2 {
-            Vector2 { x, y }
-        }
-    }
-
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    pub enum Direction {
-        N,
-        E,
-        S,
-        W,
-    }
-
-    impl Direction {
-        pub const VALUES: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::S => Vector2::new(0, 1),
-                Direction::W => Vector2::new(-1, 0),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::E,
-                Direction::E => Direction::S,
-                Direction::S => Direction::W,
-                Direction::W => Direction::N,
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::W,
-                Direction::E => Direction::N,
-                Direction::S => Direction::E,
-                Direction::W => Direction::S,
-            }
-        }
-    }
-}
@@ -250,38 +250,121 @@
 //
 // Your puzzle answer was 1514.
 
-use std::{fmt, fs};
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
-use std::collections::hash_map::Entry;
+use std::collections::{hash_map, BTreeSet, HashMap, HashSet, VecDeque};
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::solution::{Entry, Solution};
 
-use crate::geom::{Direction, Vector2};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day18;
+
+pub const SOLUTION: Entry = Entry::new::<Day18>();
+
+impl Solution for Day18 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 18;
+
+    type Input = Vec<Vec<Tile>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let grid = parse_grid(input)?;
+        check_vault(&grid)?;
+        Ok(grid)
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        Ok(explore(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        Ok(explore(&split_vault(input)?))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day18")?;
-    println!("{}", explore(&input)?);
-    let input = fs::read_to_string("input/aoc2019/day18b")?;
-    println!("{}", explore(&input)?);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn explore(s: &str) -> Result<i32> {
+///
+/// Replace the 3x3 area around the entrance with four entrances walled off from each other
+///
+fn split_vault(grid: &[Vec<Tile>]) -> Result<Vec<Vec<Tile>>> {
+    let mut grid = grid.to_vec();
+    let (x, y) = grid.iter().enumerate()
+        .find_map(|(y, row)| row.iter().position(|t| matches!(t, Tile::Node(Node::Hero(_)))).map(|x| (x, y)))
+        .ok_or_else(|| AocError::invalid("no entrance"))?;
+    if x == 0 || y == 0 || y + 1 >= grid.len() || x + 1 >= grid[y].len() {
+        return Err(AocError::invalid("entrance at the edge").into());
+    }
+
+    let centre = [b"@#@", b"###", b"@#@"];
+    for (dy, row) in centre.iter().enumerate() {
+        for (dx, &c) in row.iter().enumerate() {
+            grid[y + dy - 1][x + dx - 1] = Tile::new(c, 0).unwrap();
+        }
+    }
+    // number the entrances again, in reading order as parse_grid does
+    let heroes = grid.iter_mut()
+        .flat_map(|row| row.iter_mut())
+        .filter_map(|t| match t { Tile::Node(Node::Hero(id)) => Some(id), _ => None });
+    for (i, id) in heroes.enumerate() {
+        *id = i as u8;
+    }
+    Ok(grid)
+}
+
+fn parse_grid(s: &str) -> std::result::Result<Vec<Vec<Tile>>, AocError> {
     let mut id = 0;
-    let grid = s.lines()
+    s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| line.as_bytes().iter()
-            .map(|&c| {
-                let tile = Tile::new(c, id);
-                if let Ok(Tile::Node(Node::Hero(_))) = tile {
+        .map(|line| line.char_indices()
+            .map(|(x, c)| {
+                let tile = u8::try_from(c).ok()
+                    .and_then(|c| Tile::new(c, id))
+                    .ok_or_else(|| AocError::parse(s, &line[x..], "expected one of .#@, a key or a door"))?;
+                if let Tile::Node(Node::Hero(_)) = tile {
                     id += 1;
                 }
-                tile
+                Ok(tile)
             })
-            .collect::<Result<Vec<_>>>())
-        .collect::<Result<Vec<_>>>()?;
+            .collect())
+        .collect()
+}
+
+///
+/// Check the vault is a rectangle walled in all round with at least one entrance, so the search
+/// never steps off the grid
+///
+fn check_vault(grid: &[Vec<Tile>]) -> std::result::Result<(), AocError> {
+    let width = grid.first().map_or(0, |row| row.len());
+    if width == 0 {
+        return Err(AocError::invalid("empty vault"));
+    }
+    if let Some(y) = grid.iter().position(|row| row.len() != width) {
+        return Err(AocError::invalid(format!("row {} is {} wide, expected {}", y + 1, grid[y].len(), width)));
+    }
+    let height = grid.len();
+    let on_border = |x: usize, y: usize| x == 0 || y == 0 || x + 1 == width || y + 1 == height;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            if on_border(x, y) && *tile != Tile::Wall {
+                return Err(AocError::invalid(format!("vault open to the outside at ({}, {})", x, y)));
+            }
+        }
+    }
+    if !grid.iter().flatten().any(|t| matches!(t, Tile::Node(Node::Hero(_)))) {
+        return Err(AocError::invalid("no entrance"));
+    }
+    Ok(())
+}
 
+fn explore(grid: &[Vec<Tile>]) -> i32 {
     let mut dists = HashMap::new();
     let mut keys = BTreeSet::new();
     let mut start_nodes = BTreeSet::new();
@@ -290,8 +373,8 @@ fn explore(s: &str) -> Result<i32> {
         for (x, elem) in line.iter().enumerate() {
             match elem {
                 Tile::Node(n) => {
-                    let pos = Vector2::new(x, y);
-                    let adj_nodes = get_adj_dists(&grid, pos);
+                    let pos = Vector2::new([x as i64, y as i64]);
+                    let adj_nodes = get_adj_dists(grid, pos);
                     dists.insert(*n, adj_nodes);
                     if let Node::Key(k) = n { keys.insert(*k); }
                     if let Node::Hero(_) = n { start_nodes.insert(*n); }
@@ -300,11 +383,11 @@ fn explore(s: &str) -> Result<i32> {
             }
         }
     }
-    Ok(min_steps(start_nodes, keys, dists))
+    min_steps(start_nodes, keys, dists)
 }
 
 
-fn get_adj_dists(grid: &Vec<Vec<Tile>>,
+fn get_adj_dists(grid: &[Vec<Tile>],
                  initial_pos: Vector2) -> HashSet<(Node, i32)> {
     let mut to_process = VecDeque::new();
     let mut visited = HashSet::new();
@@ -314,10 +397,10 @@ fn get_adj_dists(grid: &Vec<Vec<Tile>>,
 
     while !to_process.is_empty() {
         let (pos, depth) = to_process.pop_front().unwrap();
-        for dir in Direction::VALUES.iter() {
+        for dir in Direction::VALUES_4D.iter() {
             let new_pos = pos + dir.dxdy();
             if !visited.contains(&new_pos) {
-                let tile = grid[new_pos.y as usize][new_pos.x as usize];
+                let tile = grid[new_pos.y() as usize][new_pos.x() as usize];
                 match tile {
                     Tile::Floor |
                     Tile::Node(Node::Hero(_)) => to_process.push_back((new_pos, depth + 1)),
@@ -357,9 +440,9 @@ fn min_steps(nodes: BTreeSet<Node>,
                 let next_state = (next_collected_keys, next_nodes);
                 let new_dist = acc_dist + dist;
                 match min_dists.entry(next_state) {
-                    Entry::Occupied(mut e) =>
+                    hash_map::Entry::Occupied(mut e) =>
                         { e.insert(std::cmp::min(*e.get(), new_dist)); }
-                    Entry::Vacant(e) => {
+                    hash_map::Entry::Vacant(e) => {
                         to_process.push_back(e.key().clone());
                         e.insert(new_dist);
                     }
@@ -448,14 +531,14 @@ impl fmt::Debug for Door {
 }
 
 impl Tile {
-    fn new(c: u8, id: u8) -> Result<Tile> {
+    fn new(c: u8, id: u8) -> Option<Tile> {
         match c {
-            b'.' => Ok(Tile::Floor),
-            b'#' => Ok(Tile::Wall),
-            b'@' => Ok(Tile::Node(Node::Hero(id))),
-            b'a'..=b'z' => Ok(Tile::Node(Node::Key(Key(c)))),
-            b'A'..=b'Z' => Ok(Tile::Node(Node::Door(Door(c)))),
-            _ => Err(anyhow::anyhow!("invalid tile: {}", c)),
+            b'.' => Some(Tile::Floor),
+            b'#' => Some(Tile::Wall),
+            b'@' => Some(Tile::Node(Node::Hero(id))),
+            b'a'..=b'z' => Some(Tile::Node(Node::Key(Key(c)))),
+            b'A'..=b'Z' => Some(Tile::Node(Node::Door(Door(c)))),
+            _ => None,
         }
     }
 }
@@ -471,7 +554,7 @@ mod tests {
         #b.A.@.a#
         #########
         ";
-        assert_eq!(8, explore(s)?);
+        assert_eq!(8, explore(&Day18::parse(s)?));
 
         let s = r"
         ########################
@@ -480,7 +563,7 @@ mod tests {
         #d.....................#
         ########################
         ";
-        assert_eq!(86, explore(s)?);
+        assert_eq!(86, explore(&Day18::parse(s)?));
 
         let s = r"
         ########################
@@ -489,7 +572,7 @@ mod tests {
         #.....@.a.B.c.d.A.e.F.g#
         ########################
         ";
-        assert_eq!(132, explore(s)?);
+        assert_eq!(132, explore(&Day18::parse(s)?));
 
         let s = r"
         #################
@@ -502,7 +585,7 @@ mod tests {
         #l.F..d...h..C.m#
         #################
         ";
-        assert_eq!(136, explore(s)?);
+        assert_eq!(136, explore(&Day18::parse(s)?));
 
         let s = r"
         ########################
@@ -512,7 +595,7 @@ mod tests {
         ###g#h#i################
         ########################
         ";
-        assert_eq!(81, explore(s)?);
+        assert_eq!(81, explore(&Day18::parse(s)?));
 
         Ok(())
     }
@@ -522,13 +605,22 @@ mod tests {
         let s = r"
         #######
         #a.#Cd#
+        ##...##
+        ##.@.##
+        ##...##
+        #cB#Ab#
+        #######
+        ";
+        let split = "\
+        #######
+        #a.#Cd#
         ##@#@##
         #######
         ##@#@##
         #cB#Ab#
-        #######
-        ";
-        assert_eq!(8, explore(s)?);
+        #######";
+        assert_eq!(parse_grid(split)?, split_vault(&Day18::parse(s)?)?);
+        assert_eq!(8, explore(&split_vault(&Day18::parse(s)?)?));
 
         let s = r"
         ###############
@@ -539,7 +631,7 @@ mod tests {
         #b.....#.....c#
         ###############
         ";
-        assert_eq!(24, explore(s)?);
+        assert_eq!(24, explore(&Day18::parse(s)?));
 
         let s = r"
         #############
@@ -550,7 +642,7 @@ mod tests {
         #fEbA.#.FgHi#
         #############
         ";
-        assert_eq!(32, explore(s)?);
+        assert_eq!(32, explore(&Day18::parse(s)?));
 
         let s = r"
         #############
@@ -563,117 +655,17 @@ mod tests {
         #o#m..#i#jk.#
         #############
         ";
-        assert_eq!(72, explore(s)?);
+        assert_eq!(72, explore(&Day18::parse(s)?));
         Ok(())
     }
-}
-
-mod geom {
-    use std::ops::{Add, AddAssign, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    pub enum Direction {
-        N,
-        E,
-        S,
-        W,
-    }
 
-    impl Direction {
-        pub const VALUES: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::S => Vector2::new(0, 1),
-                Direction::W => Vector2::new(-1, 0),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::E,
-                Direction::E => Direction::S,
-                Direction::S => Direction::W,
-                Direction::W => Direction::N,
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::W,
-                Direction::E => Direction::N,
-                Direction::S => Direction::E,
-                Direction::W => Direction::S,
-            }
-        }
+    #[test]
+    fn test_parse() {
+        assert!(Day18::parse("x").is_err());
+        assert!(Day18::parse("@").is_err());
+        assert!(Day18::parse("#####\n#.@.#\n####").is_err());
+        assert!(Day18::parse("#####\n#.@.#\n##.##").is_err());
+        assert!(Day18::parse("#####\n#.a.#\n#####").is_err());
+        assert!(Day18::parse("#####\n#.@.#\n#####").is_ok());
     }
 }
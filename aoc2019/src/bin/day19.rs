@@ -80,64 +80,102 @@
 //
 // Your puzzle answer was 10450905.
 
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::error::AocError;
+use aoc::parse;
+use aoc::search;
+use aoc::solution::{Entry, Solution};
 
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day19;
+
+pub const SOLUTION: Entry = Entry::new::<Day19>();
+
+impl Solution for Day19 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 19;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day19")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-    // inspect(&nums);
-    part1(&nums);
-    part2(&nums);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn in_beam_area(prog: &Prog, x: i64, y: i64) -> bool {
+fn in_beam_area(prog: &Prog, x: i64, y: i64) -> Result<bool> {
     let mut prog_clone = prog.clone();
     prog_clone.send(x);
     prog_clone.send(y);
-    prog_clone.resume().unwrap();
-    match prog_clone.recv_unwrap() {
-        0 => false,
-        1 => true,
-        _ => panic!("unrecognized output"),
+    prog_clone.resume()?;
+    let out = prog_clone.recv_iter().next();
+    match out {
+        Some(0) => Ok(false),
+        Some(1) => Ok(true),
+        Some(out) => Err(AocError::invalid(format!("the drone reported {} at ({}, {})", out, x, y)).into()),
+        None => Err(AocError::invalid(format!("the drone reported nothing at ({}, {})", x, y)).into()),
     }
 }
 
-fn part1(nums: &Vec<i64>) {
-    let prog = Prog::new(nums.clone());
-    let count = (0..50)
+fn part1(nums: &[i64]) -> Result<usize> {
+    let prog = Prog::new(nums.to_vec());
+    let pulled = (0..50)
         .flat_map(|y| (0..50).map(move |x| (x, y)))
-        .filter(|(x, y)| in_beam_area(&prog, *x, *y))
-        .count();
-    println!("{}", count);
+        .map(|(x, y)| in_beam_area(&prog, x, y))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(pulled.into_iter().filter(|&b| b).count())
 }
 
-fn part2(nums: &Vec<i64>) {
-    let prog = Prog::new(nums.clone());
-    let mut x = 0;
-    let mut y = 100;
-    loop {
-        while !in_beam_area(&prog, x, y) {
-            x += 1;
-        }
-        if in_beam_area(&prog, x + 99, y - 99) {
-            break;
+///
+/// The closest 100x100 square in the beam, found by the first row whose leftmost beam position
+/// has room for the square above and to the right of it
+///
+fn part2(nums: &[i64]) -> Result<i64> {
+    const SIZE: i64 = 100;
+    // the beam is a cone from the emitter at the origin, so its left edge on row `y` is no
+    // further right than `y * MAX_SLOPE` for a beam edge under 10 across per row down, as in
+    // every input; rows close to the emitter can have no beam at all
+    const MAX_SLOPE: i64 = 10;
+    let prog = Prog::new(nums.to_vec());
+    let left_edge = |y: i64| -> Result<Option<i64>> {
+        for x in 0..=y * MAX_SLOPE {
+            if in_beam_area(&prog, x, y)? {
+                return Ok(Some(x));
+            }
         }
-        y += 1;
+        Ok(None)
+    };
+    let fits = |y: i64| match left_edge(y)? {
+        Some(x) => in_beam_area(&prog, x + SIZE - 1, y - (SIZE - 1)),
+        None => Ok(false),
+    };
+    // the search takes a plain predicate, so it stops at the first failure to report it after
+    let mut failure = None;
+    let y = search::first_true_from(SIZE - 1, |y| fits(y).unwrap_or_else(|e| {
+        failure = Some(e);
+        true
+    }));
+    if let Some(e) = failure {
+        return Err(e);
     }
-    let res = (x * 10000) + (y - 99);
-    println!("{}", res);
+    let y = y.ok_or_else(|| AocError::no_solution("the beam never fits the square"))?;
+    let x = left_edge(y)?.ok_or_else(|| AocError::no_solution("the beam never fits the square"))?;
+    Ok(x * 10000 + (y - (SIZE - 1)))
 }
 
 // #...................................................................................................
@@ -248,7 +286,7 @@ fn inspect(nums: &Vec<i64>) {
     for y in 0..100 {
         for x in 0..100 {
             grid[y as usize][x as usize] =
-                if in_beam_area(&prog, x, y) {
+                if matches!(in_beam_area(&prog, x, y), Ok(true)) {
                     b'#'
                 } else {
                     b'.'
@@ -314,15 +352,10 @@ mod prog {
             self.input.push_back(i);
         }
 
-        #[allow(dead_code)]
         pub fn recv_iter(&mut self) -> Drain<i64> {
             self.output.drain(..)
         }
 
-        pub fn recv_unwrap(&mut self) -> i64 {
-            self.output.pop_front().unwrap()
-        }
-
         #[allow(dead_code)]
         pub fn is_empty_output(&self) -> bool {
             !self.output.is_empty()
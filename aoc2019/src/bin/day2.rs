@@ -75,48 +75,64 @@
 //
 // Your puzzle answer was 7264.
 
-use std::fs;
+use std::convert::TryFrom;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::error::AocError;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day2;
+
+pub const SOLUTION: Entry = Entry::new::<Day2>();
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 2;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let nums = parse::ints(input)?;
+        if nums.len() < 3 {
+            return Err(AocError::invalid("the program has no room for a noun and verb").into());
+        }
+        Ok(nums)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day2")?;
-    let nums: Vec<i32> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<Vec<_>>>()?;
-
-    part1(&nums);
-    part2(&nums);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(nums: &Vec<i32>) {
-    let mut nums = nums.clone();
-    let out = exec(12, 2, &mut nums).unwrap();
-    println!("{}", out);
+fn part1(nums: &[i32]) -> Result<i32> {
+    exec(12, 2, &mut nums.to_vec())
 }
 
-fn part2(nums: &Vec<i32>) {
+fn part2(nums: &[i32]) -> Result<i32> {
     let target = 19690720;
     for noun in 0..100 {
         for verb in 0..100 {
-            let mut nums = nums.clone();
+            let mut nums = nums.to_vec();
             let out = exec(noun, verb, &mut nums);
             if let Ok(out) = out {
                 if out == target {
-                    let result = 100 * noun + verb;
-                    println!("{}", result);
-                    return;
+                    return Ok(100 * noun + verb);
                 }
             }
         }
     }
-    println!("Failed to find target: {}", target);
+    anyhow::bail!("Failed to find target: {}", target)
 }
 
 fn exec(noun: i32, verb: i32, nums: &mut [i32]) -> Result<i32> {
@@ -130,14 +146,16 @@ fn exec(noun: i32, verb: i32, nums: &mut [i32]) -> Result<i32> {
             break;
         }
 
-        let num1 = nums[nums[i + 1] as usize];
-        let num2 = nums[nums[i + 2] as usize];
-        let pos = nums[i + 3] as usize;
+        let addr = |j: usize| nums.get(j)
+            .and_then(|&a| usize::try_from(a).ok())
+            .filter(|&a| a < nums.len())
+            .ok_or_else(|| AocError::invalid(format!("instruction {} addresses outside the program", i)));
+        let (num1, num2, pos) = (nums[addr(i + 1)?], nums[addr(i + 2)?], addr(i + 3)?);
         nums[pos] = match op {
-            1 => num1 + num2,
-            2 => num1 * num2,
+            1 => num1.checked_add(num2),
+            2 => num1.checked_mul(num2),
             _ => anyhow::bail!("Unrecognized opcode: {}", op),
-        };
+        }.ok_or_else(|| AocError::invalid(format!("instruction {} overflows", i)))?;
         i += 4;
     }
     Ok(nums[0])
@@ -166,4 +184,12 @@ mod tests {
         assert_eq!(nums, &[30, 1, 1, 4, 2, 5, 6, 0, 99]);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Day2::parse("99").is_err());
+        assert!(exec_simple(&mut [1, 0, 0, 9]).is_err());
+        assert!(exec_simple(&mut [1, 0, 0]).is_err());
+        assert!(exec_simple(&mut [2, 5, 5, 0, 99, i32::MAX]).is_err());
+    }
 }
@@ -208,28 +208,72 @@
 // Your puzzle answer was 6292.
 
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs;
+use std::fmt::Display;
 
 use regex::{Captures, Regex};
 
-use crate::geom::{Direction, Vector2};
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::solution::{Entry, Solution};
+
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day20;
+
+pub const SOLUTION: Entry = Entry::new::<Day20>();
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 20;
+
+    type Input = Maze;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(maze: &Self::Input) -> Result<impl Display> {
+        explore(maze, false)
+    }
+
+    fn part2(maze: &Self::Input) -> Result<impl Display> {
+        explore(maze, true)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day20")?;
-    println!("{}", explore(&input, false));
-    println!("{}", explore(&input, true));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn explore(s: &str, is_recursive: bool) -> i32 {
+struct Maze {
+    grid: Vec<Vec<u8>>,
+    start_pos: Vector2,
+    end_pos: Vector2,
+    portals: HashMap<Vector2, Vector2>,
+    alignments: HashMap<Vector2, Alignment>,
+}
+
+fn parse(s: &str) -> Result<Maze> {
+    for line in s.lines() {
+        if let Some(i) = line.find(|c: char| !matches!(c, ' ' | '.' | '#' | 'A'..='Z')) {
+            return Err(AocError::parse(s, &line[i..], "expected one of .#, a space or a label").into());
+        }
+    }
     let grid = s.lines()
         .filter(|line| !line.is_empty())
         .map(|line| line.bytes().collect::<Vec<_>>())
         .collect::<Vec<_>>();
+    if grid.is_empty() {
+        return Err(AocError::invalid("empty maze").into());
+    }
+    let (start_pos, end_pos, portals, alignments) = parse_grid(&grid)?;
+    Ok(Maze { grid, start_pos, end_pos, portals, alignments })
+}
 
-    let (start_pos, end_pos, portals, alignments) = parse_grid(&grid);
+fn explore(maze: &Maze, is_recursive: bool) -> Result<i32> {
+    let Maze { grid, start_pos, end_pos, portals, alignments } = maze;
+    let (start_pos, end_pos) = (*start_pos, *end_pos);
 
     let mut res = None;
     let mut visited = HashSet::new();
@@ -242,10 +286,13 @@ fn explore(s: &str, is_recursive: bool) -> i32 {
             res = Some(depth);
             break;
         }
-        for dir in Direction::VALUES.iter() {
+        for dir in Direction::VALUES_4D.iter() {
             let mut next_pos = pos + dir.dxdy();
             let mut next_level = level;
-            let c = grid[next_pos.y as usize][next_pos.x as usize];
+            let c = grid.get(next_pos.y() as usize)
+                .and_then(|row| row.get(next_pos.x() as usize))
+                .copied()
+                .unwrap_or(b' ');
             let can_move = match c {
                 b'A'..=b'Z' => {
                     if pos == start_pos || pos == end_pos {
@@ -266,8 +313,7 @@ fn explore(s: &str, is_recursive: bool) -> i32 {
                     }
                 }
                 b'.' => true,
-                b'#' => false,
-                _ => panic!("unrecognized tile"),
+                _ => false,
             };
             let next_state = (next_pos, next_level);
             if can_move && !visited.contains(&next_state) {
@@ -276,13 +322,13 @@ fn explore(s: &str, is_recursive: bool) -> i32 {
             }
         }
     }
-    res.unwrap()
+    res.ok_or_else(|| AocError::no_solution("ZZ can't be reached").into())
 }
 
 fn parse_grid(grid: &Vec<Vec<u8>>)
-              -> (Vector2, Vector2,
-                  HashMap<Vector2, Vector2>,
-                  HashMap<Vector2, Alignment>) {
+              -> Result<(Vector2, Vector2,
+                         HashMap<Vector2, Vector2>,
+                         HashMap<Vector2, Alignment>)> {
     lazy_static::lazy_static! {
         static ref RE_LEFT: Regex = Regex::new(r"(?P<label>[A-Z]{2})(?P<tile>\.)").unwrap();
         static ref RE_RIGHT: Regex = Regex::new(r"(?P<tile>\.)(?P<label>[A-Z]{2})").unwrap();
@@ -292,7 +338,7 @@ fn parse_grid(grid: &Vec<Vec<u8>>)
 
     let mut add_row_labels = |grid: &Vec<Vec<u8>>, is_tranposed: bool| {
         for (y, line) in grid.iter().enumerate() {
-            let s = std::str::from_utf8(line).unwrap();
+            let s = String::from_utf8_lossy(line);
             let mid = s.len() / 2;
             let process_cap =
                 |cap: Captures, is_left: bool| -> (String, Vector2, Alignment) {
@@ -300,9 +346,9 @@ fn parse_grid(grid: &Vec<Vec<u8>>)
                     let x = cap.name("tile").unwrap().start();
                     let pos =
                         if is_tranposed {
-                            Vector2::new(y, x)
+                            Vector2::new([y as i64, x as i64])
                         } else {
-                            Vector2::new(x, y)
+                            Vector2::new([x as i64, y as i64])
                         };
                     let alignment =
                         if (x < mid) ^ is_left {
@@ -314,8 +360,8 @@ fn parse_grid(grid: &Vec<Vec<u8>>)
                 };
 
             for (label, pos, alignment) in
-            RE_LEFT.captures_iter(s).map(|cap| process_cap(cap, true)).chain(
-                RE_RIGHT.captures_iter(s).map(|cap| process_cap(cap, false))) {
+            RE_LEFT.captures_iter(&s).map(|cap| process_cap(cap, true)).chain(
+                RE_RIGHT.captures_iter(&s).map(|cap| process_cap(cap, false))) {
                 labels.entry(label).or_insert(Vec::new()).push((pos, alignment));
             }
         }
@@ -329,23 +375,20 @@ fn parse_grid(grid: &Vec<Vec<u8>>)
     let mut portals = HashMap::new();
 
     for (label, pos_aligns) in labels.iter() {
-        if label == "AA" {
-            assert!(start_pos.is_none());
-            assert_eq!(pos_aligns.len(), 1);
-            let (pos, align) = pos_aligns[0];
-            assert_eq!(align, Alignment::Outer);
-            start_pos = Some(pos);
+        if label == "AA" || label == "ZZ" {
+            let pos = match pos_aligns[..] {
+                [(pos, Alignment::Outer)] => pos,
+                _ => return Err(AocError::invalid(format!("{} should be one tile on the outer edge", label)).into()),
+            };
+            match label.as_str() {
+                "AA" => start_pos = Some(pos),
+                _ => end_pos = Some(pos),
+            }
             continue;
         }
-        if label == "ZZ" {
-            assert!(end_pos.is_none());
-            assert_eq!(pos_aligns.len(), 1);
-            let (pos, align) = pos_aligns[0];
-            assert_eq!(align, Alignment::Outer);
-            end_pos = Some(pos);
-            continue;
+        if pos_aligns.len() != 2 {
+            return Err(AocError::invalid(format!("portal {} should have two ends", label)).into());
         }
-        assert_eq!(pos_aligns.len(), 2);
         let (pos0, align0) = pos_aligns[0];
         let (pos1, align1) = pos_aligns[1];
         alignments.insert(pos0, align0);
@@ -353,9 +396,9 @@ fn parse_grid(grid: &Vec<Vec<u8>>)
         portals.insert(pos0, pos1);
         portals.insert(pos1, pos0);
     }
-    let start_pos = start_pos.expect("AA not found");
-    let end_pos = end_pos.expect("ZZ not found");
-    (start_pos, end_pos, portals, alignments)
+    let start_pos = start_pos.ok_or_else(|| AocError::invalid("AA not found"))?;
+    let end_pos = end_pos.ok_or_else(|| AocError::invalid("ZZ not found"))?;
+    Ok((start_pos, end_pos, portals, alignments))
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
@@ -374,8 +417,8 @@ fn pprint(grid: &Vec<Vec<u8>>) {
 
 fn transpose(a: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
     let n = a.len();
-    let m = a[0].len();
-    let mut res = vec![vec![0u8; n]; m];
+    let m = a.iter().map(Vec::len).max().unwrap_or(0);
+    let mut res = vec![vec![b' '; n]; m];
     for (y, row) in a.iter().enumerate() {
         for (x, elem) in row.iter().enumerate() {
             res[x][y] = *elem;
@@ -410,7 +453,7 @@ FG..#########.....#
   ###########.#####
              Z     
              Z     ";
-        assert_eq!(23, explore(s, false));
+        assert_eq!(23, explore(&parse(s)?, false)?);
 
         let s = r"
                    A               
@@ -450,7 +493,7 @@ YN......#               VT..#....QG
   #########.###.###.#############  
            B   J   C               
            U   P   P               ";
-        assert_eq!(58, explore(s, false));
+        assert_eq!(58, explore(&parse(s)?, false)?);
 
         let s = r"
              Z L X W       C                 
@@ -490,117 +533,7 @@ RE....#.#                           #......RF
   #############.#.#.###.###################  
                A O F   N                     
                A A D   M                     ";
-        assert_eq!(396, explore(s, true));
+        assert_eq!(396, explore(&parse(s)?, true)?);
         Ok(())
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    pub enum Direction {
-        N,
-        E,
-        S,
-        W,
-    }
-
-    impl Direction {
-        pub const VALUES: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::S => Vector2::new(0, 1),
-                Direction::W => Vector2::new(-1, 0),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::E,
-                Direction::E => Direction::S,
-                Direction::S => Direction::W,
-                Direction::W => Direction::N,
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::W,
-                Direction::E => Direction::N,
-                Direction::S => Direction::E,
-                Direction::W => Direction::S,
-            }
-        }
-    }
-}
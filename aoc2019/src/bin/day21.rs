@@ -107,42 +107,60 @@
 // Your puzzle answer was 1142844041.
 
 use std::convert::TryFrom;
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day21;
+
+pub const SOLUTION: Entry = Entry::new::<Day21>();
+
+impl Solution for Day21 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 21;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        exec(nums, part1_soln1())
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        exec(nums, part2_soln())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day21")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-    exec(&nums, part1_soln1())?;
-    exec(&nums, part2_soln())?;
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn exec(nums: &Vec<i64>, inp: Vec<&str>) -> Result<()> {
-    let mut prog = Prog::new(nums.clone());
+///
+/// Run the springscript, returning the hull damage, or failing with the droid's last moments
+///
+fn exec(nums: &[i64], inp: Vec<&str>) -> Result<i64> {
+    let mut prog = Prog::new(nums.to_vec());
     for s in inp {
         prog.send_str(s);
     }
     prog.resume()?;
 
+    let mut out = String::new();
     for i in prog.recv_iter() {
         match u8::try_from(i) {
-            Ok(c) => print!("{}", c as char),
-            Err(_) => print!("{}", i.to_string()),
+            Ok(c) => out.push(c as char),
+            Err(_) => return Ok(i),
         }
     }
-    println!();
-    Ok(())
+    anyhow::bail!("droid fell into space:\n{}", out)
 }
 
 fn part1_soln1() -> Vec<&'static str> {
@@ -162,299 +162,174 @@
 //
 // Your puzzle answer was 62416301438548.
 
-use std::collections::{HashSet, VecDeque};
-use std::fs;
+use std::fmt::Display;
 
 use lazy_static::lazy_static;
+use num::{BigInt, Integer, One, Zero};
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
+
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day22;
+
+pub const SOLUTION: Entry = Entry::new::<Day22>();
+
+impl Solution for Day22 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 22;
+
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(moves: &Self::Input) -> Result<impl Display> {
+        Ok(Affine::from_moves(moves, &BigInt::from(10007)).apply(&BigInt::from(2019)))
+    }
+
+    fn part2(moves: &Self::Input) -> Result<impl Display> {
+        let [n, times, pos] = [119315717514047_u64, 101741582076661, 2020].map(BigInt::from);
+        card_at(moves, &n, &times, &pos)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day22")?;
-    let moves = parse(&input)?;
-    let simp_moves = simplify(&moves, 10007);
-
-    let deck = shuffle(&simp_moves, 10007);
-    let res = deck.iter().enumerate()
-        .find(|(_i, &c)| c == 2019 as usize);
-    println!("{:?}", res);
-
-    let n = 119315717514047_i128;
-    let moves = simplify(&moves, n);
-    println!("{:?}", moves);
-    // [DealInc(34300472370265), Cut(-13035406070497)]
-
-    let times = 101741582076661_i128;
-    let fold = fold(34300472370265, -13035406070497, n, times);
-    println!("{:?}", fold);
-    // (5606965185395, -13585759979799)
-
-    let cut = rev_cut(n, 2020, -13585759979799);
-
-    // https://math.stackexchange.com/questions/3968831/closed-form-for-a-cdot-x-bmod-n-r
-    let res = (mod_inv(5606965185395, n) * cut) % n;
-    println!("{}", res);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-// deal(x) cut(y) deal(p) cut(q)
-// = deal(x) deal(p) cut((-y * (n - p)) % n) cut(q)
-// = deal((x * p) % n)  cut((((-y * (n - p)) % n) + q) % n)
-fn fold(arg1: i128, arg2: i128, n: i128, mut target: i128) -> (i128, i128) {
-    let combine = |(x, y): (i128, i128),
-                   (p, q): (i128, i128)| -> (i128, i128) {
-        let arg1 = (x * p) % n;
-        let arg2 = (((-y * (n - p)) % n) + q) % n;
-        (arg1, arg2)
-    };
-    assert!(target > 0);
-
-    let mut res = None;
-    let mut init = false;
-    let mut args = (arg1, arg2);
-    while target != 0 {
-        if !init {
-            init = true;
-        } else {
-            args = combine(args, args);
-        }
-        if target % 2 != 0 {
-            res = if res.is_none() {
-                Some(args)
-            } else {
-                Some(combine(res.unwrap(), args))
-            }
-        }
-        target >>= 1;
-    }
-    res.unwrap()
+///
+/// The card that ends up at `pos` after shuffling a deck of `n` cards `times` times
+///
+fn card_at(moves: &[Move], n: &BigInt, times: &BigInt, pos: &BigInt) -> Result<BigInt> {
+    let shuffle = Affine::from_moves(moves, n).pow(times);
+    let unshuffle = shuffle.inverse()
+        .ok_or_else(|| AocError::invalid(format!("the shuffle deals with an increment sharing a factor with {}", n)))?;
+    Ok(unshuffle.apply(pos))
 }
 
-fn simplify(moves: &VecDeque<Move>, n: i128) -> VecDeque<Move> {
-    let mut moves = moves.clone();
-    let mut res = VecDeque::new();
-    let mut changed = true;
-
-    while changed {
-        changed = false;
-        res.push_back(moves.pop_front().unwrap());
-        while !moves.is_empty() {
-            let next_move = moves.pop_front().unwrap();
-            let prev_move = res.pop_back().unwrap();
-            match (prev_move, next_move) {
-                (Move::Cut(arg1), Move::DealInc(arg2)) => {
-                    res.push_back(next_move);
-                    let arg = (-arg1 * (n - arg2)) % n;
-                    res.push_back(Move::Cut(arg));
-                    changed = true;
-                }
-                (Move::DealInc(arg1), Move::DealInc(arg2)) => {
-                    let arg = (arg1 * arg2) % n;
-                    res.push_back(Move::DealInc(arg));
-                    changed = true;
-                }
-                (Move::Cut(arg1), Move::Cut(arg2)) => {
-                    let arg = (arg1 + arg2) % n;
-                    res.push_back(Move::Cut(arg));
-                    changed = true;
-                }
-                (Move::Cut(arg1), Move::DealNew) => {
-                    res.push_back(Move::DealNew);
-                    res.push_back(Move::Cut(-arg1));
-                    changed = true;
-                }
-                (Move::DealInc(arg1), Move::DealNew) => {
-                    res.push_back(Move::DealInc(n - arg1));
-                    res.push_back(Move::Cut(1));
-                    changed = true;
-                }
-                (Move::DealNew, Move::DealNew) => {
-                    changed = true;
-                }
-                (_, _) => {
-                    res.push_back(prev_move);
-                    res.push_back(next_move);
-                }
-            }
-        }
-        let t = res;
-        res = moves;
-        moves = t;
-    }
-    moves
+///
+/// The shuffle moving the card at position `x` to `ax + b (mod n)`
+///
+/// Every move is one of these, and so is any sequence of them, so a whole shuffle and its repeats
+/// collapse into one. Decks of any size work, as the arithmetic never overflows.
+///
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Affine {
+    a: BigInt,
+    b: BigInt,
+    n: BigInt,
 }
 
-// doesn't work
-#[allow(dead_code)]
-fn find_loop(moves: &VecDeque<Move>) {
-    let mut set = HashSet::new();
-    let deck_size = 119315717514047_i128;
-    let mut pos = 2020_i128;
-
-    set.insert(pos);
-    loop {
-        let prev_pos = rev(&moves, deck_size, pos);
-        if set.contains(&prev_pos) {
-            println!("found! {}", set.len());
-            break;
-        }
-        set.insert(prev_pos);
-        pos = prev_pos;
-        if set.len() % 100_000 == 0 {
-            println!("{}", set.len());
-        }
+impl Affine {
+    fn identity(n: &BigInt) -> Affine {
+        Affine { a: BigInt::one().mod_floor(n), b: BigInt::zero(), n: n.clone() }
     }
-}
 
-fn shuffle(moves: &VecDeque<Move>, size: usize) -> Vec<usize> {
-    let mut deck = vec![0usize; size];
-    for (i, c) in deck.iter_mut().enumerate() {
-        *c = i;
+    fn from_move(mov: Move, n: &BigInt) -> Affine {
+        let modulo = |x: i64| BigInt::from(x).mod_floor(n);
+        let (a, b) = match mov {
+            Move::DealNew => (modulo(-1), modulo(-1)),
+            Move::Cut(k) => (modulo(1), modulo(-k)),
+            Move::DealInc(k) => (modulo(k), BigInt::zero()),
+        };
+        Affine { a, b, n: n.clone() }
     }
-    for mov in moves {
-        match mov {
-            Move::Cut(arg) => cut(&mut deck, *arg),
-            Move::DealInc(arg) => deal_inc(&mut deck, *arg),
-            Move::DealNew => deck.reverse()
-        }
+
+    fn from_moves(moves: &[Move], n: &BigInt) -> Affine {
+        moves.iter().fold(Affine::identity(n), |acc, &mov| acc.then(&Affine::from_move(mov, n)))
     }
-    deck
-}
 
-fn cut(deck: &mut Vec<usize>, num: i128) {
-    if num > 0 {
-        deck.rotate_left(num as usize);
-    } else {
-        deck.rotate_right(-num as usize);
+    ///
+    /// Deal with increment `a` then cut `-b`, which is this shuffle when `a` is coprime to `n`, or
+    /// `None` if they don't fit a move
+    ///
+    #[cfg(test)]
+    fn to_moves(&self) -> Option<Vec<Move>> {
+        use num::ToPrimitive;
+
+        let cut = (-&self.b).mod_floor(&self.n);
+        Some(vec![Move::DealInc(self.a.to_i64()?), Move::Cut(cut.to_i64()?)])
     }
-}
 
-fn deal_inc(deck: &mut Vec<usize>, num: i128) {
-    let orig = deck.clone();
-    let mut i = 0;
-    for c in orig {
-        deck[i] = c;
-        i = (i + num as usize) % deck.len();
+    fn apply(&self, x: &BigInt) -> BigInt {
+        (&self.a * x + &self.b).mod_floor(&self.n)
     }
-}
 
-#[allow(dead_code)]
-fn rev(moves: &VecDeque<Move>, size: i128, mut pos: i128) -> i128 {
-    for mov in moves.iter().rev() {
-        pos = match mov {
-            Move::Cut(arg) => rev_cut(size, pos, *arg as i128),
-            Move::DealInc(arg) => rev_deal_inc(size, pos, *arg as i128),
-            Move::DealNew => rev_deal_new(size, pos),
+    /// This shuffle followed by `next`
+    fn then(&self, next: &Affine) -> Affine {
+        Affine {
+            a: (&next.a * &self.a).mod_floor(&self.n),
+            b: next.apply(&self.b),
+            n: self.n.clone(),
         }
     }
-    pos
-}
 
-#[allow(dead_code)]
-fn rev_deal_new(n: i128, i: i128) -> i128 {
-    n - 1 - i
-}
-
-#[allow(dead_code)]
-fn rev_cut(n: i128, i: i128, cut: i128) -> i128 {
-    if cut > 0 {
-        (cut + i) % n
-    } else {
-        (n + cut + i) % n
+    /// Where each position came from, if no two cards are dealt to the same place
+    fn inverse(&self) -> Option<Affine> {
+        let gcd = self.a.extended_gcd(&self.n);
+        if !gcd.gcd.is_one() {
+            return None;
+        }
+        let a = gcd.x.mod_floor(&self.n);
+        let b = (-(&a * &self.b)).mod_floor(&self.n);
+        Some(Affine { a, b, n: self.n.clone() })
     }
-}
 
-#[allow(dead_code)]
-fn rev_deal_inc(n: i128, mut i: i128, inc: i128) -> i128 {
-    let mut res = 0;
-    loop {
-        res += i / inc;
-        let r = i % inc;
-        if r == 0 {
-            break;
+    /// This shuffle `k` times in a row, by repeated squaring
+    fn pow(&self, k: &BigInt) -> Affine {
+        let two = BigInt::from(2);
+        let mut k = k.clone();
+        let mut res = Affine::identity(&self.n);
+        let mut sq = self.clone();
+        while !k.is_zero() {
+            if !(&k % &two).is_zero() {
+                res = res.then(&sq);
+            }
+            sq = sq.then(&sq);
+            k = &k / &two;
         }
-        res += 1;
-        i = n - (inc - r);
+        res
     }
-    res
 }
 
-fn parse(s: &str) -> Result<VecDeque<Move>> {
-    const P1: &str = r"cut (-?\d+)";
-    const P2: &str = r"deal with increment (\d+)";
-    const P3: &str = r"deal into new stack";
+fn parse(s: &str) -> Result<Vec<Move>> {
+    const P1: &str = r"^cut (-?\d+)$";
+    const P2: &str = r"^deal with increment (\d+)$";
+    const P3: &str = r"^deal into new stack$";
     lazy_static! {
         static ref P1_RE: Regex = Regex::new(P1).unwrap();
         static ref P2_RE: Regex = Regex::new(P2).unwrap();
         static ref P3_RE: Regex = Regex::new(P3).unwrap();
     }
-    let mut moves = VecDeque::new();
+    let mut moves = Vec::new();
     for line in s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty()) {
-        let mov = if P1_RE.is_match(line) {
-            let arg = &P1_RE.captures(line).unwrap()[1];
-            let arg = arg.parse::<i128>().unwrap();
-            Move::Cut(arg)
-        } else if P2_RE.is_match(line) {
-            let arg = &P2_RE.captures(line).unwrap()[1];
-            let arg = arg.parse::<i128>().unwrap();
-            Move::DealInc(arg)
+        let mov = if let Some(cap) = P1_RE.captures(line) {
+            Move::Cut(error::parse_at(s, &cap[1])?)
+        } else if let Some(cap) = P2_RE.captures(line) {
+            Move::DealInc(error::parse_at(s, &cap[1])?)
         } else if P3_RE.is_match(line) {
             Move::DealNew
         } else {
-            anyhow::bail!("invalid line: {}", s);
+            return Err(AocError::parse(s, line, "expected a cut, deal with increment or deal into new stack").into());
         };
-        moves.push_back(mov);
+        moves.push(mov);
     }
     Ok(moves)
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Move {
-    Cut(i128),
-    DealInc(i128),
+    Cut(i64),
+    DealInc(i64),
     DealNew,
 }
 
-fn gcd(mut a: u128, mut b: u128) -> u128 {
-    let mut t;
-    while b != 0 {
-        t = b;
-        b = a % b;
-        a = t;
-    }
-    a
-}
-
-fn mod_inv(mut a: i128, mut m: i128) -> i128 {
-    assert_eq!(1, gcd(a.abs() as u128, m.abs() as u128));
-    let orig_m = m;
-    let mut y = 0;
-    let mut x = 1;
-
-    if m == 1 {
-        return 0;
-    }
-    while a > 1 {
-        let q = a / m;
-        let r = a % m;
-
-        a = m;
-        m = r;
-
-        let ny = x - (q * y);
-        x = y;
-        y = ny;
-    }
-
-    if x < 0 {
-        x += orig_m;
-    }
-    return x;
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -521,148 +396,79 @@ mod tests {
         cut -1
         ";
         let moves = parse(s)?;
-        let deck = (0..10).map(|i| rev(&moves, 10, i)).collect::<Vec<_>>();
-        assert_eq!(vec![9, 2, 5, 8, 1, 4, 7, 0, 3, 6], deck);
-        Ok(())
-    }
-
-    #[test]
-    #[ignore]
-    // cut a, cut b == cut (a + b)
-    fn test3() -> Result<()> {
-        let s = r"
-        cut 1
-        cut 1
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 10));
-
-        let s = r"
-        cut 2
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 10));
-
-        let s = r"
-        cut 1
-        cut -1
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 10));
+        let ten = BigInt::from(10);
+        let unshuffle = Affine::from_moves(&moves, &ten).inverse().unwrap();
+        let deck = (0..10).map(|i| unshuffle.apply(&BigInt::from(i))).collect::<Vec<_>>();
+        assert_eq!([9, 2, 5, 8, 1, 4, 7, 0, 3, 6].map(BigInt::from).to_vec(), deck);
+        assert_eq!(BigInt::from(6), card_at(&moves, &ten, &BigInt::one(), &BigInt::from(9))?);
+        assert_eq!(None, Affine::from_moves(&parse("deal with increment 4")?, &ten).inverse());
         Ok(())
     }
 
     #[test]
-    #[ignore]
-    // cut 1, dealnew == dealnew, cut -1
-    fn test4() -> Result<()> {
-        let s = r"
-        cut 1
-        deal into new stack
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 10));
-
+    fn test_affine() -> Result<()> {
         let s = r"
+        cut 3
+        deal with increment 5
         deal into new stack
-        cut -1
+        cut -7
+        deal with increment 9
+        cut 11
         ";
-        println!("{:?}", shuffle(&parse(s)?, 10));
-        Ok(())
-    }
-
-    #[test]
-    #[ignore]
-    // deal_inc x
-    // ==
-    // cut 1
-    // deal_inc x
-    // cut n - x
-    // ==
-    // cut k
-    // deal_inc x
-    // cut (k(n - x)) % n
-    fn test5() -> Result<()> {
-        let size = 13; // prime
-        for i in 2..size { // coprime
-            let s = format!(r"
-            deal with increment {}", i);
-            let deck1 = shuffle(&parse(&s)?, size);
-
-            let s = format!(r"
-            cut 1
-            deal with increment {}
-            cut {}", i, size - i);
-            let deck2 = shuffle(&parse(&s)?, size);
-            assert_eq!(deck1, deck2)
+        let moves = parse(s)?;
+        let n = BigInt::from(13);
+        let shuffle1 = Affine::from_moves(&moves, &n);
+        let deck = shuffle(&moves, 13);
+        for (pos, &card) in deck.iter().enumerate() {
+            assert_eq!(BigInt::from(pos), shuffle1.apply(&BigInt::from(card)));
         }
+        assert_eq!(shuffle1, Affine::from_moves(&shuffle1.to_moves().unwrap(), &n));
+        assert_eq!(Affine::identity(&n), shuffle1.then(&shuffle1.inverse().unwrap()));
 
-        let s = r"
-        deal with increment 7
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
+        let repeated = moves.iter().cycle().take(moves.len() * 7).copied().collect::<Vec<_>>();
+        assert_eq!(Affine::from_moves(&repeated, &n), shuffle1.pow(&BigInt::from(7)));
+        assert_eq!(Affine::identity(&n), shuffle1.pow(&BigInt::zero()));
 
-        let s = r"
-        cut 1
-        deal with increment 7
-        cut 6
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-
-        let s = r"
-        cut 2
-        deal with increment 7
-        cut 12
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-
-        let s = r"
-        cut 3
-        deal with increment 7
-        cut 5
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
+        let deck = shuffle(&repeated, 13);
+        for (pos, &card) in deck.iter().enumerate() {
+            assert_eq!(BigInt::from(card), card_at(&moves, &n, &BigInt::from(7), &BigInt::from(pos))?);
+        }
 
+        // far past where u64 or i128 arithmetic would overflow
+        let n: BigInt = "1000000000000000000000000000057".parse()?;
+        let times = BigInt::from(10).pow(25);
+        let shuffle = Affine::from_moves(&moves, &n).pow(&times);
+        let pos = BigInt::from(2020);
+        assert_eq!(pos, shuffle.apply(&card_at(&moves, &n, &times, &pos)?));
         Ok(())
     }
 
-    #[test]
-    #[ignore]
-    fn test6() -> Result<()> {
-        let s = r"
-        deal with increment 3
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-
-        let s = r"
-        deal with increment 10
-        deal into new stack
-        cut -1
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-        Ok(())
+    fn shuffle(moves: &[Move], size: usize) -> Vec<usize> {
+        let mut deck = (0..size).collect::<Vec<_>>();
+        for &mov in moves {
+            match mov {
+                Move::Cut(arg) => cut(&mut deck, arg),
+                Move::DealInc(arg) => deal_inc(&mut deck, arg),
+                Move::DealNew => deck.reverse()
+            }
+        }
+        deck
     }
 
-    #[test]
-    #[ignore]
-    fn test7() -> Result<()> {
-        let s = r"
-        deal with increment 3
-        deal with increment 4
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-
-        let s = r"
-        deal with increment 12
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-
-        let s = r"
-        deal with increment 3
-        deal with increment 5
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
+    fn cut(deck: &mut [usize], num: i64) {
+        if num > 0 {
+            deck.rotate_left(num as usize);
+        } else {
+            deck.rotate_right(-num as usize);
+        }
+    }
 
-        let s = r"
-        deal with increment 2
-        ";
-        println!("{:?}", shuffle(&parse(s)?, 13));
-        Ok(())
+    fn deal_inc(deck: &mut [usize], num: i64) {
+        let orig = deck.to_vec();
+        let mut i = 0;
+        for c in orig {
+            deck[i] = c;
+            i = (i + num as usize) % deck.len();
+        }
     }
 }
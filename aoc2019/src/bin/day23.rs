@@ -33,37 +33,59 @@
 //
 // Your puzzle answer was 19316.
 
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day23;
+
+pub const SOLUTION: Entry = Entry::new::<Day23>();
+
+impl Solution for Day23 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 23;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        let (first_y, _) = exec(nums)?;
+        Ok(first_y)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        let (_, repeated_y) = exec(nums)?;
+        Ok(repeated_y)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day23")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-    exec(&nums)?;
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn init_progs(nums: &Vec<i64>, n: usize) -> Vec<Prog> {
+fn init_progs(nums: &[i64], n: usize) -> Vec<Prog> {
     let mut progs = Vec::new();
     for i in 0..n {
-        let mut prog = Prog::new(nums.clone());
+        let mut prog = Prog::new(nums.to_vec());
         prog.send(i as i64);
         progs.push(prog);
     }
     progs
 }
 
-fn exec(nums: &Vec<i64>) -> Result<()> {
+///
+/// Run the network, returning the first y value sent to the NAT and the first it sends twice in a
+/// row
+///
+fn exec(nums: &[i64]) -> Result<(i64, i64)> {
     let mut progs = init_progs(nums, 50);
 
     let mut nat = None;
@@ -97,7 +119,7 @@ fn exec(nums: &Vec<i64>) -> Result<()> {
             }
         }
         if idle {
-            let (x, y) = nat.unwrap();
+            let (x, y) = nat.ok_or_else(|| anyhow::anyhow!("network idle before using the NAT"))?;
             if previous_wakeup_y == Some(y) {
                 break;
             }
@@ -106,9 +128,7 @@ fn exec(nums: &Vec<i64>) -> Result<()> {
             previous_wakeup_y = Some(y);
         }
     };
-    println!("{:?}", first_y);
-    println!("{:?}", previous_wakeup_y);
-    Ok(())
+    Ok((first_y.unwrap(), previous_wakeup_y.unwrap()))
 }
 
 mod prog {
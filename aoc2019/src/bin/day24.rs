@@ -231,35 +231,55 @@
 //
 // Your puzzle answer was 1912.
 
-use std::collections::{HashSet, VecDeque};
-use std::convert::{TryFrom, TryInto};
-use std::fs;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::iter::successors;
+
+use aoc::cycle;
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::solution::{Entry, Solution};
 
-use crate::geom::{Direction, Vector2};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day24;
+
+pub const SOLUTION: Entry = Entry::new::<Day24>();
+
+impl Solution for Day24 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 24;
+
+    type Input = [[u8; 5]; 5];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display> {
+        Ok(part2(grid))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day24")?;
-    part1(&input);
-    part2(&input);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(s: &str) {
-    let mut grid = parse_grid(s);
-    let mut seen = HashSet::new();
-    while seen.insert(grid) {
-        grid = tick(&grid);
-    }
-    let res = biodiversity(&grid);
-    println!("{}", res);
+fn part1(grid: &[[u8; 5]; 5]) -> u32 {
+    let cycle = cycle::detect_cycle(*grid, tick);
+    let first_repeat = successors(Some(*grid), |grid| Some(tick(grid))).nth(cycle.start).unwrap();
+    biodiversity(&first_repeat)
 }
 
-fn part2(s: &str) {
-    let grid = parse_grid(s);
+fn part2(grid: &[[u8; 5]; 5]) -> usize {
     let mut grids = VecDeque::new();
-    grids.push_back(grid);
+    grids.push_back(*grid);
 
     for _ in 0..200 {
         grids = tick_recursive(&grids);
@@ -271,7 +291,7 @@ fn part2(s: &str) {
             .filter(|&&c| c == b'#')
             .count()
         ).sum::<usize>();
-    println!("{}", total_bugs);
+    total_bugs
 }
 
 fn tick_recursive(grids: &VecDeque<[[u8; 5]; 5]>) -> VecDeque<[[u8; 5]; 5]> {
@@ -360,11 +380,11 @@ fn count_adj_tile(grid: &[[u8; 5]; 5], y: usize, x: usize) -> usize {
         (_, 4) => [Direction::N, Direction::W, Direction::S].iter(),
         (4, _) => [Direction::E, Direction::N, Direction::W].iter(),
         (_, 0) => [Direction::S, Direction::E, Direction::N].iter(),
-        _ => Direction::VALUES.iter(),
+        _ => Direction::VALUES_4D.iter(),
     };
-    let pos = Vector2::new(x, y);
+    let pos = Vector2::new([x as i64, y as i64]);
     dirs.map(|dir| pos + dir.dxdy())
-        .filter(|new_pos| grid[new_pos.y as usize][new_pos.x as usize] == b'#')
+        .filter(|new_pos| grid[new_pos.y() as usize][new_pos.x() as usize] == b'#')
         .count()
 }
 
@@ -374,16 +394,26 @@ fn count_adj_wall(grid: &[[u8; 5]; 5], dir: Direction) -> usize {
         Direction::E => grid.iter().map(|l| l[4]).filter(|&c| c == b'#').count(),
         Direction::S => grid[4].iter().filter(|&&c| c == b'#').count(),
         Direction::W => grid.iter().map(|l| l[0]).filter(|&c| c == b'#').count(),
+        _ => unreachable!("the grids only meet orthogonally"),
     }
 }
 
-fn parse_grid(s: &str) -> [[u8; 5]; 5] {
-    s.lines()
+fn parse_grid(s: &str) -> Result<[[u8; 5]; 5]> {
+    let mut grid = [[b'.'; 5]; 5];
+    let mut lines = s.lines()
         .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| <[u8; 5]>::try_from(line.as_bytes()).unwrap())
-        .collect::<Vec<_>>()
-        .try_into().unwrap()
+        .filter(|line| !line.is_empty());
+    for row in grid.iter_mut() {
+        let line = lines.next().ok_or_else(|| AocError::parse_offset(s, s.len(), "expected 5 rows"))?;
+        if let Some(i) = line.find(|c| c != '#' && c != '.') {
+            return Err(AocError::parse(s, &line[i..], "expected # or .").into());
+        }
+        *row = <[u8; 5]>::try_from(line.as_bytes()).map_err(|_| AocError::parse(s, line, "expected 5 tiles"))?;
+    }
+    if let Some(line) = lines.next() {
+        return Err(AocError::parse(s, line, "expected 5 rows").into());
+    }
+    Ok(grid)
 }
 
 fn biodiversity(grid: &[[u8; 5]; 5]) -> u32 {
@@ -432,7 +462,7 @@ mod tests {
         #....
         .#...
         ";
-        assert_eq!(2129920, biodiversity(&parse_grid(s)));
+        assert_eq!(2129920, biodiversity(&parse_grid(s)?));
 
         let s = r"
         ....#
@@ -441,7 +471,7 @@ mod tests {
         ..#..
         #....
         ";
-        let g = parse_grid(s);
+        let g = parse_grid(s)?;
         assert_eq!(1, count_adj_wall(&g, Direction::N));
         assert_eq!(2, count_adj_wall(&g, Direction::E));
         assert_eq!(1, count_adj_wall(&g, Direction::S));
@@ -449,113 +479,3 @@ mod tests {
         Ok(())
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    pub enum Direction {
-        N,
-        E,
-        S,
-        W,
-    }
-
-    impl Direction {
-        pub const VALUES: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::S => Vector2::new(0, 1),
-                Direction::W => Vector2::new(-1, 0),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::E,
-                Direction::E => Direction::S,
-                Direction::S => Direction::W,
-                Direction::W => Direction::N,
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self) -> Direction {
-            match self {
-                Direction::N => Direction::W,
-                Direction::E => Direction::N,
-                Direction::S => Direction::E,
-                Direction::W => Direction::S,
-            }
-        }
-    }
-}
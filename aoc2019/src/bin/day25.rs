@@ -41,34 +41,49 @@
 // If you like, you can
 // .
 
-use std::collections::{HashMap, VecDeque};
-use std::fs;
+use std::fmt::Display;
+use std::iter;
 
-use anyhow::Context;
+use aoc::combinatorics;
+use aoc::error::AocError;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::prog::{Prog, Status};
+use self::prog::{Prog, Status};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day25")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-    exec(&nums)?;
-    Ok(())
+struct Day25;
+
+pub const SOLUTION: Entry = Entry::new::<Day25>();
+
+impl Solution for Day25 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 25;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        let out = pass_checkpoint(nums)?;
+        out.split_whitespace()
+            .find_map(|word| word.parse::<u64>().ok())
+            .ok_or_else(|| anyhow::anyhow!("no password in: {}", out))
+    }
+
+    fn part2(_: &Self::Input) -> Result<impl Display> {
+        Ok("Merry Christmas!")
+    }
 }
 
-fn read_stdin() -> String {
-    let stdin = std::io::stdin();
-    let mut s = String::new();
-    stdin.read_line(&mut s).unwrap();
-    s.trim().to_string()
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
+// picks up every item that's safe to carry, and stops at the security checkpoint
 const AUTO: &str = "s,take fixed point,n,\
                     w,w,w,take hologram,e,e,e,\
                     n,take candy cane,\
@@ -78,67 +93,55 @@ const AUTO: &str = "s,take fixed point,n,\
                     n,n,take polygon,\
                     s,w,take fuel cell,w";
 
-fn exec(nums: &Vec<i64>) -> Result<()> {
-    let mut items = HashMap::new();
-    items.insert(1, "fixed point"); // +
-    items.insert(2, "hologram"); // -
-    items.insert(3, "candy cane"); // +
-    items.insert(4, "antenna"); // -
-    items.insert(5, "whirled peas"); // -
-    items.insert(6, "shell"); // +
-    items.insert(7, "polygon"); // +
-    items.insert(8, "fuel cell"); // -
-
-    let mut prog = Prog::new(nums.clone());
-    let mut queued_commands = VecDeque::new();
-    loop {
-        let state = prog.resume()?;
-        println!("{}", prog.recv_string());
-        if state == Status::Stopped {
-            break;
-        }
+const ITEMS: [&str; 8] = [
+    "fixed point", "hologram", "candy cane", "antenna", "whirled peas", "shell", "polygon", "fuel cell",
+];
+
+// the pressure-sensitive floor
+const FLOOR: &str = "w";
+
+///
+/// Collect the items, then try every combination of them on the floor, taking or dropping one
+/// item between tries, returning the output once the droid is let through
+///
+fn pass_checkpoint(nums: &[i64]) -> Result<String> {
+    let mut prog = Prog::new(nums.to_vec());
+    prog.resume()?;
+    prog.recv_string();
+    for command in AUTO.split(',') {
+        exec(&mut prog, command)?;
+    }
 
-        if queued_commands.is_empty() {
-            let s = read_stdin();
-            match &*s {
-                "auto" => {
-                    queued_commands.extend(
-                        AUTO.split(",").map(|s| s.to_string()));
-                }
-                _ => queued_commands.push_front(s),
-            }
+    let mut held = [true; ITEMS.len()];
+    for toggle in iter::once(None).chain(combinatorics::gray_code(ITEMS.len() as u32).map(Some)) {
+        if let Some(i) = toggle {
+            let verb = if held[i] { "drop" } else { "take" };
+            exec(&mut prog, &format!("{} {}", verb, ITEMS[i]))?;
+            held[i] = !held[i];
         }
-
-        let mut s = queued_commands.pop_front().unwrap();
-        let mut sp = s.trim();
-        sp = match sp {
-            "n" => "north",
-            "s" => "south",
-            "e" => "east",
-            "w" => "west",
-            "i" => "inv",
-            "quit" => { break; }
-            _ => sp,
-        };
-        s = sp.to_string();
-
-        let num = &s[s.len() - 1..].parse::<i32>();
-        if let Ok(d) = num {
-            let obj = items[&d];
-            let com = match &s[..1] {
-                "d" => Some("drop"),
-                "g" => Some("take"),
-                _ => None,
-            };
-            if let Some(c) = com {
-                s = format!("{} {}", c, obj);
-            }
+        if let (Status::Stopped, out) = exec(&mut prog, FLOOR)? {
+            return Ok(out);
         }
-        println!("{}", s);
-        prog.send_str(&s);
-        prog.send_str("\n");
     }
-    Ok(())
+    Err(AocError::no_solution("no combination of items weighs the right amount").into())
+}
+
+///
+/// Send `command`, returning the output up to when the program next waits or stops
+///
+fn exec(prog: &mut Prog, command: &str) -> Result<(Status, String)> {
+    let command = match command.trim() {
+        "n" => "north",
+        "s" => "south",
+        "e" => "east",
+        "w" => "west",
+        "i" => "inv",
+        command => command,
+    };
+    prog.send_str(command);
+    prog.send_str("\n");
+    let state = prog.resume()?;
+    Ok((state, prog.recv_string()))
 }
 
 mod prog {
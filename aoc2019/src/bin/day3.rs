@@ -81,41 +81,14 @@
 // Your puzzle answer was 4158.
 
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 use std::str::FromStr;
 
-use anyhow;
-use anyhow::Context;
+use aoc::error::{self, AocError};
+use aoc::geom::{Direction, Vector2};
+use aoc::solution::{Entry, Solution};
 
-enum Direction {
-    U,
-    R,
-    D,
-    L,
-}
-
-impl Direction {
-    fn dxdy(&self) -> (i32, i32) {
-        match self {
-            Direction::U => (0, -1),
-            Direction::R => (1, 0),
-            Direction::D => (0, 1),
-            Direction::L => (-1, 0),
-        }
-    }
-}
-
-#[derive(Eq, PartialEq, Hash, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn manhattan_distance(&self) -> u32 {
-        (self.x.abs() + self.y.abs()) as u32
-    }
-}
+type Result<T> = std::result::Result<T, anyhow::Error>;
 
 struct Move {
     dir: Direction,
@@ -123,40 +96,37 @@ struct Move {
 }
 
 impl FromStr for Move {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, len) = (&s[0..1], &s[1..]);
-        let len = len.parse::<i32>()
-            .with_context(|| format!("Failed to parse {}", s))?;
-
-        match dir {
-            "U" => Ok(Move { dir: Direction::U, len }),
-            "R" => Ok(Move { dir: Direction::R, len }),
-            "D" => Ok(Move { dir: Direction::D, len }),
-            "L" => Ok(Move { dir: Direction::L, len }),
-            _ => anyhow::bail!("Invalid move {}", s),
-        }
+    type Err = AocError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let dir = match s.chars().next() {
+            Some('U') => Direction::N,
+            Some('R') => Direction::E,
+            Some('D') => Direction::S,
+            Some('L') => Direction::W,
+            _ => return Err(AocError::parse(s, s, "expected a move of U, R, D or L")),
+        };
+        let len = error::parse_at(s, &s[1..])?;
+        Ok(Move { dir, len })
     }
 }
 
-fn parse_wire(s: &str) -> Result<Vec<Move>, anyhow::Error> {
-    s.trim()
+fn parse_wire(input: &str, line: &str) -> std::result::Result<Vec<Move>, AocError> {
+    line.trim()
         .split(',')
-        .map(|x| x.parse::<Move>())
+        .map(|x| x.parse::<Move>().map_err(|e| e.within(input, x)))
         .collect()
 }
 
-fn lay_wire(wire: &[Move]) -> HashMap<Point, u32> {
+fn lay_wire(wire: &[Move]) -> HashMap<Vector2<i32>, u32> {
     let mut points = HashMap::new();
-    let mut pos = Point { x: 0, y: 0 };
+    let mut pos = Vector2::zero();
     let mut steps = 0;
 
     for mov in wire {
-        let (dx, dy) = mov.dir.dxdy();
+        let d = mov.dir.dxdy();
         for _ in 0..mov.len {
-            pos.x += dx;
-            pos.y += dy;
+            pos += d;
             steps += 1;
             points.entry(pos).or_insert(steps);
         }
@@ -164,33 +134,48 @@ fn lay_wire(wire: &[Move]) -> HashMap<Point, u32> {
     points
 }
 
-fn main() -> Result<(), anyhow::Error> {
-    let input = fs::read_to_string("input/aoc2019/day3")?;
+struct Day3;
+
+pub const SOLUTION: Entry = Entry::new::<Day3>();
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 3;
+
+    type Input = (Vec<Move>, Vec<Move>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut wires = input.lines().map(|line| parse_wire(input, line));
+        match (wires.next(), wires.next()) {
+            (Some(wire0), Some(wire1)) => Ok((wire0?, wire1?)),
+            _ => Err(AocError::parse_offset(input, input.len(), "expected two wires").into()),
+        }
+    }
 
-    let wires =
-        input.lines().take(2)
-            .map(|line| parse_wire(line))
-            .collect::<Result<Vec<_>, _>>()?;
+    fn part1((wire0, wire1): &Self::Input) -> Result<impl Display> {
+        let (p1, _) = part1_part2(wire0, wire1);
+        p1.ok_or_else(|| AocError::no_solution("no intersections").into())
+    }
 
-    anyhow::ensure!(wires.len() >= 2, "insufficient lines");
+    fn part2((wire0, wire1): &Self::Input) -> Result<impl Display> {
+        let (_, p2) = part1_part2(wire0, wire1);
+        p2.ok_or_else(|| AocError::no_solution("no intersections").into())
+    }
+}
 
-    let (p1, p2) = part1_part2(&wires[0], &wires[1]);
-    let p1 = p1.ok_or_else(|| anyhow::anyhow!("no intersections"))?;
-    let p2 = p2.ok_or_else(|| anyhow::anyhow!("no intersections"))?;
-    println!("{}", p1);
-    println!("{}", p2);
-    Ok(())
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn part1_part2(wire0: &[Move], wire1: &[Move]) -> (Option<u32>, Option<u32>) {
     let wire0 = lay_wire(wire0);
     let wire1 = lay_wire(wire1);
-    let wire0_points: HashSet<&Point> = wire0.keys().collect();
-    let wire1_points: HashSet<&Point> = wire1.keys().collect();
-    let intersects: Vec<&&Point> = wire0_points.intersection(&wire1_points).collect();
+    let wire0_points: HashSet<&Vector2<i32>> = wire0.keys().collect();
+    let wire1_points: HashSet<&Vector2<i32>> = wire1.keys().collect();
+    let intersects: Vec<&&Vector2<i32>> = wire0_points.intersection(&wire1_points).collect();
 
     let min_md = intersects.iter()
-        .map(|x| x.manhattan_distance())
+        .map(|x| x.manhattan() as u32)
         .min();
 
     let min_steps = intersects.iter()
@@ -205,18 +190,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test1() -> Result<(), anyhow::Error> {
-        let wire0 = parse_wire("R75,D30,R83,U83,L12,D49,R71,U7,L72")?;
-        let wire1 = parse_wire("U62,R66,U55,R34,D71,R55,D58,R83")?;
+    fn test1() -> Result<()> {
+        let wire0 = parse_wire("R75,D30,R83,U83,L12,D49,R71,U7,L72", "R75,D30,R83,U83,L12,D49,R71,U7,L72")?;
+        let wire1 = parse_wire("U62,R66,U55,R34,D71,R55,D58,R83", "U62,R66,U55,R34,D71,R55,D58,R83")?;
         let (p1, p2) = part1_part2(&wire0, &wire1);
         assert_eq!((p1.unwrap(), p2.unwrap()), (159, 610));
         Ok(())
     }
 
     #[test]
-    fn test2() -> Result<(), anyhow::Error> {
-        let wire0 = parse_wire("R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51")?;
-        let wire1 = parse_wire("U98,R91,D20,R16,D67,R40,U7,R15,U6,R7")?;
+    fn test2() -> Result<()> {
+        let wire0 = parse_wire("R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51", "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51")?;
+        let wire1 = parse_wire("U98,R91,D20,R16,D67,R40,U7,R15,U6,R7", "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7")?;
         let (p1, p2) = part1_part2(&wire0, &wire1);
         assert_eq!((p1.unwrap(), p2.unwrap()), (135, 410));
         Ok(())
@@ -34,11 +34,41 @@
 //
 // Your puzzle answer was 1462.
 
-fn main() {
-    let p1 = (124075..=580769).filter(|&i| is_valid_part1(i)).count();
-    let p2 = (124075..=580769).filter(|&i| is_valid_part2(i)).count();
-    println!("{}", p1);
-    println!("{}", p2);
+use std::fmt::Display;
+
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
+
+type Result<T> = std::result::Result<T, anyhow::Error>;
+
+struct Day4;
+
+pub const SOLUTION: Entry = Entry::new::<Day4>();
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 4;
+
+    type Input = (i32, i32);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let range = input.trim();
+        let (lo, hi) = range.split_once('-')
+            .ok_or_else(|| AocError::parse(input, range, "expected a range lo-hi"))?;
+        Ok((error::parse_at(input, lo)?, error::parse_at(input, hi)?))
+    }
+
+    fn part1(&(lo, hi): &Self::Input) -> Result<impl Display> {
+        Ok((lo..=hi).filter(|&i| is_valid_part1(i)).count())
+    }
+
+    fn part2(&(lo, hi): &Self::Input) -> Result<impl Display> {
+        Ok((lo..=hi).filter(|&i| is_valid_part2(i)).count())
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn is_valid_part1(num: i32) -> bool {
@@ -95,36 +95,58 @@
 //
 // Your puzzle answer was 11430197.
 
-use std::{fs, io};
 use std::convert::TryFrom;
+use std::fmt::Display;
 use std::iter;
+use std::io;
 
-use anyhow::Context;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day5;
+
+pub const SOLUTION: Entry = Entry::new::<Day5>();
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 5;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day5")?;
-    let nums: Vec<i32> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<Vec<_>>>()?;
+    aoc::runner::main(&[SOLUTION])
+}
 
-    part1(&nums)?;
-    part2(&nums)?;
-    Ok(())
+fn part1(nums: &[i32]) -> Result<String> {
+    diagnostic_code(1, nums)
 }
 
-fn part1(nums: &Vec<i32>) -> Result<()> {
-    exec(1, &mut nums.clone(), &mut io::stdout())?;
-    Ok(())
+fn part2(nums: &[i32]) -> Result<String> {
+    diagnostic_code(5, nums)
 }
 
-fn part2(nums: &Vec<i32>) -> Result<()> {
-    exec(5, &mut nums.clone(), &mut io::stdout())?;
-    Ok(())
+fn diagnostic_code(input: i32, nums: &[i32]) -> Result<String> {
+    let mut out = Vec::new();
+    exec(input, &mut nums.to_vec(), &mut out)?;
+    let out = String::from_utf8(out)?;
+    out.lines().last()
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("no output"))
 }
 
 fn exec(input: i32,
@@ -110,70 +110,69 @@
 //
 // Your puzzle answer was 532.
 
-use std::collections::{HashMap, VecDeque};
-use std::fs;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc::error::AocError;
+use aoc::graph::Tree;
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day6;
+
+pub const SOLUTION: Entry = Entry::new::<Day6>();
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 6;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        build_tree(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<impl Display> {
+        let orbits = build_tree(input)?;
+        Ok(count_orbits(&orbits))
+    }
+
+    fn part2(input: &Self::Input) -> Result<impl Display> {
+        let orbits = build_tree(input)?;
+        count_transfers(&orbits, "YOU", "SAN")
+            .ok_or_else(|| AocError::no_solution("YOU and SAN aren't orbiting bodies in the same system").into())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day6")?;
-    let (graph, parents) = build_graph(&input)?;
-    println!("{}", count_orbits(&graph));
-    println!("{}", count_transfers(&parents, "YOU", "SAN"));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn build_graph(input: &str)
-               -> Result<(HashMap<&str, Vec<&str>>,
-                          HashMap<&str, &str>)> {
-    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
-    let mut parents: HashMap<&str, &str> = HashMap::new();
+fn build_tree(input: &str) -> Result<Tree<&str>> {
+    let mut parents = HashMap::new();
     for line in input.lines()
         .map(|s| s.trim())
         .filter(|s| !s.is_empty()) {
         let s: Vec<&str> = line.split(')').collect();
-        anyhow::ensure!(s.len() == 2, "invalid input: {}", line);
-
-        graph.entry(s[0]).or_default().push(s[1]);
-        parents.entry(s[1]).or_insert(s[0]);
-    }
-    Ok((graph, parents))
-}
-
-fn count_orbits(graph: &HashMap<&str, Vec<&str>>) -> i32 {
-    let mut count = 0;
-    let mut stack: Vec<(&str, i32)> = Vec::new();
-    stack.push(("COM", 0));
-    while !stack.is_empty() {
-        let (node, depth) = stack.pop().unwrap();
-        count += depth;
-        if let Some(neighbours) = graph.get(node) {
-            stack.extend(neighbours.iter().map(|&x| (x, depth + 1)))
+        if s.len() != 2 {
+            return Err(AocError::parse(input, line, "expected an orbit A)B").into());
+        }
+        if parents.insert(s[1], s[0]).is_some() {
+            return Err(AocError::parse(input, line, "a body can only orbit one other").into());
         }
     }
-    count
+    Tree::from_parents(parents).map_err(|err| AocError::invalid(format!("orbits go round in a {}", err)).into())
 }
 
-fn count_transfers(parents: &HashMap<&str, &str>, from: &str, to: &str) -> usize {
-    let p1 = get_path(&parents, from);
-    let p2 = get_path(&parents, to);
-    let c = p1.iter().zip(p2.iter()).take_while(|(x, y)| x == y).count();
-    let len = (p1.len() - c) + (p2.len() - c);
-    len
+fn count_orbits(orbits: &Tree<&str>) -> usize {
+    orbits.depths().map(|(_, depth)| depth).sum()
 }
 
-fn get_path<'a>(parents: &HashMap<&'a str, &'a str>, target: &str) -> VecDeque<&'a str> {
-    let mut path = VecDeque::new();
-    let mut node = match parents.get_key_value(target) {
-        Some((&k, _)) => k,
-        None => return VecDeque::new(),
-    };
-
-    while node != "COM" {
-        node = parents[node];
-        path.push_front(node);
-    }
-    path
+fn count_transfers(orbits: &Tree<&str>, from: &str, to: &str) -> Option<usize> {
+    // the transfers are between the bodies the two are orbiting
+    orbits.distance(orbits.parent(from)?, orbits.parent(to)?)
 }
 
 #[cfg(test)]
@@ -195,8 +194,8 @@ mod tests {
         J)K
         K)L
         ";
-        let (graph, _) = build_graph(input)?;
-        assert_eq!(42, count_orbits(&graph));
+        let orbits = build_tree(input)?;
+        assert_eq!(42, count_orbits(&orbits));
 
         let input = "
         COM)B
@@ -213,8 +212,10 @@ mod tests {
         K)YOU
         I)SAN
         ";
-        let (_, parents) = build_graph(input)?;
-        assert_eq!(4, count_transfers(&parents, "YOU", "SAN"));
+        let orbits = build_tree(input)?;
+        assert_eq!(Some(4), count_transfers(&orbits, "YOU", "SAN"));
+        assert_eq!(None, count_transfers(&orbits, "YOU", "XYZ"));
+        assert!(build_tree("COM)B\nB)C\nC)B").is_err());
         Ok(())
     }
 }
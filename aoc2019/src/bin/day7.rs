@@ -87,54 +87,67 @@
 //
 // Your puzzle answer was 49810599.
 
-use std::{fs, thread};
+use std::fmt::Display;
 use std::sync::mpsc;
+use std::thread;
 
-use anyhow::Context;
+use aoc::combinatorics::Permutations;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day7;
+
+pub const SOLUTION: Entry = Entry::new::<Day7>();
+
+impl Solution for Day7 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 7;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day7")?;
-    let nums: Vec<i32> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-
-    part1(&nums)?;
-    part2(&nums)?;
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(nums: &Vec<i32>) -> Result<()> {
+fn part1(nums: &[i32]) -> Result<i32> {
     let phases = [0, 1, 2, 3, 4];
-    let (signal, phase) = max_amplified_signal(&nums, &phases, false)?;
-    println!("{:?}", (signal, phase));
-    Ok(())
+    let (signal, _) = max_amplified_signal(nums, &phases, false)?;
+    Ok(signal)
 }
 
-fn part2(nums: &Vec<i32>) -> Result<()> {
+fn part2(nums: &[i32]) -> Result<i32> {
     let phases = [5, 6, 7, 8, 9];
-    let (signal, phase) = max_amplified_signal(&nums, &phases, true)?;
-    println!("{:?}", (signal, phase));
-    Ok(())
+    let (signal, _) = max_amplified_signal(nums, &phases, true)?;
+    Ok(signal)
 }
 
 fn max_amplified_signal(nums: &[i32], phases: &[i32], is_loop: bool) -> Result<(i32, Vec<i32>)> {
-    let phases = phases.to_vec();
     let mut max_phases: Vec<i32> = Vec::new();
     let mut max_signal = i32::MIN;
 
-    let perms = Permutation::new(phases);
-    for phases_perm in perms {
-        let signal = amplify_signal(nums, &phases_perm, is_loop)?;
+    let mut perms = Permutations::new(phases.to_vec());
+    while let Some(phases_perm) = perms.next_perm() {
+        let signal = amplify_signal(nums, phases_perm, is_loop)?;
         if signal > max_signal {
             max_signal = signal;
-            max_phases = phases_perm;
+            max_phases = phases_perm.to_vec();
         }
     }
     Ok((max_signal, max_phases))
@@ -240,65 +253,6 @@ mod tests {
 
         Ok(())
     }
-
-    #[test]
-    fn test_perm() {
-        let a = [1, 2, 3].to_vec();
-        let mut x = Permutation::new(a);
-        assert_eq!(x.next(), Some(vec![1, 2, 3]));
-        assert_eq!(x.next(), Some(vec![2, 1, 3]));
-        assert_eq!(x.next(), Some(vec![3, 1, 2]));
-        assert_eq!(x.next(), Some(vec![1, 3, 2]));
-        assert_eq!(x.next(), Some(vec![2, 3, 1]));
-        assert_eq!(x.next(), Some(vec![3, 2, 1]));
-        assert_eq!(x.next(), None);
-    }
-}
-
-struct Permutation<T> {
-    i: i32,
-    c: Vec<usize>,
-    a: Vec<T>,
-}
-
-impl<T: Clone> Permutation<T> {
-    fn new(a: Vec<T>) -> Permutation<T> {
-        let c = vec![0; a.len()];
-        Permutation {
-            i: -1,
-            c,
-            a,
-        }
-    }
-}
-
-impl<T: Clone> Iterator for Permutation<T> {
-    type Item = Vec<T>;
-
-    fn next(&mut self) -> Option<Vec<T>> {
-        let Permutation { i: ip, c, a } = self;
-        if *ip < 0 {
-            *ip = 0;
-            return Some(a.clone());
-        }
-        let mut i = *ip as usize;
-        while i < c.len() {
-            if c[i] < i {
-                if i % 2 == 0 {
-                    a.swap(0, i);
-                } else {
-                    a.swap(c[i], i);
-                }
-                c[i] += 1;
-                *ip = 0;
-                return Some(a.clone());
-            } else {
-                c[i] = 0;
-                i += 1;
-            }
-        }
-        None
-    }
 }
 
 mod prog {
@@ -61,42 +61,59 @@
 //
 // Your puzzle answer was GCPHL.
 
-use std::fs;
+use std::fmt::Display;
+
+use aoc::error::AocError;
+use aoc::ocr;
+use aoc::solution::{Entry, Solution};
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
 const WIDTH: usize = 25;
 const HEIGHT: usize = 6;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day8")?;
-    let a: Vec<u8> = input.trim().as_bytes().iter().map(|c| c - '0' as u8).collect();
-    let a: Vec<&[u8]> = a.chunks_exact(WIDTH).collect();
-    let a: Vec<&[&[u8]]> = a.chunks_exact(HEIGHT).collect();
+struct Day8;
 
-    let p1 = part1(&a)?;
-    let p2 = part2(&a)?;
+pub const SOLUTION: Entry = Entry::new::<Day8>();
 
-    println!("{}", p1);
-    pprint(&p2);
-    Ok(())
-}
+impl Solution for Day8 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 8;
 
-fn pprint(image: &[[u8; WIDTH]; HEIGHT]) {
-    for row in image {
-        for elem in row {
-            let c = match elem {
-                0 => ' ',
-                1 => '#',
-                _ => unreachable!()
-            };
-            print!("{}", c);
+    type Input = Vec<Vec<Vec<u8>>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let pixels = input.trim();
+        let a: Vec<u8> = pixels.char_indices()
+            .map(|(i, c)| c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| AocError::parse(input, &pixels[i..], "expected a digit")))
+            .collect::<std::result::Result<_, _>>()?;
+        if a.is_empty() || !a.len().is_multiple_of(WIDTH * HEIGHT) {
+            return Err(AocError::invalid(format!("{} pixels aren't whole {}x{} layers", a.len(), WIDTH, HEIGHT)).into());
         }
-        println!();
+        let a: Vec<Vec<u8>> = a.chunks_exact(WIDTH).map(|row| row.to_vec()).collect();
+        Ok(a.chunks_exact(HEIGHT).map(|layer| layer.to_vec()).collect())
     }
+
+    fn part1(layers: &Self::Input) -> Result<impl Display> {
+        part1(layers)
+    }
+
+    fn part2(layers: &Self::Input) -> Result<impl Display> {
+        part2(layers)
+    }
+
+    fn render(layers: &Self::Input, part: u8) -> Result<Option<String>> {
+        Ok((part == 2).then(|| ocr::draw_grid(&decode(layers), |&p| p == 1)))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(a: &Vec<&[&[u8]]>) -> Result<u32> {
+fn part1(a: &[Vec<Vec<u8>>]) -> Result<u32> {
     let min_layer = a.iter().min_by_key(
         |layer| layer.iter()
             .flat_map(|x| x.iter())
@@ -109,7 +126,12 @@ fn part1(a: &Vec<&[&[u8]]>) -> Result<u32> {
     Ok(res)
 }
 
-fn part2(a: &Vec<&[&[u8]]>) -> Result<[[u8; WIDTH]; HEIGHT]> {
+fn part2(a: &[Vec<Vec<u8>>]) -> Result<String> {
+    let res = ocr::recognize_grid(&decode(a), |&p| p == 1)?;
+    Ok(res)
+}
+
+fn decode(a: &[Vec<Vec<u8>>]) -> [[u8; WIDTH]; HEIGHT] {
     let mut image = [[0u8; WIDTH]; HEIGHT];
 
     for y in 0..HEIGHT {
@@ -118,6 +140,5 @@ fn part2(a: &Vec<&[&[u8]]>) -> Result<[[u8; WIDTH]; HEIGHT]> {
                 .skip_while(|layer| layer[y][x] == 2).next().unwrap()[y][x];
         }
     }
-
-    Ok(image)
+    image
 }
@@ -52,38 +52,50 @@
 //
 // Your puzzle answer was 66772.
 
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::Context;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
 
-use crate::prog::Prog;
+use self::prog::Prog;
 
 type Result<T> = std::result::Result<T, anyhow::Error>;
 
+struct Day9;
+
+pub const SOLUTION: Entry = Entry::new::<Day9>();
+
+impl Solution for Day9 {
+    const YEAR: u16 = 2019;
+    const DAY: u8 = 9;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::ints(input)?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2019/day9")?;
-    let nums: Vec<i64> =
-        input.trim()
-            .split(',')
-            .map(|s| s.parse()
-                .with_context(|| format!("Failed to parse {}", s)))
-            .collect::<Result<_>>()?;
-
-    part1(&nums)?;
-    part2(&nums)?;
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(nums: &Vec<i64>) -> Result<()> {
-    let outs = Prog::exec_once(nums.clone(), 1)?;
-    println!("{:?}", outs);
-    Ok(())
+fn part1(nums: &[i64]) -> Result<i64> {
+    let outs = Prog::exec_once(nums.to_vec(), 1)?;
+    outs.last().copied().ok_or_else(|| anyhow::anyhow!("no output"))
 }
 
-fn part2(nums: &Vec<i64>) -> Result<()> {
-    let outs = Prog::exec_once(nums.clone(), 2)?;
-    println!("{:?}", outs);
-    Ok(())
+fn part2(nums: &[i64]) -> Result<i64> {
+    let outs = Prog::exec_once(nums.to_vec(), 2)?;
+    outs.last().copied().ok_or_else(|| anyhow::anyhow!("no output"))
 }
 
 
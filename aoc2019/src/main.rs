@@ -1,4 +1,8 @@
-fn main() {}
+use anyhow::Result;
+
+fn main() -> Result<()> {
+    aoc::runner::main(aoc2019::SOLUTIONS)
+}
 
 #[cfg(test)]
 mod tests {
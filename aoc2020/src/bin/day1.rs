@@ -38,48 +38,64 @@
 //
 // Your puzzle answer was 13891280.
 
-use std::collections::HashSet;
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::Context;
 use anyhow::Result;
-use itertools::Itertools;
+
+use aoc::combinatorics;
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
+
+struct Day1;
+
+pub const SOLUTION: Entry = Entry::new::<Day1>();
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        part1(nums).ok_or_else(|| AocError::no_solution("no pair sums to 2020").into())
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        part2(nums).ok_or_else(|| AocError::no_solution("no triple sums to 2020").into())
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day1")?;
-    let nums = parse(&input)?;
-    println!("{:?}", part1(&nums).unwrap());
-    println!("{:?}", part2(&nums).unwrap());
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Result<HashSet<i32>> {
-    s.lines()
+fn parse(s: &str) -> Result<Vec<i32>> {
+    Ok(s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|s| s.parse::<i32>()
-            .with_context(|| format!("failed to parse: {}", s)))
-        .collect()
+        .map(|line| error::parse_at(s, line))
+        .collect::<Result<_, AocError>>()?)
 }
 
-fn part1(nums: &HashSet<i32>) -> Option<i32> {
-    for i in nums.iter() {
-        let j = 2020 - i;
-        if nums.contains(&j) {
-            return Some(i * j);
-        }
-    }
-    None
+fn part1(nums: &[i32]) -> Option<i32> {
+    product_summing_to(nums, 2, 2020)
 }
 
-fn part2(nums: &HashSet<i32>) -> Option<i32> {
-    for (i, j) in nums.iter().cartesian_product(nums.iter()) {
-        let k = 2020 - i - j;
-        if nums.contains(&k) {
-            return Some(i * j * k);
-        }
-    }
-    None
+fn part2(nums: &[i32]) -> Option<i32> {
+    product_summing_to(nums, 3, 2020)
+}
+
+///
+/// The product of the first `k` entries that add up to `total`
+///
+fn product_summing_to(nums: &[i32], k: usize, total: i32) -> Option<i32> {
+    combinatorics::combinations(nums, k)
+        .find(|entries| entries.iter().sum::<i32>() == total)
+        .map(|entries| entries.iter().product())
 }
 
 #[cfg(test)]
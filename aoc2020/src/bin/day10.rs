@@ -143,28 +143,67 @@
 //
 // Your puzzle answer was 16198260678656.
 
-use std::collections::{HashMap, VecDeque};
-use std::fs;
+use std::collections::VecDeque;
+use std::fmt::Display;
 
-use anyhow::{Context, Result};
-use itertools::Itertools;
+use anyhow::Result;
+
+use aoc::error::{self, AocError};
+use aoc::memo;
+use aoc::solution::{Entry, Solution};
+
+struct Day10;
+
+pub const SOLUTION: Entry = Entry::new::<Day10>();
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 10;
+
+    type Input = VecDeque<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        let (d1, _, d3) = num_diffs(nums);
+        Ok(d1 * d3)
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        Ok(count_ways(nums))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day10")?;
-    let nums = parse(&input)?;
-    let (d1, _, d3) = num_diffs(&nums);
-    println!("{}", d1 * d3);
-    println!("{}", count_ways(&nums));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn parse(s: &str) -> Result<VecDeque<u32>> {
-    s.lines()
+    let nums = s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<u32>()
-            .context("unable to parse num"))
-        .try_collect()
+        .map(|line| error::parse_at(s, line))
+        .collect::<Result<_, AocError>>()?;
+    check_gaps(&nums)?;
+    Ok(nums)
+}
+
+///
+/// Check the adapters chain from the outlet to the device, each 1 to 3 jolts above the last
+///
+fn check_gaps(nums: &VecDeque<u32>) -> Result<(), AocError> {
+    let mut nums = nums.clone();
+    pad_nums(&mut nums);
+    for w in nums.make_contiguous().windows(2) {
+        match w[1] - w[0] {
+            0 => return Err(AocError::invalid(format!("adapter {} appears twice", w[0]))),
+            1..=3 => (),
+            _ => return Err(AocError::invalid(format!("adapters {} and {} are more than 3 jolts apart", w[0], w[1]))),
+        }
+    }
+    Ok(())
 }
 
 fn pad_nums(nums: &mut VecDeque<u32>) {
@@ -183,7 +222,7 @@ fn num_diffs(nums: &VecDeque<u32>) -> (u32, u32, u32) {
         .windows(2)
         .map(|w| w[1] - w[0])
     {
-        assert!(diff >= 1 && diff <= 3);
+        // 1 to 3, as checked by parse
         diffs[diff as usize] += 1;
     }
     (diffs[1], diffs[2], diffs[3])
@@ -192,32 +231,20 @@ fn num_diffs(nums: &VecDeque<u32>) -> (u32, u32, u32) {
 fn count_ways(nums: &VecDeque<u32>) -> u64 {
     let mut nums = nums.clone();
     pad_nums(&mut nums);
-    nums.make_contiguous();
-    return count_ways_rec(&nums, 0, &mut HashMap::new());
-
-    fn count_ways_rec(nums: &VecDeque<u32>,
-                      i: usize,
-                      counts: &mut HashMap<usize, u64>) -> u64 {
-        if let Some(count) = counts.get(&i) {
-            return *count;
-        }
-        if i >= nums.len() - 1 {
-            return 1;
-        }
+    let nums = nums.make_contiguous();
 
-        let num = nums[i];
-        let mut count = 0;
-        for (j, next_num) in nums.as_slices().0[i + 1..].iter()
-            .take(3)
-            .enumerate()
-        {
-            if next_num - num <= 3 {
-                count += count_ways_rec(nums, i + 1 + j, counts);
-            }
+    // the ways to reach each adapter from the outlet, through one of the three before it
+    let ways = memo::table(nums.len(), |i, ways: &[u64]| {
+        if i == 0 {
+            1
+        } else {
+            (i.saturating_sub(3)..i)
+                .filter(|&j| nums[i] - nums[j] <= 3)
+                .map(|j| ways[j])
+                .sum()
         }
-        counts.insert(i, count);
-        return count;
-    }
+    });
+    ways[ways.len() - 1]
 }
 
 #[cfg(test)]
@@ -281,6 +308,10 @@ mod tests {
         let nums = parse(s)?;
         assert_matches!(num_diffs(&nums), (22, _, 10));
         assert_eq!(count_ways(&nums), 19208);
+
+        assert!(parse("99").is_err());
+        assert!(parse("1\n4\n8").is_err());
+        assert!(parse("1\n2\n2").is_err());
         Ok(())
     }
 }
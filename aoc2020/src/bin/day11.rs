@@ -212,26 +212,69 @@
 //
 // Your puzzle answer was 1978.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Either;
 
-use crate::chargrid::Grid;
-use crate::geom::{Direction, Vector2};
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::render::{Animation, Colour, Palette};
+use aoc::solution::{Entry, Solution};
+
+use self::chargrid::Grid;
+
+struct Day11;
+
+pub const SOLUTION: Entry = Entry::new::<Day11>();
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 11;
+
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        for line in input.lines().map(str::trim) {
+            if let Some(i) = line.find(|c| !matches!(c, 'L' | '#' | '.')) {
+                return Err(AocError::parse(input, &line[i..], "expected L, # or .").into());
+            }
+        }
+        Grid::parse(input)
+    }
+
+    fn part1(seats: &Self::Input) -> Result<impl Display> {
+        let mut anim = animation()?;
+        let res = num_occupied(&evolve(seats, true, &mut anim));
+        anim.finish()?;
+        Ok(res)
+    }
+
+    fn part2(seats: &Self::Input) -> Result<impl Display> {
+        let mut anim = animation()?;
+        let res = num_occupied(&evolve(seats, false, &mut anim));
+        anim.finish()?;
+        Ok(res)
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day11")?;
-    let seats = Grid::parse(&input)?;
-    println!("{}", num_occupied(&evolve(&seats, true)));
-    println!("{}", num_occupied(&evolve(&seats, false)));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn evolve(seats: &Grid, directly_adj: bool) -> Grid {
+fn animation() -> Result<Animation> {
+    let palette = Palette::new()
+        .with('L', Colour::Green)
+        .with('#', Colour::Red)
+        .with('.', Colour::Grey);
+    Ok(Animation::from_env()?.with_palette(palette))
+}
+
+fn evolve(seats: &Grid, directly_adj: bool, anim: &mut Animation) -> Grid {
     let mut seats = seats.clone();
     let mut changed = true;
     while changed {
+        anim.frame(|| &seats);
         changed = false;
         let mut new_seats = seats.clone();
         for (y, x, &c) in seats.iter() {
@@ -258,11 +301,11 @@ fn count_visibly_occupied(seats: &Grid,
     Direction::VALUES_8D.iter()
         .map(|dir| {
             let iter = std::iter::successors(
-                Some(Vector2::new(x, y)), move |pos| {
+                Some(Vector2::new([x as i64, y as i64])), move |pos| {
                     let pos = *pos + dir.dxdy();
-                    if pos.y < 0 || pos.x < 0 ||
-                        pos.y as usize >= seats.num_rows ||
-                        pos.x as usize >= seats.num_cols {
+                    if pos.y() < 0 || pos.x() < 0 ||
+                        pos.y() as usize >= seats.num_rows ||
+                        pos.x() as usize >= seats.num_cols {
                         None
                     } else {
                         Some(pos)
@@ -290,7 +333,7 @@ fn num_occupied(seats: &Grid) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use crate::chargrid::Grid;
+    use super::chargrid::Grid;
 
     use super::*;
 
@@ -309,8 +352,9 @@ mod tests {
         L.LLLLL.LL
         ";
         let seats = Grid::parse(s)?;
-        assert_eq!(num_occupied(&evolve(&seats, true)), 37);
-        assert_eq!(num_occupied(&evolve(&seats, false)), 26);
+        let mut anim = Animation::disabled();
+        assert_eq!(num_occupied(&evolve(&seats, true, &mut anim)), 37);
+        assert_eq!(num_occupied(&evolve(&seats, false, &mut anim)), 26);
 
         Ok(())
     }
@@ -324,7 +368,8 @@ mod chargrid {
     use anyhow::Result;
     use itertools::Itertools;
 
-    use crate::geom::Vector2;
+    use aoc::error::AocError;
+    use aoc::geom::Vector2;
 
     #[derive(Clone, Hash, Eq, PartialEq, Debug)]
     pub struct Grid {
@@ -335,20 +380,20 @@ mod chargrid {
 
     impl Grid {
         pub fn parse(s: &str) -> Result<Grid> {
-            let grid = s.lines()
+            let lines = s.lines()
                 .map(|line| line.trim())
                 .filter(|line| !line.is_empty())
-                .map(|line| line.as_bytes().to_vec())
                 .collect_vec();
-            if grid.is_empty() {
-                anyhow::bail!("empty grid");
+            if lines.is_empty() {
+                return Err(AocError::invalid("empty grid").into());
             }
-            let num_rows = grid.len();
-            let num_cols = grid[0].len();
+            let num_rows = lines.len();
+            let num_cols = lines[0].len();
 
-            if grid.iter().any(|row| row.len() != num_cols) {
-                anyhow::bail!("jagged grid");
+            if let Some(line) = lines.iter().find(|line| line.len() != num_cols) {
+                return Err(AocError::parse(s, line, format!("expected {} columns like the first row", num_cols)).into());
             }
+            let grid = lines.iter().map(|line| line.as_bytes().to_vec()).collect();
             Ok(Grid { grid, num_rows, num_cols })
         }
 
@@ -375,16 +420,16 @@ mod chargrid {
         type Output = u8;
 
         fn index(&self, index: Vector2) -> &Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &self.grid[y][x]
         }
     }
 
     impl IndexMut<Vector2> for Grid {
         fn index_mut(&mut self, index: Vector2) -> &mut Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &mut self.grid[y][x]
         }
     }
@@ -403,148 +448,3 @@ mod chargrid {
         }
     }
 }
-
-mod geom {
-    use std::convert::{TryFrom, TryInto};
-    use std::ops::{Add, AddAssign, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        N,
-        NE,
-        E,
-        SE,
-        S,
-        SW,
-        W,
-        NW,
-    }
-
-    impl Direction {
-        #[allow(dead_code)]
-        pub const VALUES_4D: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        #[allow(dead_code)]
-        pub const VALUES_8D: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-
-        #[allow(dead_code)]
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(1, 1),
-                Direction::S => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(-1, -1),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self, num_turns: isize) -> Direction {
-            let dir = ((*self as isize) + num_turns) % 8;
-            dir.try_into().unwrap()
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self, num_turns: isize) -> Direction {
-            let mut dir = ((*self as isize) - num_turns) % 8;
-            if dir < 0 { dir += 8; }
-            dir.try_into().unwrap()
-        }
-    }
-
-    impl TryFrom<isize> for Direction {
-        type Error = ();
-
-        fn try_from(i: isize) -> Result<Self, Self::Error> {
-            match i {
-                x if x == Direction::N as isize => Ok(Direction::N),
-                x if x == Direction::NE as isize => Ok(Direction::NE),
-                x if x == Direction::E as isize => Ok(Direction::E),
-                x if x == Direction::SE as isize => Ok(Direction::SE),
-                x if x == Direction::S as isize => Ok(Direction::S),
-                x if x == Direction::SW as isize => Ok(Direction::SW),
-                x if x == Direction::W as isize => Ok(Direction::W),
-                x if x == Direction::NW as isize => Ok(Direction::NW),
-                _ => Err(()),
-            }
-        }
-    }
-}
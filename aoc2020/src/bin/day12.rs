@@ -69,34 +69,56 @@
 //
 // Your puzzle answer was 48739.
 
-use std::fs;
+use std::fmt::Display;
 use std::str::FromStr;
 
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::geom::{Direction, Vector2};
+use aoc::error::{self, AocError};
+use aoc::geom::{Direction, Vector2};
+use aoc::solution::{Entry, Solution};
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day12")?;
-    let moves = parse(&input)?;
-    let mut ship = Ship::default();
-    ship.navigate(&moves);
-    println!("{:?}", ship.pos.manhattan_distance(Vector2::ZERO));
 
-    let mut waypoint = Vector2::new(10, -1);
-    let mut ship = Ship::default();
-    ship.navigate2(&moves, &mut waypoint);
-    println!("{:?}", ship.pos.manhattan_distance(Vector2::ZERO));
-    Ok(())
+struct Day12;
+
+pub const SOLUTION: Entry = Entry::new::<Day12>();
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 12;
+
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(moves: &Self::Input) -> Result<impl Display> {
+        let mut ship = Ship::default();
+        ship.navigate(moves);
+        Ok(ship.pos.manhattan_distance(Vector2::zero()))
+    }
+
+    fn part2(moves: &Self::Input) -> Result<impl Display> {
+        let waypoint = Vector2::new([10, -1]);
+        let mut ship = Ship::default();
+        ship.navigate2(moves, &waypoint);
+        Ok(ship.pos.manhattan_distance(Vector2::zero()))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn parse(s: &str) -> Result<Vec<Move>> {
     s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<Move>())
-        .try_collect()
+        .map(|line| line.parse::<Move>().map_err(|e| e.within(s, line)))
+        .try_collect::<_, _, AocError>()
+        .map_err(Into::into)
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -127,9 +149,9 @@ impl Ship {
             if angle < 0 { angle += 360; }
             match angle {
                 0 => pt,
-                90 => Vector2::new(pt.y, -pt.x),
-                180 => Vector2::new(-pt.x, -pt.y),
-                270 => Vector2::new(-pt.y, pt.x),
+                90 => Vector2::new([pt.y(), -pt.x()]),
+                180 => Vector2::new([-pt.x(), -pt.y()]),
+                270 => Vector2::new([-pt.y(), pt.x()]),
                 _ => panic!("invalid angle"),
             }
         }
@@ -152,7 +174,7 @@ impl Ship {
 
 impl Default for Ship {
     fn default() -> Self {
-        Ship { pos: Vector2::ZERO, facing: Direction::E }
+        Ship { pos: Vector2::zero(), facing: Direction::E }
     }
 }
 
@@ -163,12 +185,17 @@ struct Move {
 }
 
 impl FromStr for Move {
-    type Err = anyhow::Error;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (first, rest) = s.split_at(1);
-        let mov = first.as_bytes()[0];
-        let arg = rest.parse::<i64>()?;
+        let mov = match s.bytes().next() {
+            Some(mov @ (b'N' | b'S' | b'E' | b'W' | b'L' | b'R' | b'F')) => mov,
+            _ => return Err(AocError::parse(s, s, "expected an action of N, S, E, W, L, R or F")),
+        };
+        let arg = error::parse_at(s, &s[1..])?;
+        if (mov == b'L' || mov == b'R') && arg % 90 != 0 {
+            return Err(AocError::parse(s, &s[1..], "expected a multiple of 90 degrees"));
+        }
         Ok(Move { mov, arg })
     }
 }
@@ -189,172 +216,12 @@ mod tests {
         let moves = parse(s)?;
         let mut ship = Ship::default();
         ship.navigate(&moves);
-        assert_eq!(25, ship.pos.manhattan_distance(Vector2::ZERO));
+        assert_eq!(25, ship.pos.manhattan_distance(Vector2::zero()));
 
         let mut ship = Ship::default();
-        let mut waypoint = Vector2::new(10, -1);
+        let mut waypoint = Vector2::new([10, -1]);
         ship.navigate2(&moves, &mut waypoint);
-        assert_eq!(286, ship.pos.manhattan_distance(Vector2::ZERO));
+        assert_eq!(286, ship.pos.manhattan_distance(Vector2::zero()));
         Ok(())
     }
 }
-
-mod geom {
-    use std::convert::{TryFrom, TryInto};
-    use std::ops::{Add, AddAssign, Mul, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        N,
-        NE,
-        E,
-        SE,
-        S,
-        SW,
-        W,
-        NW,
-    }
-
-    impl Direction {
-        #[allow(dead_code)]
-        pub const VALUES_4D: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        #[allow(dead_code)]
-        pub const VALUES_8D: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-
-        #[allow(dead_code)]
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(1, 1),
-                Direction::S => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(-1, -1),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self, num_turns: isize) -> Direction {
-            let dir = ((*self as isize) + num_turns) % 8;
-            dir.try_into().unwrap()
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self, num_turns: isize) -> Direction {
-            let mut dir = ((*self as isize) - num_turns) % 8;
-            if dir < 0 { dir += 8; }
-            dir.try_into().unwrap()
-        }
-    }
-
-    impl TryFrom<isize> for Direction {
-        type Error = ();
-
-        fn try_from(i: isize) -> Result<Self, Self::Error> {
-            match i {
-                x if x == Direction::N as isize => Ok(Direction::N),
-                x if x == Direction::NE as isize => Ok(Direction::NE),
-                x if x == Direction::E as isize => Ok(Direction::E),
-                x if x == Direction::SE as isize => Ok(Direction::SE),
-                x if x == Direction::S as isize => Ok(Direction::S),
-                x if x == Direction::SW as isize => Ok(Direction::SW),
-                x if x == Direction::W as isize => Ok(Direction::W),
-                x if x == Direction::NW as isize => Ok(Direction::NW),
-                _ => Err(()),
-            }
-        }
-    }
-}
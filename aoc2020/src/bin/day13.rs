@@ -116,36 +116,60 @@
 //
 // Your puzzle answer was 500033211739354.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
+
+use aoc::error::{self, AocError};
+use aoc::numtheory;
+use aoc::solution::{Entry, Solution};
+
+struct Day13;
+
+pub const SOLUTION: Entry = Entry::new::<Day13>();
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 13;
+
+    type Input = (i128, Vec<(usize, i128)>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((ts, schedule): &Self::Input) -> Result<impl Display> {
+        let (bus, wait) = earliest(*ts, schedule);
+        Ok(bus * wait)
+    }
+
+    fn part2((_, schedule): &Self::Input) -> Result<impl Display> {
+        earliest_offset(schedule)
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day13")?;
-    let (ts, schedule) = parse(&input);
-    let (bus, wait) = earliest(ts, &schedule);
-    println!("{}", bus * wait);
-    let earliest_offset = earliest_offset(&schedule);
-    println!("{}", earliest_offset);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (i128, Vec<(usize, i128)>) {
+fn parse(s: &str) -> Result<(i128, Vec<(usize, i128)>)> {
     let mut lines = s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty());
+    let mut next = || lines.next().ok_or_else(|| AocError::parse_offset(s, s.len(), "expected a timestamp and a schedule"));
 
-    let ts = lines.next().unwrap()
-        .parse::<i128>().unwrap();
+    let ts = error::parse_at(s, next()?)?;
 
-    let schedule = lines.next().unwrap()
+    let schedule = next()?
         .split(',')
         .enumerate()
-        .filter(|(_, s)| *s != "x")
-        .map(|(i, s)| (i, s.parse::<i128>().unwrap()))
-        .collect_vec();
-    (ts, schedule)
+        .filter(|(_, id)| *id != "x")
+        .map(|(i, id)| match error::parse_at(s, id)? {
+            n if n > 0 => Ok((i, n)),
+            _ => Err(AocError::parse(s, id, "expected a positive bus ID")),
+        })
+        .collect::<Result<_, AocError>>()?;
+    Ok((ts, schedule))
 }
 
 fn earliest(ts: i128, schedule: &Vec<(usize, i128)>) -> (i128, i128) {
@@ -155,34 +179,12 @@ fn earliest(ts: i128, schedule: &Vec<(usize, i128)>) -> (i128, i128) {
         .unwrap()
 }
 
-// k = ax + offset_a
-// k = by + offset_b
-// => ax + offset_a = by + offset_b
-// e.g. k = 7x + 0, k = 13y + 12
-// k = 77, x = 11, y = 5
-//
-// (extended euclidean):
-// 7x + 13y = 1
-// 7(2) + 13(-1) = 1
-// 7(2)(12) + 13(-1)(12) = 12
-//
-// k = 7x + 0 = 168
-// lcm(7, 13) = 91
-// solns = 168 + 91x = 77 + 91x
-fn earliest_offset(schedule: &Vec<(usize, i128)>) -> i128 {
-    let (offset_a, mut a) = *schedule.first().unwrap();
-    let mut offset_a = offset_a as i128;
-    for &(offset_b, b) in schedule[1..].iter() {
-        let offset_b = b - offset_b as i128;
-        let m = offset_b - offset_a;
-        let lcm = lcm(a, b);
-        let (arg0, arg1, ofs) = if m < 0 { (b, a, offset_b) } else { (a, b, offset_a) };
-        let inv = mod_inv(arg0 as i128, arg1 as i128);
-        let orig = (arg0 * inv * m.abs()) + ofs;
-        offset_a = orig % lcm;
-        a = lcm;
-    }
-    offset_a
+// bus b leaves at t + offset, so t = -offset (mod b)
+fn earliest_offset(schedule: &Vec<(usize, i128)>) -> Result<i128> {
+    let congruences = schedule.iter().map(|&(offset, bus)| (-(offset as i128), bus));
+    let (t, _) = numtheory::crt(congruences)
+        .ok_or_else(|| AocError::no_solution("the buses never leave at their offsets"))?;
+    Ok(t)
 }
 
 #[cfg(test)]
@@ -195,50 +197,9 @@ mod tests {
         939
         7,13,x,x,59,x,31,19
         ";
-        let (ts, schedule) = parse(s);
+        let (ts, schedule) = parse(s)?;
         assert_eq!((59, 5), earliest(ts, &schedule));
-        assert_eq!(1068781, earliest_offset(&schedule));
+        assert_eq!(1068781, earliest_offset(&schedule)?);
         Ok(())
     }
 }
-
-fn lcm(a: i128, b: i128) -> i128 {
-    (a * b) / gcd(a, b)
-}
-
-fn gcd(mut a: i128, mut b: i128) -> i128 {
-    let mut t;
-    while b != 0 {
-        t = b;
-        b = a % b;
-        a = t;
-    }
-    a
-}
-
-fn mod_inv(mut a: i128, mut m: i128) -> i128 {
-    assert_eq!(1, gcd(a.abs(), m.abs()));
-    let orig_m = m;
-    let mut y = 0;
-    let mut x = 1;
-
-    if m == 1 {
-        return 0;
-    }
-    while a > 1 {
-        let q = a / m;
-        let r = a % m;
-
-        a = m;
-        m = r;
-
-        let ny = x - (q * y);
-        x = y;
-        y = ny;
-    }
-
-    if x < 0 {
-        x += orig_m;
-    }
-    return x;
-}
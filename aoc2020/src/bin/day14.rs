@@ -100,41 +100,65 @@
 // Your puzzle answer was 3369767240513.
 
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
+
+struct Day14;
+
+pub const SOLUTION: Entry = Entry::new::<Day14>();
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 14;
+
+    type Input = Vec<Op>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(ops: &Self::Input) -> Result<impl Display> {
+        Ok(exec1(ops))
+    }
+
+    fn part2(ops: &Self::Input) -> Result<impl Display> {
+        Ok(exec2(ops))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day14")?;
-    let ops = parse(&input);
-    println!("{}", exec1(&ops));
-    println!("{}", exec2(&ops));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<Op> {
+fn parse(s: &str) -> Result<Vec<Op>> {
     lazy_static::lazy_static! {
-        static ref RE: Regex = Regex::new(r"^mem\[(\d+)\]$").unwrap();
+        static ref MASK_RE: Regex = Regex::new(r"^mask = ([01X]{36})$").unwrap();
+        static ref MEM_RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
     }
-    s.lines()
+    let mut ops = vec![];
+    for line in s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| {
-            let mut iter = line.split("=");
-            let lhs = iter.next().unwrap().trim();
-            let rhs = iter.next().unwrap().trim();
-            if lhs.starts_with("mask") {
-                Op::Mask(rhs.to_string())
-            } else {
-                let rhs = rhs.parse::<i64>().unwrap();
-                let lhs = &RE.captures(lhs).unwrap()[1];
-                let lhs = lhs.parse::<i64>().unwrap();
-                Op::Mem(lhs, rhs)
+    {
+        let op = if let Some(cap) = MASK_RE.captures(line) {
+            Op::Mask(cap[1].to_string())
+        } else if let Some(cap) = MEM_RE.captures(line) {
+            if ops.is_empty() {
+                return Err(AocError::parse(s, line, "expected a mask before the first write").into());
             }
-        })
-        .collect_vec()
+            Op::Mem(error::parse_at(s, &cap[1])?, error::parse_at(s, &cap[2])?)
+        } else {
+            return Err(AocError::parse(s, line, "expected mask = <36 of 0, 1 or X> or mem[a] = v").into());
+        };
+        ops.push(op);
+    }
+    Ok(ops)
 }
 
 fn apply_mask(mask: &str, val: i64) -> i64 {
@@ -216,7 +240,7 @@ mod tests {
         mem[7] = 101
         mem[8] = 0
         ";
-        let ops = parse(s);
+        let ops = parse(s)?;
         assert_eq!(165, exec1(&ops));
 
         let s = r"
@@ -225,7 +249,7 @@ mod tests {
         mask = 00000000000000000000000000000000X0XX
         mem[26] = 1
         ";
-        let ops = parse(s);
+        let ops = parse(s)?;
         assert_eq!(208, exec2(&ops));
         Ok(())
     }
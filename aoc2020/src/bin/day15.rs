@@ -59,21 +59,47 @@
 // Your puzzle answer was 16671510.
 
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
+
+struct Day15;
+
+pub const SOLUTION: Entry = Entry::new::<Day15>();
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 15;
+
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        Ok(count(nums, 2020 - 1))
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        Ok(count(nums, 30000000 - 1))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day15")?;
-    println!("{}", count(&input, 2020 - 1));
-    println!("{}", count(&input, 30000000 - 1));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
+}
+
+fn parse(s: &str) -> Result<Vec<i64>> {
+    Ok(parse::ints(s)?)
 }
 
-fn count(s: &str, n: usize) -> i64 {
-    let nums = s.trim().split(",")
-        .map(|i| i.parse::<i64>().unwrap())
+fn count(nums: &[i64], n: usize) -> i64 {
+    let nums = nums.iter().copied()
         .enumerate()
         .collect_vec();
     if n < nums.len() {
@@ -104,7 +130,7 @@ mod tests {
         let s = r"
         0,3,6
         ";
-        assert_eq!(436, count(s, 2020 - 1));
+        assert_eq!(436, count(&parse(s)?, 2020 - 1));
         Ok(())
     }
 }
@@ -70,77 +70,113 @@
 // Your puzzle answer was 3429967441937.
 
 use std::collections::HashMap;
-use std::fs;
-use std::ops::Range;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::interval::{Interval, IntervalSet};
+use aoc::solution::{Entry, Solution};
+
+struct Day16;
+
+pub const SOLUTION: Entry = Entry::new::<Day16>();
+
+impl Solution for Day16 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 16;
+
+    type Input = (HashMap<String, IntervalSet<u32>>, Vec<u32>, Vec<Vec<u32>>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((rules, _, all_tickets): &Self::Input) -> Result<impl Display> {
+        Ok(scanning_error_rate(rules, &mut all_tickets.clone()))
+    }
+
+    fn part2((rules, my_ticket, all_tickets): &Self::Input) -> Result<impl Display> {
+        let mut all_tickets = all_tickets.clone();
+        scanning_error_rate(rules, &mut all_tickets);
+        all_tickets.push(my_ticket.clone());
+        let fields = solve_fields(rules, &all_tickets);
+        let product = fields.iter().enumerate()
+            .filter(|(_, v)| v.starts_with("departure"))
+            .map(|(i, _)| my_ticket[i] as u64)
+            .product::<u64>();
+        Ok(product)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day16")?;
-    let (rules, my_ticket, mut all_tickets) = parse(&input);
-    println!("{}", scanning_error_rate(&rules, &mut all_tickets));
-
-    all_tickets.push(my_ticket.clone());
-    let fields = solve_fields(&rules, &all_tickets);
-    let product = fields.iter().enumerate()
-        .filter(|(_, v)| v.starts_with("departure"))
-        .map(|(i, _)| my_ticket[i] as u64)
-        .product::<u64>();
-    println!("{}", product);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (HashMap<String, (Range<u32>, Range<u32>)>, Vec<u32>, Vec<Vec<u32>>) {
+fn parse(s: &str) -> Result<(HashMap<String, IntervalSet<u32>>, Vec<u32>, Vec<Vec<u32>>)> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^([^:]+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
     }
 
     let mut sections = s.trim().split("\n\n");
-    let rules = sections.next().unwrap();
-    let rules = rules.lines()
+    let mut next_section = |heading: &str| {
+        let section = sections.next()
+            .ok_or_else(|| AocError::parse_offset(s, s.len(), format!("expected a section {:?}", heading)))?;
+        let mut lines = section.lines().map(|line| line.trim());
+        match heading {
+            "" => Ok(lines),
+            _ => match lines.next() {
+                Some(line) if line == heading => Ok(lines),
+                _ => Err(AocError::parse(s, section, format!("expected {:?}", heading))),
+            }
+        }
+    };
+
+    let rules = next_section("")?
         .map(|line| {
-            let caps = RE.captures(line.trim()).unwrap();
+            let caps = RE.captures(line)
+                .ok_or_else(|| AocError::parse(s, line, "expected a rule such as class: 1-3 or 5-7"))?;
             let category = caps[1].to_string();
-            let from1 = caps[2].parse::<u32>().unwrap();
-            let to1 = caps[3].parse::<u32>().unwrap();
-            let from2 = caps[4].parse::<u32>().unwrap();
-            let to2 = caps[5].parse::<u32>().unwrap();
-            let range1 = from1..to1 + 1;
-            let range2 = from2..to2 + 1;
-            (category, (range1, range2))
+            let from1 = error::parse_at(s, &caps[2])?;
+            let to1 = error::parse_at(s, &caps[3])?;
+            let from2 = error::parse_at(s, &caps[4])?;
+            let to2 = error::parse_at(s, &caps[5])?;
+            let ranges = vec![Interval::inclusive(from1, to1), Interval::inclusive(from2, to2)];
+            Ok((category, ranges.into_iter().collect()))
         })
-        .collect::<HashMap<_, _>>();
+        .collect::<Result<HashMap<_, _>, AocError>>()?;
 
-    fn parse_ticket(line: &str) -> Vec<u32> {
-        line.trim().split(',')
-            .map(|s| s.parse::<u32>().unwrap())
-            .collect_vec()
-    }
+    let parse_ticket = |line: &str| -> Result<Vec<u32>, AocError> {
+        let ticket = line.split(',')
+            .map(|field| error::parse_at(s, field))
+            .collect::<Result<Vec<_>, _>>()?;
+        match ticket.len() == rules.len() {
+            true => Ok(ticket),
+            false => Err(AocError::parse(s, line, format!("expected {} fields, one per rule", rules.len()))),
+        }
+    };
 
-    let mut my_ticket = sections.next().unwrap().lines();
-    assert_eq!("your ticket:", my_ticket.next().unwrap().trim());
-    let my_ticket = parse_ticket(my_ticket.next().unwrap());
+    let my_ticket = next_section("your ticket:")?
+        .next()
+        .ok_or_else(|| AocError::parse_offset(s, s.len(), "expected your ticket"))?;
+    let my_ticket = parse_ticket(my_ticket)?;
 
-    let mut all_tickets = sections.next().unwrap().lines();
-    assert_eq!("nearby tickets:", all_tickets.next().unwrap().trim());
-    let all_tickets = all_tickets.map(parse_ticket).collect_vec();
+    let all_tickets = next_section("nearby tickets:")?
+        .map(parse_ticket)
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (rules, my_ticket, all_tickets)
+    Ok((rules, my_ticket, all_tickets))
 }
 
-fn scanning_error_rate(rules: &HashMap<String, (Range<u32>, Range<u32>)>,
+fn scanning_error_rate(rules: &HashMap<String, IntervalSet<u32>>,
                        tickets: &mut Vec<Vec<u32>>) -> u32 {
     let ranges = rules.values()
-        .fold(Vec::with_capacity(rules.len() * 2), |mut vec, ranges| {
-            vec.push(ranges.0.clone());
-            vec.push(ranges.1.clone());
-            vec
-        });
+        .fold(IntervalSet::new(), |acc, ranges| acc.union(ranges));
 
     let is_valid_field = |field| -> bool {
-        ranges.iter().any(|range| range.contains(&field))
+        ranges.contains(field)
     };
 
     let sum = tickets.iter()
@@ -153,14 +189,14 @@ fn scanning_error_rate(rules: &HashMap<String, (Range<u32>, Range<u32>)>,
     sum
 }
 
-fn solve_fields<'a>(rules: &'a HashMap<String, (Range<u32>, Range<u32>)>,
+fn solve_fields<'a>(rules: &'a HashMap<String, IntervalSet<u32>>,
                     tickets: &Vec<Vec<u32>>) -> Vec<&'a String> {
     let possible_cats = |i| {
         rules.iter()
-            .filter(|(_, (r1, r2))|
+            .filter(|(_, ranges)|
                 tickets.iter()
-                    .map(|fields| &fields[i])
-                    .all(|v| { r1.contains(v) || r2.contains(v) }))
+                    .map(|fields| fields[i])
+                    .all(|v| ranges.contains(v)))
             .map(|(cat, _)| cat)
             .collect_vec()
     };
@@ -210,7 +246,7 @@ mod tests {
         55,2,20
         38,6,12
         ";
-        let (rules, _, mut all_tickets) = parse(s);
+        let (rules, _, mut all_tickets) = parse(s)?;
         assert_eq!(71, scanning_error_rate(&rules, &mut all_tickets));
 
         let s = r"
@@ -226,7 +262,7 @@ mod tests {
         15,1,5
         5,14,9
         ";
-        let (rules, my_ticket, mut all_tickets) = parse(s);
+        let (rules, my_ticket, mut all_tickets) = parse(s)?;
         all_tickets.push(my_ticket.clone());
         let fields = solve_fields(&rules, &all_tickets);
         assert_eq!(vec!["row", "class", "seat"], fields);
@@ -401,50 +401,78 @@
 // Your puzzle answer was 2440.
 
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day17")?;
-    let pts = parse(&input);
-    let mut pts3d = to_3d(&pts);
-    for _ in 0..6 {
-        evolve(&mut pts3d);
+use aoc::error::AocError;
+use aoc::geom::Vector;
+use aoc::solution::{Entry, Solution};
+
+struct Day17;
+
+pub const SOLUTION: Entry = Entry::new::<Day17>();
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 17;
+
+    type Input = HashSet<(usize, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(pts: &Self::Input) -> Result<impl Display> {
+        let mut pts3d = embed::<3>(pts);
+        for _ in 0..6 {
+            evolve(&mut pts3d);
+        }
+        Ok(pts3d.len())
     }
-    println!("{}", pts3d.len());
 
-    let mut pts4d = to_4d(&pts);
-    for _ in 0..6 {
-        evolve(&mut pts4d);
+    fn part2(pts: &Self::Input) -> Result<impl Display> {
+        let mut pts4d = embed::<4>(pts);
+        for _ in 0..6 {
+            evolve(&mut pts4d);
+        }
+        Ok(pts4d.len())
     }
-    println!("{}", pts4d.len());
-    Ok(())
 }
 
-fn parse(s: &str) -> HashSet<(usize, usize)> {
-    s.lines()
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
+}
+
+fn parse(s: &str) -> Result<HashSet<(usize, usize)>> {
+    for line in s.lines().map(str::trim) {
+        if let Some(i) = line.find(|c| c != '#' && c != '.') {
+            return Err(AocError::parse(s, &line[i..], "expected # or .").into());
+        }
+    }
+    Ok(s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
         .enumerate()
         .flat_map(|(y, row)|
             row.bytes().enumerate()
                 .filter_map(move |(x, c)| if c == b'#' { Some((x, y)) } else { None }))
-        .collect::<HashSet<_>>()
-}
-
-fn to_3d(pts: &HashSet<(usize, usize)>) -> HashSet<Vec<i32>> {
-    pts.iter().map(|&(x, y)| vec![x as i32, y as i32, 0]).collect()
+        .collect::<HashSet<_>>())
 }
 
-fn to_4d(pts: &HashSet<(usize, usize)>) -> HashSet<Vec<i32>> {
-    pts.iter().map(|&(x, y)| vec![x as i32, y as i32, 0, 0]).collect()
+fn embed<const N: usize>(pts: &HashSet<(usize, usize)>) -> HashSet<Vector<i32, N>> {
+    pts.iter().map(|&(x, y)| {
+        let mut pt = Vector::zero();
+        pt[0] = x as i32;
+        pt[1] = y as i32;
+        pt
+    }).collect()
 }
 
-fn evolve(pts: &mut HashSet<Vec<i32>>) {
+fn evolve<const N: usize>(pts: &mut HashSet<Vector<i32, N>>) {
     let mut num_neighbours = HashMap::new();
     for pt in pts.iter() {
-        for npt in neighbours(pt) {
+        for npt in pt.neighbours() {
             *num_neighbours.entry(npt).or_insert(0) += 1;
         }
     }
@@ -464,31 +492,6 @@ fn evolve(pts: &mut HashSet<Vec<i32>>) {
     pts.extend(iter);
 }
 
-fn neighbours(pt: &Vec<i32>) -> Vec<Vec<i32>> {
-    let mut res = vec![vec![]];
-    let mut tmp_res = Vec::new();
-    for &dim in pt.iter() {
-        for x in res.drain(..) {
-            let mut x1 = x.clone();
-            let mut x2 = x.clone();
-            let mut x3 = x;
-
-            x1.push(dim - 1);
-            x2.push(dim);
-            x3.push(dim + 1);
-
-            tmp_res.push(x1);
-            tmp_res.push(x2);
-            tmp_res.push(x3);
-        }
-        let t = res;
-        res = tmp_res;
-        tmp_res = t;
-    }
-    res.retain(|v| v != pt);
-    res
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -500,14 +503,14 @@ mod tests {
         ..#
         ###
         ";
-        let pts = parse(s);
-        let mut pts3d = to_3d(&pts);
+        let pts = parse(s)?;
+        let mut pts3d = embed::<3>(&pts);
         for _ in 0..6 {
             evolve(&mut pts3d);
         }
         assert_eq!(112, pts3d.len());
 
-        let mut pts4d = to_4d(&pts);
+        let mut pts4d = embed::<4>(&pts);
         for _ in 0..6 {
             evolve(&mut pts4d);
         }
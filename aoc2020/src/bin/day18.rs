@@ -65,30 +65,76 @@
 //
 // Your puzzle answer was 241216538527890.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
+struct Day18;
+
+pub const SOLUTION: Entry = Entry::new::<Day18>();
+
+impl Solution for Day18 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 18;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(exprs: &Self::Input) -> Result<impl Display> {
+        Ok(exprs.iter().map(|expr| eval(expr, false)).sum::<u64>())
+    }
+
+    fn part2(exprs: &Self::Input) -> Result<impl Display> {
+        Ok(exprs.iter().map(|expr| eval(expr, true)).sum::<u64>())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day18")?;
-    let exprs = parse(&input);
-    let sum = exprs.iter().map(|expr| eval(expr, false)).sum::<u64>();
-    println!("{}", sum);
-    let sum = exprs.iter().map(|expr| eval(expr, true)).sum::<u64>();
-    println!("{}", sum);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<String> {
+fn parse(s: &str) -> Result<Vec<String>> {
     s.lines()
         .map(|line| {
-            let mut s = line.to_string();
-            s.retain(|c| !c.is_whitespace());
-            s
+            check(s, line)?;
+            let mut expr = line.to_string();
+            expr.retain(|c| !c.is_whitespace());
+            Ok(expr)
         })
-        .filter(|line| !line.is_empty())
-        .collect_vec()
+        .filter(|expr| !matches!(expr, Ok(expr) if expr.is_empty()))
+        .try_collect()
+}
+
+///
+/// Check that `line` alternates single digit operands and operators, with balanced brackets, so
+/// that `eval` can't fail
+///
+fn check(input: &str, line: &str) -> Result<(), AocError> {
+    let mut depth = 0;
+    let mut expect_operand = true;
+    for (i, c) in line.char_indices().filter(|(_, c)| !c.is_whitespace()) {
+        match (expect_operand, c) {
+            (true, '0'..='9') => expect_operand = false,
+            (true, '(') => depth += 1,
+            (false, '+' | '*') => expect_operand = true,
+            (false, ')') if depth > 0 => depth -= 1,
+            (true, _) => return Err(AocError::parse(input, &line[i..], "expected a digit or (")),
+            (false, _) => return Err(AocError::parse(input, &line[i..], "expected +, * or a closing )")),
+        }
+    }
+    let end = &line[line.trim_end().len()..];
+    match (expect_operand, depth) {
+        (_, 0) if !expect_operand || line.trim().is_empty() => Ok(()),
+        (true, _) => Err(AocError::parse(input, end, "expected a digit or (")),
+        (false, _) => Err(AocError::parse(input, end, "expected a closing )")),
+    }
 }
 
 fn eval(expr: &str, add_precendence: bool) -> u64 {
@@ -146,7 +192,7 @@ mod tests {
         5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
         ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2
         ";
-        let exprs = parse(s);
+        let exprs = parse(s)?;
         let vals = exprs.iter().map(|expr| eval(expr, false)).collect_vec();
         assert_eq!(vec![26, 437, 12240, 13632], vals);
 
@@ -157,7 +203,7 @@ mod tests {
         5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
         ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2
         ";
-        let exprs = parse(s);
+        let exprs = parse(s)?;
         let vals = exprs.iter().map(|expr| eval(expr, true)).collect_vec();
         assert_eq!(vec![51, 46, 1445, 669060, 23340], vals);
         Ok(())
@@ -140,12 +140,14 @@
 //
 // Your puzzle answer was 359.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 use pest::Parser;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
 mod p1 {
     use pest_derive::Parser;
 
@@ -172,25 +174,47 @@ mod p2 {
 // 0: 8 11
 // 0 => 42+ 42{n} 31{n}
 // 0 => (?!42 11) 42 0 | 42 11  # while lookahead not 42 11, chomp 42
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day19")?;
-    let msgs = input.split("\n\n").nth(1).unwrap();
-    let msgs = msgs.lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .collect_vec();
+struct Day19;
+
+pub const SOLUTION: Entry = Entry::new::<Day19>();
 
-    let count = msgs.iter()
-        .filter(|msg| p1::MsgParser::parse(p1::Rule::main, msg).is_ok())
-        .count();
-    println!("{}", count);
+impl Solution for Day19 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 19;
 
-    let count = msgs.iter()
-        .filter(|msg| p2::MsgParser::parse(p2::Rule::main, msg).is_ok())
-        .count();
-    println!("{}", count);
+    type Input = Vec<String>;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        let msgs = input.split("\n\n").nth(1)
+            .ok_or_else(|| AocError::parse_offset(input, input.len(), "expected rules, a blank line and messages"))?;
+        let msgs = msgs.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| match line.find(|c| c != 'a' && c != 'b') {
+                Some(i) => Err(AocError::parse(input, &line[i..], "expected a or b")),
+                None => Ok(line.to_string()),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(msgs)
+    }
+
+    fn part1(msgs: &Self::Input) -> Result<impl Display> {
+        let count = msgs.iter()
+            .filter(|msg| p1::MsgParser::parse(p1::Rule::main, msg).is_ok())
+            .count();
+        Ok(count)
+    }
+
+    fn part2(msgs: &Self::Input) -> Result<impl Display> {
+        let count = msgs.iter()
+            .filter(|msg| p2::MsgParser::parse(p2::Rule::main, msg).is_ok())
+            .count();
+        Ok(count)
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
 #[cfg(test)]
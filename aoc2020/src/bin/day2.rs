@@ -41,29 +41,41 @@
 //
 // Your puzzle answer was 497.
 
-use std::fs;
+use std::fmt::Display;
 use std::str::FromStr;
 
 use anyhow::Result;
 use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
+
+struct Day2;
+
+pub const SOLUTION: Entry = Entry::new::<Day2>();
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Password>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(|line| line.parse::<Password>().map_err(|e| e.within(input, line))).try_collect()?)
+    }
+
+    fn part1(passwords: &Self::Input) -> Result<impl Display> {
+        Ok(passwords.iter().filter(|p| p.is_valid1()).count())
+    }
+
+    fn part2(passwords: &Self::Input) -> Result<impl Display> {
+        Ok(passwords.iter().filter(|p| p.is_valid2()).count())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day2")?;
-    let passwords: Vec<_> = input.lines()
-        .map(|line| line.parse::<Password>())
-        .try_collect()?;
-
-    let count = passwords.iter()
-        .filter(|p| p.is_valid1())
-        .count();
-    println!("{}", count);
-
-    let count = passwords.iter()
-        .filter(|p| p.is_valid2())
-        .count();
-    println!("{}", count);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -92,21 +104,18 @@ impl Password {
 }
 
 impl FromStr for Password {
-    type Err = anyhow::Error;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static::lazy_static! {
-            static ref RE: Regex = Regex::new(r"(\d+)-(\d+) ([a-z]): ([a-z]+)").unwrap();
-        }
-        for cap in RE.captures_iter(s) {
-            let n1 = cap[1].parse::<usize>()?;
-            let n2 = cap[2].parse::<usize>()?;
-            let c = cap[3].as_bytes()[0];
-            let s = cap[4].as_bytes().to_vec();
-            let res = Password { n1, n2, c, s };
-            return Ok(res);
+            static ref RE: Regex = Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").unwrap();
         }
-        anyhow::bail!("unable to parse password")
+        let cap = RE.captures(s).ok_or_else(|| AocError::parse(s, s, "expected a policy and password such as 1-3 a: abcde"))?;
+        let n1 = error::parse_at(s, &cap[1])?;
+        let n2 = error::parse_at(s, &cap[2])?;
+        let c = cap[3].as_bytes()[0];
+        let pass = cap[4].as_bytes().to_vec();
+        Ok(Password { n1, n2, c, s: pass })
     }
 }
 
@@ -270,17 +270,20 @@
 // Your puzzle answer was 2152.
 
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 use num::integer::Roots;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::orient::D4;
+use aoc::solution::{Entry, Solution};
 use aoc2020::Enumerate2D;
 
-use crate::chargrid::Grid;
-use crate::tileborder::SquareTileBorder;
+use self::chargrid::Grid;
+use self::tileborder::SquareTileBorder;
 
 const DRAGON_RAW: &str = r"
                   # 
@@ -302,32 +305,68 @@ lazy_static::lazy_static! {
             .collect();
 }
 
+struct Day20;
+
+pub const SOLUTION: Entry = Entry::new::<Day20>();
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 20;
+
+    type Input = (HashMap<u64, Grid>, Vec<Vec<SquareTileBorder>>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let tiles = parse(input)?;
+        let tileborders = solve(&tiles)?;
+        Ok((tiles, tileborders))
+    }
+
+    fn part1((_, tileborders): &Self::Input) -> Result<impl Display> {
+        Ok(corner_prod(tileborders))
+    }
+
+    fn part2((tiles, tileborders): &Self::Input) -> Result<impl Display> {
+        let image = assemble_image(tileborders.clone(), tiles.clone());
+        calc_water_roughness(&image).ok_or_else(|| AocError::no_solution("no sea monsters").into())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day20")?;
-    let tiles = parse(&input);
-    let tileborders = solve(&tiles);
-    println!("{}", corner_prod(&tileborders));
-    let image = assemble_image(tileborders, tiles);
-    println!("{:?}", calc_water_roughness(&image));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> HashMap<u64, Grid> {
+fn parse(s: &str) -> Result<HashMap<u64, Grid>> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^Tile (\d+):$").unwrap();
     }
-    s.trim().split("\n\n")
-        .map(|tile| {
-            let (first_line, rest) = tile.split_at(tile.find('\n').unwrap());
-            let id = &RE.captures(first_line.trim()).unwrap()[1];
-            let id = id.parse::<u64>().unwrap();
-            let grid = Grid::parse(rest).unwrap();
-            (id, grid)
-        })
-        .collect::<HashMap<_, _>>()
+    let mut tiles = HashMap::new();
+    for tile in s.trim().split("\n\n") {
+        let (first_line, rest) = tile.split_once('\n').unwrap_or((tile, ""));
+        let first_line = first_line.trim();
+        let cap = RE.captures(first_line).ok_or_else(|| AocError::parse(s, first_line, "expected Tile <id>:"))?;
+        let id = error::parse_at(s, &cap[1])?;
+        if let Some(line) = rest.lines().map(str::trim).find(|line| line.contains(|c| c != '#' && c != '.')) {
+            let i = line.find(|c| c != '#' && c != '.').unwrap_or(0);
+            return Err(AocError::parse(s, &line[i..], "expected # or .").into());
+        }
+        let grid = Grid::parse(rest).map_err(|e| e.within(s, rest))?;
+        if grid.num_rows != grid.num_cols || grid.num_rows >= 16 {
+            return Err(AocError::parse(s, first_line, "expected a square tile under 16 pixels wide").into());
+        }
+        tiles.insert(id, grid);
+    }
+    if tiles.values().map(|grid| grid.num_rows).unique().count() != 1 {
+        return Err(AocError::invalid("tiles are different sizes").into());
+    }
+    if tiles.len().sqrt().pow(2) != tiles.len() {
+        return Err(AocError::invalid(format!("{} tiles can't make a square image", tiles.len())).into());
+    }
+    Ok(tiles)
 }
 
 mod tileborder {
+    use aoc::geom::{Direction, Vector2};
+
     use super::*;
 
     #[derive(Clone, Eq, PartialEq, Debug, Default)]
@@ -337,8 +376,7 @@ mod tileborder {
         pub e: u16,
         pub s: u16,
         pub w: u16,
-        pub num_cw_rotations: usize,
-        pub is_flipped: bool,
+        pub orientation: D4,
         len: usize,
     }
 
@@ -368,44 +406,41 @@ mod tileborder {
             let w = to_int(&grid.iter_row().map(|v| v[0]).collect_vec());
             let e = to_int(&grid.iter_row().map(|v| v[v.len() - 1]).collect_vec());
 
-            SquareTileBorder { id, n, e, s, w, len: grid.num_rows, num_cw_rotations: 0, is_flipped: false }
-        }
-
-        pub fn flip_x(&self) -> SquareTileBorder {
-            let mut res = self.clone();
-            res.is_flipped = !res.is_flipped;
-            std::mem::swap(&mut res.e, &mut res.w);
-            res.n = reverse(res.n, res.len);
-            res.s = reverse(res.s, res.len);
-            res
+            SquareTileBorder { id, n, e, s, w, len: grid.num_rows, orientation: D4::IDENTITY }
         }
 
-        pub fn rotate_cw(&self) -> SquareTileBorder {
-            let mut res = self.clone();
-            res.num_cw_rotations = (res.num_cw_rotations + 1) % 4;
-            std::mem::swap(&mut res.n, &mut res.e);
-            std::mem::swap(&mut res.n, &mut res.s);
-            std::mem::swap(&mut res.n, &mut res.w);
-            res.s = reverse(res.s, res.len);
-            res.n = reverse(res.n, res.len);
-            res
+        pub fn orient(&self, orientation: D4) -> SquareTileBorder {
+            // read clockwise, a side stays the same under rotation and reverses under a flip
+            let len = self.len;
+            let sides = [self.n, self.e, reverse(self.s, len), reverse(self.w, len)];
+            let mut oriented = [0; 4];
+            for (i, dir) in Direction::VALUES_4D.iter().enumerate() {
+                let d: Vector2 = dir.dxdy().orient(orientation);
+                let j = Direction::VALUES_4D.iter()
+                    .position(|dir| dir.dxdy() == d)
+                    .unwrap();
+                oriented[j] = if orientation.is_flipped() { reverse(sides[i], len) } else { sides[i] };
+            }
+            let [n, e, s, w] = oriented;
+
+            SquareTileBorder {
+                id: self.id,
+                n,
+                e,
+                s: reverse(s, len),
+                w: reverse(w, len),
+                orientation: self.orientation.then(orientation),
+                len,
+            }
         }
 
-        pub fn variations(&self) -> impl Iterator<Item=Self> {
-            std::iter::successors(Some(self.clone()), |prev|
-                if prev.num_cw_rotations < 3 {
-                    Some(prev.rotate_cw())
-                } else if !prev.is_flipped {
-                    Some(prev.rotate_cw().flip_x())
-                } else {
-                    None
-                },
-            )
+        pub fn variations(&self) -> impl Iterator<Item=Self> + '_ {
+            D4::all().map(move |orientation| self.orient(orientation))
         }
     }
 }
 
-fn solve(tiles: &HashMap<u64, Grid>) -> Vec<Vec<SquareTileBorder>> {
+fn solve(tiles: &HashMap<u64, Grid>) -> Result<Vec<Vec<SquareTileBorder>>> {
     let mut tileborders = tiles.iter()
         .map(|(&id, grid)| SquareTileBorder::new(id, grid))
         .collect::<VecDeque<_>>();
@@ -418,15 +453,17 @@ fn solve(tiles: &HashMap<u64, Grid>) -> Vec<Vec<SquareTileBorder>> {
                 .filter(|tb2| tb != tb2)
                 .flat_map(SquareTileBorder::variations)
                 .all(|variation| variation.s != tb.n && variation.e != tb.w))
-        .unwrap();
+        .ok_or_else(|| AocError::no_solution("no tile fits the top left corner"))?;
 
     let n = tileborders.len().sqrt();
     let mut grid = vec![vec![SquareTileBorder::default(); n]; n];
     grid[0][0] = tileborders.remove(tl_idx).unwrap();
 
     let found = solve_rec(&mut grid, n, 1, &mut tileborders);
-    assert!(found);
-    return grid;
+    if !found {
+        return Err(AocError::no_solution("tiles don't fit together").into());
+    }
+    return Ok(grid);
 
     fn solve_rec(mut grid: &mut Vec<Vec<SquareTileBorder>>,
                  n: usize, i: usize,
@@ -468,20 +505,13 @@ fn assemble_image(tileborders: Vec<Vec<SquareTileBorder>>,
     let mut res = vec![vec![b'.'; n]; n];
 
     for (grid_y, grid_x, tb) in tileborders.iter().enumerate_2d() {
-        let mut gridp = &tiles[&tb.id];
-        let mut grid;
-        if tb.is_flipped {
-            grid = gridp.flip_x();
-            gridp = &grid;
-        }
-        for _ in 0..tb.num_cw_rotations {
-            grid = gridp.rotate_cw();
-            gridp = &grid;
-        }
+        let grid = &tiles[&tb.id];
         let (offset_y, offset_x) = (grid_y * (tile_size - 2), grid_x * (tile_size - 2));
+        let source = tb.orientation.source_map(tile_size, tile_size);
         for i in 1..(tile_size - 1) {
             for j in 1..(tile_size - 1) {
-                res[offset_y + i - 1][offset_x + j - 1] = gridp[i][j];
+                let (x, y) = source(j, i);
+                res[offset_y + i - 1][offset_x + j - 1] = grid[y][x];
             }
         }
     }
@@ -490,8 +520,8 @@ fn assemble_image(tileborders: Vec<Vec<SquareTileBorder>>,
 
 fn calc_water_roughness(grid: &Grid) -> Option<usize> {
     let n = grid.iter().filter(|(_, _, &c)| c == b'#').count();
-    for grid in grid.variations() {
-        let pts = find_dragon_pts(&grid);
+    for orientation in D4::all() {
+        let pts = find_dragon_pts(grid, orientation);
         if pts.len() > 0 {
             return Some(n - pts.len());
         }
@@ -499,12 +529,19 @@ fn calc_water_roughness(grid: &Grid) -> Option<usize> {
     None
 }
 
-fn find_dragon_pts(grid: &Grid) -> HashSet<(usize, usize)> {
+fn find_dragon_pts(grid: &Grid, orientation: D4) -> HashSet<(usize, usize)> {
+    let (num_cols, num_rows) = orientation.dims(grid.num_cols, grid.num_rows);
+    let source = orientation.source_map(grid.num_cols, grid.num_rows);
+    let get = |y: usize, x: usize| {
+        let (x, y) = source(x, y);
+        grid[y][x]
+    };
+
     let mut res = HashSet::new();
-    for y_tl in 0..(grid.num_rows - DRAGON.len() + 1) {
-        for x_tl in 0..(grid.num_cols - DRAGON[0].len() + 1) {
+    for y_tl in 0..(num_rows - DRAGON.len() + 1) {
+        for x_tl in 0..(num_cols - DRAGON[0].len() + 1) {
             if DRAGON_PTS.iter().all(|(y, x)|
-                grid[y_tl + y][x_tl + x] == b'#')
+                get(y_tl + y, x_tl + x) == b'#')
             {
                 res.extend(DRAGON_PTS.iter().map(|(y, x)| (y_tl + y, x_tl + x)));
             }
@@ -628,8 +665,8 @@ mod tests {
         ..#.......
         ..#.###...
         ";
-        let tiles = parse(s);
-        let tileborders = solve(&tiles);
+        let tiles = parse(s)?;
+        let tileborders = solve(&tiles)?;
         assert_eq!(20899048083289, corner_prod(&tileborders));
         let image = assemble_image(tileborders, tiles);
         assert_eq!(Some(273), calc_water_roughness(&image));
@@ -642,13 +679,12 @@ mod chargrid {
     use std::fmt;
     use std::ops::{Index, IndexMut};
 
-    use anyhow::Result;
     use itertools::Itertools;
 
+    use aoc::error::AocError;
+    use aoc::geom::Vector2;
     use aoc2020::{Enumerate2D, TrimEmpty};
 
-    use crate::geom::Vector2;
-
     #[derive(Clone, Hash, Eq, PartialEq, Debug)]
     pub struct Grid {
         grid: Vec<Vec<u8>>,
@@ -663,20 +699,20 @@ mod chargrid {
             Grid { grid, num_rows, num_cols }
         }
 
-        pub fn parse(s: &str) -> Result<Grid> {
-            let grid = s.lines()
+        pub fn parse(s: &str) -> Result<Grid, AocError> {
+            let lines = s.lines()
                 .trim_empty()
-                .map(|line| line.as_bytes().to_vec())
                 .collect_vec();
-            if grid.is_empty() {
-                anyhow::bail!("empty grid");
+            if lines.is_empty() {
+                return Err(AocError::parse_offset(s, s.len(), "expected a grid"));
             }
-            let num_rows = grid.len();
-            let num_cols = grid[0].len();
+            let num_rows = lines.len();
+            let num_cols = lines[0].len();
 
-            if grid.iter().any(|row| row.len() != num_cols) {
-                anyhow::bail!("jagged grid");
+            if let Some(line) = lines.iter().find(|line| line.len() != num_cols) {
+                return Err(AocError::parse(s, line, format!("expected {} columns like the first row", num_cols)));
             }
+            let grid = lines.iter().map(|line| line.as_bytes().to_vec()).collect();
             Ok(Grid { grid, num_rows, num_cols })
         }
 
@@ -687,60 +723,6 @@ mod chargrid {
         pub fn iter_row(&self) -> impl Iterator<Item=&Vec<u8>> {
             self.grid.iter()
         }
-
-        pub fn flip_x(&self) -> Grid {
-            let (num_rows, num_cols) = (self.num_rows, self.num_cols);
-            let mut grid = vec![vec![b'.'; num_cols]; num_rows];
-            for (y, x, c) in self.iter() {
-                grid[y][num_cols - 1 - x] = *c;
-            }
-            Grid { grid, num_rows, num_cols }
-        }
-
-        pub fn rotate_cw(&self) -> Grid {
-            let (num_rows, num_cols) = (self.num_rows, self.num_cols);
-            let mut grid = vec![vec![b'.'; num_rows]; num_cols];
-            for (y, x, c) in self.iter() {
-                grid[x][num_rows - 1 - y] = *c;
-            }
-            Grid { grid, num_cols: num_rows, num_rows: num_cols }
-        }
-
-        pub fn variations(&self) -> VariationIter {
-            VariationIter {
-                initial: self,
-                num_rotations: 0,
-                is_flipped: false,
-                next: Some(self.clone()),
-            }
-        }
-    }
-
-    pub struct VariationIter<'a> {
-        initial: &'a Grid,
-        num_rotations: usize,
-        is_flipped: bool,
-        next: Option<Grid>,
-    }
-
-    impl<'a> Iterator for VariationIter<'a> {
-        type Item = Grid;
-
-        fn next(&mut self) -> Option<Self::Item> {
-            let item = self.next.take()?;
-
-            self.next = if self.num_rotations < 3 {
-                self.num_rotations += 1;
-                Some(item.rotate_cw())
-            } else if !self.is_flipped {
-                self.is_flipped = true;
-                self.num_rotations = 0;
-                Some(self.initial.flip_x())
-            } else {
-                None
-            };
-            Some(item)
-        }
     }
 
     impl Display for Grid {
@@ -757,16 +739,16 @@ mod chargrid {
         type Output = u8;
 
         fn index(&self, index: Vector2) -> &Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &self.grid[y][x]
         }
     }
 
     impl IndexMut<Vector2> for Grid {
         fn index_mut(&mut self, index: Vector2) -> &mut Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &mut self.grid[y][x]
         }
     }
@@ -785,164 +767,3 @@ mod chargrid {
         }
     }
 }
-
-mod geom {
-    use std::convert::{TryFrom, TryInto};
-    use std::ops::{Add, AddAssign, Mul, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        #[allow(dead_code)]
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        N,
-        NE,
-        E,
-        SE,
-        S,
-        SW,
-        W,
-        NW,
-    }
-
-    impl Direction {
-        #[allow(dead_code)]
-        pub const VALUES_4D: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        #[allow(dead_code)]
-        pub const VALUES_8D: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-
-        #[allow(dead_code)]
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(1, 1),
-                Direction::S => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(-1, -1),
-            }
-        }
-
-        #[allow(dead_code)]
-        pub fn cw(&self, num_turns: isize) -> Direction {
-            let dir = ((*self as isize) + num_turns) % 8;
-            dir.try_into().unwrap()
-        }
-
-        #[allow(dead_code)]
-        pub fn ccw(&self, num_turns: isize) -> Direction {
-            let mut dir = ((*self as isize) - num_turns) % 8;
-            if dir < 0 { dir += 8; }
-            dir.try_into().unwrap()
-        }
-    }
-
-    impl TryFrom<isize> for Direction {
-        type Error = ();
-
-        fn try_from(i: isize) -> Result<Self, Self::Error> {
-            match i {
-                x if x == Direction::N as isize => Ok(Direction::N),
-                x if x == Direction::NE as isize => Ok(Direction::NE),
-                x if x == Direction::E as isize => Ok(Direction::E),
-                x if x == Direction::SE as isize => Ok(Direction::SE),
-                x if x == Direction::S as isize => Ok(Direction::S),
-                x if x == Direction::SW as isize => Ok(Direction::SW),
-                x if x == Direction::W as isize => Ok(Direction::W),
-                x if x == Direction::NW as isize => Ok(Direction::NW),
-                _ => Err(()),
-            }
-        }
-    }
-}
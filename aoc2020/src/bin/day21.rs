@@ -42,32 +42,53 @@
 
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 use std::hash::Hash;
 
 use anyhow::Result;
 use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 use aoc2020::TrimEmpty;
 
+struct Day21;
+
+pub const SOLUTION: Entry = Entry::new::<Day21>();
+
+impl Solution for Day21 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 21;
+
+    type Input = (HashMap<String, Vec<HashSet<String>>>, Vec<String>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((allergen_ingredients, all_ingredients): &Self::Input) -> Result<impl Display> {
+        Ok(part1(all_ingredients, &solve(allergen_ingredients)?))
+    }
+
+    fn part2((allergen_ingredients, _): &Self::Input) -> Result<impl Display> {
+        Ok(part2(&solve(allergen_ingredients)?))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day21")?;
-    let (allergen_ingredients, all_ingredients) = parse(&input);
-    let soln = solve(&allergen_ingredients);
-    println!("{}", part1(&all_ingredients, &soln));
-    println!("{}", part2(&soln));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (HashMap<String, Vec<HashSet<String>>>, Vec<String>) {
+fn parse(s: &str) -> Result<(HashMap<String, Vec<HashSet<String>>>, Vec<String>)> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^(.*)\(contains(.*)\)$").unwrap();
     }
     let mut all = Vec::new();
     let mut res = HashMap::new();
     for line in s.lines().trim_empty() {
-        let caps = RE.captures(line).unwrap();
+        let caps = RE.captures(line)
+            .ok_or_else(|| AocError::parse(s, line, "expected <ingredients> (contains <allergens>)"))?;
         let ingredients = caps[1]
             .split(' ').trim_empty()
             .map(ToString::to_string)
@@ -80,11 +101,11 @@ fn parse(s: &str) -> (HashMap<String, Vec<HashSet<String>>>, Vec<String>) {
             res.entry(k).or_insert(Vec::new()).push(v);
         }
     }
-    (res, all)
+    Ok((res, all))
 }
 
 fn solve(allergen_ingredients: &HashMap<String, Vec<HashSet<String>>>)
-         -> HashMap<String, String>
+         -> Result<HashMap<String, String>, AocError>
 {
     let mut res = allergen_ingredients.iter()
         .map(|(allergen, ingredients)| (allergen, intersects(ingredients)))
@@ -107,8 +128,10 @@ fn solve(allergen_ingredients: &HashMap<String, Vec<HashSet<String>>>)
         }
     }
     res.iter().map(|(allergen, ingredient)| {
-        assert_eq!(ingredient.len(), 1);
-        (allergen.to_string(), ingredient.iter().next().unwrap().to_string())
+        match ingredient.iter().exactly_one() {
+            Ok(ingredient) => Ok((allergen.to_string(), ingredient.to_string())),
+            Err(_) => Err(AocError::no_solution(format!("{} isn't in exactly one ingredient", allergen))),
+        }
     }).collect()
 }
 
@@ -150,8 +173,8 @@ mod tests {
         sqjhc fvjkl (contains soy)
         sqjhc mxmxvkd sbzzf (contains fish)
         ";
-        let (recipes, all) = parse(&s);
-        let solved = solve(&recipes);
+        let (recipes, all) = parse(&s)?;
+        let solved = solve(&recipes)?;
         assert_eq!(5, part1(&all, &solved));
         assert_eq!("mxmxvkd,sqjhc,fvjkl", part2(&solved));
         Ok(())
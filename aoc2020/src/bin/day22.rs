@@ -383,32 +383,61 @@
 
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs;
+use std::fmt::Display;
 use std::iter::FromIterator;
 
 use anyhow::Result;
+use itertools::Itertools;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
 use aoc2020::TrimEmpty;
 
+struct Day22;
+
+pub const SOLUTION: Entry = Entry::new::<Day22>();
+
+impl Solution for Day22 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 22;
+
+    type Input = [VecDeque<u32>; 2];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(cards: &Self::Input) -> Result<impl Display> {
+        Ok(part1(cards))
+    }
+
+    fn part2(cards: &Self::Input) -> Result<impl Display> {
+        Ok(part2(cards))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day22")?;
-    let cards = parse(&input);
-    println!("{}", part1(&cards));
-    println!("{}", part2(&cards));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> [VecDeque<u32>; 2] {
+fn parse(s: &str) -> Result<[VecDeque<u32>; 2]> {
     let mut iter = s.trim().split("\n\n")
-        .map(|s| {
-            let mut iter = s.lines().trim_empty();
-            assert!(iter.next().unwrap().starts_with("Player"));
-            iter.map(|i| i.parse::<u32>().unwrap())
-                .collect::<VecDeque<_>>()
+        .map(|deck| {
+            let mut iter = deck.lines().trim_empty();
+            match iter.next() {
+                Some(line) if line.trim().starts_with("Player") => {}
+                _ => return Err(AocError::parse(s, deck, "expected Player <n>:")),
+            }
+            iter.map(|i| error::parse_at::<u32>(s, i.trim()))
+                .collect::<Result<VecDeque<_>, _>>()
         });
-    let p0 = iter.next().unwrap();
-    let p1 = iter.next().unwrap();
-    [p0, p1]
+    let mut next = || iter.next()
+        .unwrap_or_else(|| Err(AocError::parse_offset(s, s.len(), "expected two players")));
+    let cards = [next()?, next()?];
+    if cards.iter().flatten().unique().count() != cards.iter().map(VecDeque::len).sum::<usize>() {
+        return Err(AocError::invalid("the same card is dealt twice").into());
+    }
+    Ok(cards)
 }
 
 fn part1(cards: &[VecDeque<u32>; 2]) -> u32 {
@@ -456,12 +485,10 @@ fn play_rec(p0: &mut VecDeque<u32>,
     let mut game_winner = None;
     let mut visited = HashSet::new();
     while !p0.is_empty() && !p1.is_empty() {
-        let round_state = (p0.clone(), p1.clone());
-        if visited.contains(&round_state) {
+        if !visited.insert((p0.clone(), p1.clone())) {
             game_winner = Some(0);
             break;
         }
-        visited.insert(round_state);
 
         let c0 = p0.pop_front().unwrap();
         let c1 = p1.pop_front().unwrap();
@@ -520,7 +547,7 @@ mod tests {
         7
         10
         ";
-        let cards = parse(&s);
+        let cards = parse(&s)?;
         assert_eq!(306, part1(&cards));
         assert_eq!(291, part2(&cards));
         Ok(())
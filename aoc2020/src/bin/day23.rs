@@ -93,24 +93,53 @@
 //
 // Your puzzle answer was 689500518476.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
+use itertools::Itertools;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day23")?;
-    let cups = parse(&input);
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
+struct Day23;
+
+pub const SOLUTION: Entry = Entry::new::<Day23>();
+
+impl Solution for Day23 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 23;
 
-    let res = play(&cups, cups.len(), 100);
-    println!("{}", part1(&res));
+    type Input = Vec<usize>;
 
-    let res = play(&cups, 1000000, 10000000);
-    println!("{}", part2(&res));
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(cups: &Self::Input) -> Result<impl Display> {
+        Ok(part1(&play(cups, cups.len(), 100)))
+    }
+
+    fn part2(cups: &Self::Input) -> Result<impl Display> {
+        Ok(part2(&play(cups, 1000000, 10000000)))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<usize> {
-    s.trim().chars().map(|c| c.to_digit(10).unwrap() as usize).collect()
+fn parse(s: &str) -> Result<Vec<usize>> {
+    let line = s.trim();
+    let cups = line.char_indices()
+        .map(|(i, c)| match c.to_digit(10) {
+            Some(d) => Ok(d as usize),
+            None => Err(AocError::parse(s, &line[i..], "expected a digit")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if cups.len() < 4 || cups.iter().sorted().ne((1..=cups.len()).collect_vec().iter()) {
+        return Err(AocError::invalid("expected the cups to be labelled 1 to n, with at least 4 cups").into());
+    }
+    Ok(cups)
 }
 
 fn play(initial_cups: &Vec<usize>,
@@ -182,7 +211,7 @@ mod tests {
     fn test() -> Result<()> {
         let s = "389125467";
 
-        let cups = parse(&s);
+        let cups = parse(&s)?;
         let res = play(&cups, cups.len(), 100);
         assert_eq!("67384529", part1(&res));
 
@@ -85,54 +85,120 @@
 // Your puzzle answer was 4231.
 
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::AocError;
+use aoc::geom::Vector2;
+use aoc::solution::{Entry, Solution};
 use aoc2020::TrimEmpty;
 
-use crate::geom::{Direction, Vector2};
-
 // Axial coordinates:
 // https://www.redblobgames.com/grids/hexagons/
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day24")?;
-    let moves = parse(&input);
-    let mut blacks = flip(&moves);
-    println!("{}", blacks.len());
-    for _ in 0..100 {
-        tick(&mut blacks);
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+enum HexDirection {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+impl HexDirection {
+    const VALUES: [HexDirection; 6] = [
+        HexDirection::E,
+        HexDirection::SE,
+        HexDirection::SW,
+        HexDirection::W,
+        HexDirection::NW,
+        HexDirection::NE,
+    ];
+
+    fn dxdy(&self) -> Vector2 {
+        let [x, y] = match self {
+            HexDirection::E => [1, 0],
+            HexDirection::SE => [0, 1],
+            HexDirection::SW => [-1, 1],
+            HexDirection::W => [-1, 0],
+            HexDirection::NW => [0, -1],
+            HexDirection::NE => [1, -1],
+        };
+        Vector2::new([x, y])
     }
-    println!("{}", blacks.len());
-    Ok(())
 }
 
-fn parse(s: &str) -> Vec<Vec<Direction>> {
+struct Day24;
+
+pub const SOLUTION: Entry = Entry::new::<Day24>();
+
+impl Solution for Day24 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 24;
+
+    type Input = Vec<Vec<HexDirection>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(moves: &Self::Input) -> Result<impl Display> {
+        Ok(flip(moves).len())
+    }
+
+    fn part2(moves: &Self::Input) -> Result<impl Display> {
+        let mut blacks = flip(moves);
+        for _ in 0..100 {
+            tick(&mut blacks);
+        }
+        Ok(blacks.len())
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
+}
+
+fn parse(s: &str) -> Result<Vec<Vec<HexDirection>>> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"(e|se|sw|w|nw|ne)").unwrap();
     }
     s.lines().trim_empty()
-        .map(|line|
-            RE.find_iter(line)
-                .map(|m| match m.as_str() {
-                    "e" => Direction::E,
-                    "se" => Direction::SE,
-                    "sw" => Direction::SW,
-                    "w" => Direction::W,
-                    "nw" => Direction::NW,
-                    "ne" => Direction::NE,
-                    _ => unreachable!(),
+        .map(|line| {
+            let line = line.trim();
+            let mut end = 0;
+            let path = RE.find_iter(line)
+                .map(|m| {
+                    if m.start() != end {
+                        return Err(AocError::parse(s, &line[end..], "expected e, se, sw, w, nw or ne"));
+                    }
+                    end = m.end();
+                    Ok(match m.as_str() {
+                        "e" => HexDirection::E,
+                        "se" => HexDirection::SE,
+                        "sw" => HexDirection::SW,
+                        "w" => HexDirection::W,
+                        "nw" => HexDirection::NW,
+                        "ne" => HexDirection::NE,
+                        _ => unreachable!(),
+                    })
                 })
-                .collect_vec())
-        .collect_vec()
+                .collect::<Result<Vec<_>, _>>()?;
+            if end != line.len() {
+                return Err(AocError::parse(s, &line[end..], "expected e, se, sw, w, nw or ne"));
+            }
+            Ok(path)
+        })
+        .collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
-fn flip(paths: &Vec<Vec<Direction>>) -> HashSet<Vector2> {
-    fn walk(path: &Vec<Direction>) -> Vector2 {
-        path.iter().fold(Vector2::ZERO, |pos, dir|
+fn flip(paths: &Vec<Vec<HexDirection>>) -> HashSet<Vector2> {
+    fn walk(path: &Vec<HexDirection>) -> Vector2 {
+        path.iter().fold(Vector2::zero(), |pos, dir|
             pos + dir.dxdy(),
         )
     }
@@ -149,7 +215,7 @@ fn tick(blacks: &mut HashSet<Vector2>) {
     let mut neighbours = HashMap::new();
     for &coord in blacks.iter() {
         neighbours.entry(coord).or_insert(0);
-        for dir in Direction::VALUES.iter() {
+        for dir in HexDirection::VALUES.iter() {
             let neighbour = coord + dir.dxdy();
             *neighbours.entry(neighbour).or_insert(0) += 1;
         }
@@ -192,7 +258,7 @@ mod tests {
         neswnwewnwnwseenwseesewsenwsweewe
         wseweeenwnesenwwwswnew
         ";
-        let moves = parse(&s);
+        let moves = parse(&s)?;
         let mut blacks = flip(&moves);
         assert_eq!(10, blacks.len());
         for _ in 0..100 {
@@ -202,118 +268,3 @@ mod tests {
         Ok(())
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Mul, Neg, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        #[allow(dead_code)]
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        E,
-        SE,
-        SW,
-        W,
-        NW,
-        NE,
-    }
-
-    impl Direction {
-        #[allow(dead_code)]
-        pub const VALUES: [Direction; 6] = [
-            Direction::E,
-            Direction::SE,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-            Direction::NE,
-        ];
-
-        #[allow(dead_code)]
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-            }
-        }
-    }
-}
\ No newline at end of file
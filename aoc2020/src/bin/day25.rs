@@ -59,43 +59,67 @@
 // If you like, you can
 // .
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
+use aoc::error::{self, AocError};
+use aoc::numtheory;
+use aoc::solution::{Entry, Solution};
 use aoc2020::TrimEmpty;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day25")?;
-    let mut lines = input.lines().trim_empty();
-    let n1 = lines.next().unwrap().parse::<u128>().unwrap();
-    let n2 = lines.next().unwrap().parse::<u128>().unwrap();
-
-    let loop_size_n1 = find_n(1, 7, 20201227, n1);
-    let res = lcg_iter(1, n2, 20201227).nth(loop_size_n1);
-    println!("{:?}", res);
-    Ok(())
-}
+struct Day25;
+
+pub const SOLUTION: Entry = Entry::new::<Day25>();
+
+impl Solution for Day25 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 25;
+
+    type Input = (u64, u64);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut lines = input.lines().trim_empty();
+        let mut next = || -> Result<u64, AocError> {
+            let line = lines.next()
+                .ok_or_else(|| AocError::parse_offset(input, input.len(), "expected 2 public keys"))?
+                .trim();
+            match error::parse_at(input, line)? {
+                key @ 1..MODULUS => Ok(key),
+                _ => Err(AocError::parse(input, line, format!("expected a public key below {}", MODULUS))),
+            }
+        };
+        Ok((next()?, next()?))
+    }
 
-#[allow(dead_code)]
-fn lcg(x0: u128, a: u128, m: u128, n: u128) -> u128 {
-    let mut x = x0;
-    for _ in 0..n {
-        x = (x * a) % m;
+    fn part1(&(card, door): &Self::Input) -> Result<impl Display> {
+        handshake(card, door, SUBJECT, MODULUS)
+    }
+
+    fn part2(_: &Self::Input) -> Result<impl Display> {
+        Ok("Merry Christmas!")
     }
-    x
 }
 
-fn lcg_iter(x0: u128, a: u128, m: u128) -> impl Iterator<Item=u128> {
-    std::iter::successors(Some(x0), move |x| Some((x * a) % m))
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn find_n(x0: u128, a: u128, m: u128, target: u128) -> usize {
-    lcg_iter(x0, a, m)
-        .enumerate()
-        .find(|(_, val)| *val == target)
-        .map(|(i, _)| i)
-        .unwrap()
+const SUBJECT: u64 = 7;
+const MODULUS: u64 = 20201227;
+
+///
+/// The encryption key both sides arrive at, from the loop sizes that produce their public keys
+///
+fn handshake(card: u64, door: u64, subject: u64, m: u64) -> Result<u64> {
+    let loop_size = |key| numtheory::discrete_log(subject, key, m)
+        .ok_or_else(|| AocError::no_solution(format!("no loop size transforms {} into {}", subject, key)));
+    let (card_loop, door_loop) = (loop_size(card)?, loop_size(door)?);
+    let key = numtheory::powmod(door, card_loop, m);
+    if key != numtheory::powmod(card, door_loop, m) {
+        return Err(AocError::invalid("the card and door arrive at different encryption keys").into());
+    }
+    Ok(key)
 }
 
 #[cfg(test)]
@@ -104,10 +128,14 @@ mod tests {
 
     #[test]
     fn test() -> Result<()> {
-        assert_eq!(8, find_n(1, 7, 20201227, 5764801));
-        assert_eq!(11, find_n(1, 7, 20201227, 17807724));
-        assert_eq!(14897079, lcg_iter(1, 17807724, 20201227).nth(8).unwrap());
-        assert_eq!(14897079, lcg_iter(1, 5764801, 20201227).nth(11).unwrap());
+        assert_eq!(Some(8), numtheory::discrete_log(SUBJECT, 5764801, MODULUS));
+        assert_eq!(Some(11), numtheory::discrete_log(SUBJECT, 17807724, MODULUS));
+        assert_eq!(14897079, handshake(5764801, 17807724, SUBJECT, MODULUS)?);
+
+        // a larger prime modulus, whose group order 2 * 500000003 isn't smooth
+        let m = 1_000_000_007;
+        let (card, door) = (numtheory::powmod(5, 123456, m), numtheory::powmod(5, 654321, m));
+        assert_eq!(numtheory::powmod(5, 123456 * 654321, m), handshake(card, door, 5, m)?);
         Ok(())
     }
 }
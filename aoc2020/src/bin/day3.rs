@@ -75,38 +75,67 @@
 //
 // Your puzzle answer was 2421944712.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
+struct Day3;
+
+pub const SOLUTION: Entry = Entry::new::<Day3>();
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display> {
+        Ok(part2(grid))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day3")?;
-    println!("{}", part1(&input));
-    println!("{}", part2(&input));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn part1(s: &str) -> usize {
-    let grid = parse(s);
-    num_trees(&grid, 1, 3)
+fn part1(grid: &Vec<Vec<u8>>) -> usize {
+    num_trees(grid, 1, 3)
 }
 
-fn part2(s: &str) -> usize {
-    let grid = parse(s);
-    let p1 = num_trees(&grid, 1, 1);
-    let p2 = num_trees(&grid, 1, 3);
-    let p3 = num_trees(&grid, 1, 5);
-    let p4 = num_trees(&grid, 1, 7);
-    let p5 = num_trees(&grid, 2, 1);
+fn part2(grid: &Vec<Vec<u8>>) -> usize {
+    let p1 = num_trees(grid, 1, 1);
+    let p2 = num_trees(grid, 1, 3);
+    let p3 = num_trees(grid, 1, 5);
+    let p4 = num_trees(grid, 1, 7);
+    let p5 = num_trees(grid, 2, 1);
     p1 * p2 * p3 * p4 * p5
 }
 
-fn parse(s: &str) -> Vec<Vec<u8>> {
-    s.lines()
+fn parse(s: &str) -> Result<Vec<Vec<u8>>> {
+    let lines = s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| line.as_bytes().to_vec())
-        .collect()
+        .collect::<Vec<_>>();
+    for &line in lines.iter() {
+        if let Some(i) = line.find(|c| c != '#' && c != '.') {
+            return Err(AocError::parse(s, &line[i..], "expected # or .").into());
+        }
+        if line.len() != lines[0].len() {
+            return Err(AocError::parse(s, line, format!("expected {} squares like the first row", lines[0].len())).into());
+        }
+    }
+    Ok(lines.into_iter().map(|line| line.as_bytes().to_vec()).collect())
 }
 
 fn num_trees(grid: &Vec<Vec<u8>>,
@@ -143,8 +172,9 @@ mod tests {
         #...##....#
         .#..#...#.#
         ";
-        assert_eq!(7, part1(s));
-        assert_eq!(336, part2(s));
+        let grid = parse(s)?;
+        assert_eq!(7, part1(&grid));
+        assert_eq!(336, part2(&grid));
         Ok(())
     }
 }
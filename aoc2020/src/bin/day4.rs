@@ -121,31 +121,47 @@
 // Your puzzle answer was 186.
 
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use regex::Regex;
 
+use aoc::parse::{self, ident, key_values, records, take_while1, Parser};
+use aoc::solution::{Entry, Solution};
+
+struct Day4;
+
+pub const SOLUTION: Entry = Entry::new::<Day4>();
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 4;
+
+    type Input = Vec<HashMap<String, String>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(pps: &Self::Input) -> Result<impl Display> {
+        Ok(num_valid1(pps))
+    }
+
+    fn part2(pps: &Self::Input) -> Result<impl Display> {
+        Ok(num_valid2(pps))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day4")?;
-    let pps = parse(&input);
-    println!("{}", num_valid1(&pps));
-    println!("{}", num_valid2(&pps));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<HashMap<String, String>> {
-    lazy_static::lazy_static! {
-        static ref RE: Regex = Regex::new(r"([a-z]+):([a-z0-9#]+)").unwrap();
-    }
-    s.split("\n\n")
-        .map(|es| es.trim())
-        .filter(|es| !es.is_empty())
-        .map(|es| es.split(' ')
-            .flat_map(|e| RE.captures_iter(e)
-                .map(|cap| (cap[1].to_string(), cap[2].to_string())))
-            .collect())
-        .collect()
+fn parse(s: &str) -> Result<Vec<HashMap<String, String>>> {
+    let value = take_while1("a value", |c| !c.is_whitespace());
+    let passport = key_values(ident(), ":", value).map(|fields| fields.into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect());
+    Ok(parse::all(s, records(passport))?)
 }
 
 fn num_valid1(passports: &Vec<HashMap<String, String>>) -> usize {
@@ -255,7 +271,7 @@ mod tests {
         hcl:#cfa07d eyr:2025 pid:166559648
         iyr:2011 ecl:brn hgt:59in
         ";
-        let pps = parse(s);
+        let pps = parse(s)?;
         assert_eq!(2, num_valid1(&pps));
 
         let s = r"
@@ -273,7 +289,7 @@ mod tests {
         eyr:2038 hcl:74454a iyr:2023
         pid:3556412378 byr:2007
         ";
-        let pps = parse(s);
+        let pps = parse(s)?;
         assert_eq!(0, num_valid2(&pps));
 
         let s = r"
@@ -290,7 +306,7 @@ mod tests {
 
         iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
         ";
-        let pps = parse(s);
+        let pps = parse(s)?;
         assert_eq!(4, num_valid2(&pps));
         Ok(())
     }
@@ -57,25 +57,65 @@
 
 use std::cmp::Ordering;
 use std::collections::VecDeque;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
+struct Day5;
+
+pub const SOLUTION: Entry = Entry::new::<Day5>();
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 5;
+
+    type Input = Vec<(u32, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(bps: &Self::Input) -> Result<impl Display> {
+        part1(bps).ok_or_else(|| AocError::invalid("no boarding passes").into())
+    }
+
+    fn part2(bps: &Self::Input) -> Result<impl Display> {
+        part2(bps).ok_or_else(|| AocError::no_solution("no empty seat").into())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day5")?;
+    aoc::runner::main(&[SOLUTION])
+}
 
-    let mut bps = input.lines()
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(|s| parse_bp(s))
-        .collect::<VecDeque<_>>();
+fn parse(s: &str) -> Result<Vec<(u32, u32)>> {
+    Ok(s.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let valid = line.len() == 10
+                && line[..7].chars().all(|c| c == 'F' || c == 'B')
+                && line[7..].chars().all(|c| c == 'L' || c == 'R');
+            match valid {
+                true => Ok(parse_bp(line)),
+                false => Err(AocError::parse(s, line, "expected 7 of F or B then 3 of L or R")),
+            }
+        })
+        .collect::<Result<_, AocError>>()?)
+}
 
-    let max = bps.iter()
+fn part1(bps: &[(u32, u32)]) -> Option<u32> {
+    bps.iter()
         .map(|&bp| id(bp))
-        .max().unwrap();
-    println!("{}", max);
+        .max()
+}
 
+fn part2(bps: &[(u32, u32)]) -> Option<u32> {
+    let mut bps = bps.iter().copied().collect::<VecDeque<_>>();
     bps.make_contiguous()
         .sort_unstable_by(|(r1, c1), (r2, c2)| {
             match r1.cmp(r2) {
@@ -85,8 +125,8 @@ fn main() -> Result<()> {
             }
         });
 
-    let (first_row, _) = bps.front().unwrap();
-    let (last_row, _) = bps.back().unwrap();
+    let (first_row, _) = bps.front()?;
+    let (last_row, _) = bps.back()?;
     for (r, g) in &bps.iter().group_by(|(r, _)| r) {
         let v = g.collect_vec();
         if v.len() != 8
@@ -95,11 +135,10 @@ fn main() -> Result<()> {
             let t1 = (0..8).sum::<u32>();
             let t2 = v.iter().map(|(_, c)| c).sum::<u32>();
             let c = t1 - t2;
-            println!("{:?}", id((*r, c)));
-            break;
+            return Some(id((*r, c)));
         }
     }
-    Ok(())
+    None
 }
 
 fn parse_bp(s: &str) -> (u32, u32) {
@@ -84,17 +84,38 @@
 // Your puzzle answer was 3585.
 
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
+
+use aoc::parse::{self, lines, records, take_while1, Parser};
+use aoc::solution::{Entry, Solution};
+
+struct Day6;
+
+pub const SOLUTION: Entry = Entry::new::<Day6>();
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 6;
+
+    type Input = Vec<Vec<String>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(vs: &Self::Input) -> Result<impl Display> {
+        Ok(sum_groups(vs, count_group1))
+    }
+
+    fn part2(vs: &Self::Input) -> Result<impl Display> {
+        Ok(sum_groups(vs, count_group2))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day6")?;
-    let vs = parse(&input);
-    println!("{}", sum_groups(&vs, count_group1));
-    println!("{}", sum_groups(&vs, count_group2));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn sum_groups<F>(vs: &Vec<Vec<String>>, count_fn: F) -> usize
@@ -122,16 +143,9 @@ fn count_group2(v: &Vec<String>) -> usize {
         .count()
 }
 
-fn parse(s: &str) -> Vec<Vec<String>> {
-    s.split("\n\n")
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(|s| s.lines()
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_string())
-            .collect_vec())
-        .collect_vec()
+fn parse(input: &str) -> Result<Vec<Vec<String>>> {
+    let answers = take_while1("the questions a to z", |c| c.is_ascii_lowercase()).map(str::to_string);
+    Ok(parse::all(input, records(lines(answers)))?)
 }
 
 #[cfg(test)]
@@ -157,7 +171,7 @@ mod tests {
 
         b
         ";
-        let vs = parse(s);
+        let vs = parse(s)?;
         assert_eq!(11, sum_groups(&vs, count_group1));
         assert_eq!(6, sum_groups(&vs, count_group2));
         Ok(())
@@ -65,92 +65,85 @@
 //
 // Your puzzle answer was 1038.
 
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::graph::Graph;
+use aoc::solution::{Entry, Solution};
+
+struct Day7;
+
+pub const SOLUTION: Entry = Entry::new::<Day7>();
+
+impl Solution for Day7 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 7;
+
+    type Input = Rules;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(rules: &Self::Input) -> Result<impl Display> {
+        Ok(count_outer_colour(rules, "shiny gold"))
+    }
+
+    fn part2(rules: &Self::Input) -> Result<impl Display> {
+        count_inner_bag(rules, "shiny gold")
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day7")?;
-    let rules = parse(&input);
-    println!("{}", count_outer_colour(&rules, "shiny gold"));
-    println!("{}", count_inner_bag(&rules, "shiny gold"));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn count_inner_bag(rules: &Rules, initial_col: &str) -> u32 {
-    fn count_rec<'a>(col: &'a str,
-                     mut counts: &mut HashMap<&'a str, u32>,
-                     graph: &'a HashMap<String, Vec<(u32, String)>>) -> u32 {
-        if counts.contains_key(col) {
-            return counts[col];
-        }
-        let mut res = 0;
-        for (num, adj_col) in graph[col].iter() {
-            res += num + (num * count_rec(adj_col, &mut counts, graph));
-        }
-        counts.insert(col, res);
-        res
-    }
-    count_rec(initial_col, &mut HashMap::new(), &rules.f)
+///
+/// The number of bags inside a bag of colour `col`
+///
+fn count_inner_bag(rules: &Rules, col: &str) -> Result<u32> {
+    let inside = rules
+        .accumulate(|_, held: &[(&u32, &u32)]| held.iter().map(|&(num, inside)| num * (1 + inside)).sum())
+        .map_err(|err| AocError::invalid(format!("bags can't hold themselves, but there's a {}", err)))?;
+    inside.get(col).copied().ok_or_else(|| AocError::invalid(format!("no rule for {} bags", col)).into())
 }
 
-fn count_outer_colour(rules: &Rules, initial_col: &str) -> usize {
-    let graph = &rules.b;
-    let mut q = Vec::new();
-    let mut visited = HashSet::new();
-
-    q.push(initial_col);
-    visited.insert(initial_col);
-    while !q.is_empty() {
-        let col = q.pop().unwrap();
-        for next_col in graph[col].iter() {
-            let next_col = &**next_col;
-            if !visited.contains(next_col) {
-                q.push(next_col);
-                visited.insert(next_col);
-            }
-        }
-    }
-    visited.len() - 1
+///
+/// The number of colours of bag that can hold a bag of colour `col`, however deep inside
+///
+fn count_outer_colour(rules: &Rules, col: &str) -> usize {
+    rules.reaching(col).len()
 }
 
-fn parse(s: &str) -> Rules {
+fn parse(s: &str) -> Result<Rules> {
     lazy_static::lazy_static! {
-        static ref L_RE: Regex = Regex::new(r"([a-z]+ [a-z]+) bag").unwrap();
+        static ref LINE_RE: Regex = Regex::new(
+            r"^([a-z]+ [a-z]+) bags contain (no other bags|\d+ [a-z]+ [a-z]+ bags?(?:, \d+ [a-z]+ [a-z]+ bags?)*)\.$").unwrap();
         static ref R_RE: Regex = Regex::new(r"([\d]+) ([a-z]+ [a-z]+) bag").unwrap();
     }
-    let mut f = HashMap::new();
-    let mut b = HashMap::new();
-    for (l, r) in s.lines()
+    let mut rules = Graph::new();
+    for line in s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| line.split("contain").collect_tuple().unwrap())
     {
-        let col_from = L_RE.captures(l).unwrap()[1].to_string();
-        b.entry(col_from.clone()).or_insert(HashSet::new());
-
-        let vec = f.entry(col_from.clone()).or_insert(Vec::new());
+        let cap = LINE_RE.captures(line)
+            .ok_or_else(|| AocError::parse(s, line, "expected a rule such as a b bags contain 1 c d bag."))?;
+        let (l, r) = (&cap[1], &cap[2]);
+        let col_from = l.to_string();
+        rules.add_node(col_from.clone());
         for caps in R_RE.captures_iter(r) {
-            let num = caps[1].parse::<u32>().unwrap();
-            let col_to = caps[2].to_string();
-
-            b.entry(col_to.clone()).or_insert(HashSet::new())
-                .insert(col_from.clone());
-
-            vec.push((num, col_to));
+            let num = error::parse_at(s, &caps[1])?;
+            rules.add_edge(col_from.clone(), caps[2].to_string(), num);
         }
     }
-    Rules { f, b }
+    Ok(rules)
 }
 
-#[derive(Debug, Clone)]
-struct Rules {
-    f: HashMap<String, Vec<(u32, String)>>,
-    b: HashMap<String, HashSet<String>>,
-}
+// from each colour of bag to the colours it holds, weighted by how many of each
+type Rules = Graph<String, u32>;
 
 #[cfg(test)]
 mod tests {
@@ -169,9 +162,9 @@ mod tests {
         faded blue bags contain no other bags.
         dotted black bags contain no other bags.
         ";
-        let rules = parse(s);
+        let rules = parse(s)?;
         assert_eq!(4, count_outer_colour(&rules, "shiny gold"));
-        assert_eq!(32, count_inner_bag(&rules, "shiny gold"));
+        assert_eq!(32, count_inner_bag(&rules, "shiny gold")?);
 
         Ok(())
     }
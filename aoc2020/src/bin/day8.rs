@@ -87,24 +87,43 @@
 //
 // Your puzzle answer was 552.
 
-use std::collections::HashSet;
-use std::fs;
-use std::str::FromStr;
+use std::fmt::Display;
+use std::iter::successors;
 
 use anyhow::Result;
-use itertools::Itertools;
-use regex::Regex;
+
+use aoc::cycle;
+use aoc::error::AocError;
+use aoc::parse::{self, int, lines, tag, Parser};
+use aoc::solution::{Entry, Solution};
+
+struct Day8;
+
+pub const SOLUTION: Entry = Entry::new::<Day8>();
+
+impl Solution for Day8 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 8;
+
+    type Input = Prog;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(prog: &Self::Input) -> Result<impl Display> {
+        let mut prog = prog.clone();
+        prog.exec();
+        Ok(prog.acc)
+    }
+
+    fn part2(prog: &Self::Input) -> Result<impl Display> {
+        find_broken(prog).ok_or_else(|| AocError::no_solution("no single change stops the program").into())
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day8")?;
-    let mut prog = parse(&input)?;
-    let state = prog.exec();
-    println!("{:?}", state);
-    println!("{}", prog.acc);
-
-    prog.acc = 0;
-    println!("{:?}", find_broken(&prog));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn find_broken(prog: &Prog) -> Option<isize> {
@@ -127,12 +146,11 @@ fn find_broken(prog: &Prog) -> Option<isize> {
 }
 
 fn parse(s: &str) -> Result<Prog> {
-    let ops: Vec<_> = s.lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<Op>())
-        .try_collect()?;
-    Ok(Prog::new(ops))
+    let op = tag("acc").map(|_| OpType::Acc)
+        .or(tag("jmp").map(|_| OpType::Jmp))
+        .or(tag("nop").map(|_| OpType::Nop));
+    let ops = parse::all(s, lines(op.skip(tag(" ")).and(int())))?;
+    Ok(Prog::new(ops.into_iter().map(|(op, arg)| Op { op, arg }).collect()))
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -152,26 +170,29 @@ impl Prog {
         Prog { ops, acc: 0 }
     }
 
+    ///
+    /// Runs until an instruction is about to run a second time, or the program counter leaves the
+    /// program and stays there
+    ///
     fn exec(&mut self) -> State {
-        let mut visited = HashSet::new();
-        let mut idx = 0_isize;
-        loop {
-            if idx < 0 || idx >= self.ops.len() as isize {
-                break State::Stopped;
-            }
-            if visited.contains(&idx) {
-                break State::Looping;
-            }
-            visited.insert(idx);
-            let Op { op, arg } = self.ops[idx as usize];
-            match op {
-                OpType::Acc => {
-                    self.acc += arg;
-                    idx += 1;
-                }
-                OpType::Jmp => idx += arg,
-                OpType::Nop => idx += 1,
+        let step = |&(idx, acc): &(isize, isize)| {
+            match self.ops.get(idx as usize) {
+                Some(Op { op: OpType::Acc, arg }) => (idx + 1, acc + arg),
+                Some(Op { op: OpType::Jmp, arg }) => (idx + arg, acc),
+                Some(Op { op: OpType::Nop, .. }) => (idx + 1, acc),
+                None => (idx, acc),
             }
+        };
+        let init = (0, self.acc);
+        let cycle = cycle::detect_cycle_by_key(init, step, |&(idx, _)| idx);
+        let (idx, acc) = successors(Some(init), |state| Some(step(state)))
+            .nth(cycle.start + cycle.len)
+            .unwrap();
+        self.acc = acc;
+        if idx < 0 || idx >= self.ops.len() as isize {
+            State::Stopped
+        } else {
+            State::Looping
         }
     }
 }
@@ -189,28 +210,6 @@ enum OpType {
     Nop,
 }
 
-impl FromStr for Op {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        lazy_static::lazy_static! {
-            static ref RE: Regex = Regex::new(r"^(acc|nop|jmp) ((?:-|\+)\d+)$").unwrap();
-        }
-        if !RE.is_match(s) {
-            anyhow::bail!("cannot parse op: {}", s);
-        }
-        let cap = RE.captures(s).unwrap();
-        let op = match &cap[1] {
-            "acc" => OpType::Acc,
-            "jmp" => OpType::Jmp,
-            "nop" => OpType::Nop,
-            _ => unreachable!(),
-        };
-        let arg = cap[2].parse::<isize>().unwrap();
-        Ok(Op { op, arg })
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
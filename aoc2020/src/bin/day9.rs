@@ -86,29 +86,49 @@
 //
 // Your puzzle answer was 93727241.
 
-use std::fs;
+use std::fmt::Display;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
+
+struct Day9;
+
+pub const SOLUTION: Entry = Entry::new::<Day9>();
+
+impl Solution for Day9 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 9;
+
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        find_invalid(nums, 25).ok_or_else(|| AocError::no_solution("no invalid number").into())
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        let invalid = find_invalid(nums, 25).ok_or_else(|| AocError::no_solution("no invalid number"))?;
+        find_weakness(nums, invalid).ok_or_else(|| AocError::no_solution("no weakness").into())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2020/day9")?;
-    let nums = parse(&input)?;
-    let invalid = find_invalid(&nums, 25).unwrap();
-    println!("{}", invalid);
-
-    let weakness = find_weakness(&nums, invalid).unwrap();
-    println!("{}", weakness);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn parse(s: &str) -> Result<Vec<u64>> {
     s.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| line.parse::<u64>()
-            .context("unable to parse num"))
-        .try_collect()
+        .map(|line| error::parse_at(s, line))
+        .collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
 fn find_invalid(nums: &Vec<u64>, preamble: usize) -> Option<u64> {
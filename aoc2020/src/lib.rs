@@ -1,5 +1,9 @@
+extern crate self as aoc2020;
+
 use std::iter::Enumerate;
 
+use aoc::solution::Entry;
+
 ///
 /// trim_empty for lines
 ///
@@ -110,6 +114,112 @@ pub trait Enumerate2D: Iterator + Sized {
 
 impl<T> Enumerate2D for T where T: Iterator {}
 
+// Each day's binary, also compiled here so that the days can be run together
+
+#[allow(dead_code)]
+#[path = "bin/day1.rs"]
+pub mod day1;
+#[allow(dead_code)]
+#[path = "bin/day2.rs"]
+pub mod day2;
+#[allow(dead_code)]
+#[path = "bin/day3.rs"]
+pub mod day3;
+#[allow(dead_code)]
+#[path = "bin/day4.rs"]
+pub mod day4;
+#[allow(dead_code)]
+#[path = "bin/day5.rs"]
+pub mod day5;
+#[allow(dead_code)]
+#[path = "bin/day6.rs"]
+pub mod day6;
+#[allow(dead_code)]
+#[path = "bin/day7.rs"]
+pub mod day7;
+#[allow(dead_code)]
+#[path = "bin/day8.rs"]
+pub mod day8;
+#[allow(dead_code)]
+#[path = "bin/day9.rs"]
+pub mod day9;
+#[allow(dead_code)]
+#[path = "bin/day10.rs"]
+pub mod day10;
+#[allow(dead_code)]
+#[path = "bin/day11.rs"]
+pub mod day11;
+#[allow(dead_code)]
+#[path = "bin/day12.rs"]
+pub mod day12;
+#[allow(dead_code)]
+#[path = "bin/day13.rs"]
+pub mod day13;
+#[allow(dead_code)]
+#[path = "bin/day14.rs"]
+pub mod day14;
+#[allow(dead_code)]
+#[path = "bin/day15.rs"]
+pub mod day15;
+#[allow(dead_code)]
+#[path = "bin/day16.rs"]
+pub mod day16;
+#[allow(dead_code)]
+#[path = "bin/day17.rs"]
+pub mod day17;
+#[allow(dead_code)]
+#[path = "bin/day18.rs"]
+pub mod day18;
+#[allow(dead_code)]
+#[path = "bin/day19.rs"]
+pub mod day19;
+#[allow(dead_code)]
+#[path = "bin/day20.rs"]
+pub mod day20;
+#[allow(dead_code)]
+#[path = "bin/day21.rs"]
+pub mod day21;
+#[allow(dead_code)]
+#[path = "bin/day22.rs"]
+pub mod day22;
+#[allow(dead_code)]
+#[path = "bin/day23.rs"]
+pub mod day23;
+#[allow(dead_code)]
+#[path = "bin/day24.rs"]
+pub mod day24;
+#[allow(dead_code)]
+#[path = "bin/day25.rs"]
+pub mod day25;
+
+pub const SOLUTIONS: &[Entry] = &[
+    day1::SOLUTION,
+    day2::SOLUTION,
+    day3::SOLUTION,
+    day4::SOLUTION,
+    day5::SOLUTION,
+    day6::SOLUTION,
+    day7::SOLUTION,
+    day8::SOLUTION,
+    day9::SOLUTION,
+    day10::SOLUTION,
+    day11::SOLUTION,
+    day12::SOLUTION,
+    day13::SOLUTION,
+    day14::SOLUTION,
+    day15::SOLUTION,
+    day16::SOLUTION,
+    day17::SOLUTION,
+    day18::SOLUTION,
+    day19::SOLUTION,
+    day20::SOLUTION,
+    day21::SOLUTION,
+    day22::SOLUTION,
+    day23::SOLUTION,
+    day24::SOLUTION,
+    day25::SOLUTION,
+];
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -137,4 +247,10 @@ mod tests {
 
         assert_eq!(out, v.iter().enumerate_2d().collect_vec());
     }
+
+    #[test]
+    fn test_examples() {
+        let root = aoc::input::workspace_root();
+        assert!(aoc::examples::check_all(SOLUTIONS, &root).unwrap() > 0);
+    }
 }
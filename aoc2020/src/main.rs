@@ -1,4 +1,8 @@
-fn main() {}
+use anyhow::Result;
+
+fn main() -> Result<()> {
+    aoc::runner::main(aoc2020::SOLUTIONS)
+}
 
 #[cfg(test)]
 mod tests {
@@ -85,27 +85,47 @@
 //
 // Your puzzle answer was 1805.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day1")?;
-    let nums = parse(&input);
+struct Day1;
+
+pub const SOLUTION: Entry = Entry::new::<Day1>();
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 1;
+
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
 
-    println!("{:?}", part1(&nums));
-    println!("{:?}", part2(&nums));
-    Ok(())
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        Ok(part1(nums))
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        Ok(part2(nums))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<u32> {
+fn parse(s: &str) -> Result<Vec<u32>> {
     s.lines()
         .trim_empty()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect_vec()
+        .map(|line| error::parse_at(s, line))
+        .collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
 fn part1(nums: &Vec<u32>) -> usize {
@@ -138,7 +158,7 @@ mod tests {
         260
         263
         ";
-        let nums = parse(s);
+        let nums = parse(s)?;
         assert_eq!(7, part1(&nums));
         assert_eq!(5, part2(&nums));
         Ok(())
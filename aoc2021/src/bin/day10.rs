@@ -104,27 +104,52 @@
 // Your puzzle answer was 3122628974.
 
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day10;
+
+pub const SOLUTION: Entry = Entry::new::<Day10>();
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 10;
+
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<impl Display> {
+        Ok(solve(lines).0)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<impl Display> {
+        solve(lines).1.ok_or_else(|| AocError::invalid("expected an odd number of incomplete lines").into())
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day10")?;
-    let lines = parse(&input);
-    print!("{:?}", solve(&lines));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<Vec<u8>> {
+fn parse(s: &str) -> Result<Vec<Vec<u8>>> {
     s.lines().trim_empty()
-        .map(|line| line.as_bytes().to_vec())
-        .collect_vec()
+        .map(|line| match line.find(|c| !"()[]{}<>".contains(c)) {
+            Some(i) => Err(AocError::parse(s, &line[i..], "expected a bracket")),
+            None => Ok(line.as_bytes().to_vec()),
+        })
+        .collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
-fn solve(lines: &Vec<Vec<u8>>) -> (u64, u64) {
+fn solve(lines: &Vec<Vec<u8>>) -> (u64, Option<u64>) {
     lazy_static::lazy_static! {
     static ref SCORE1: HashMap<u8, u64> = HashMap::from([
         (b')', 3),
@@ -169,9 +194,11 @@ fn solve(lines: &Vec<Vec<u8>>) -> (u64, u64) {
         });
         scores.push(completion_score);
     }
-    assert_eq!(scores.len() % 2, 1);
+    if scores.len() % 2 == 0 {
+        return (sum, None);
+    }
     scores.sort_unstable();
-    (sum, scores[scores.len() / 2])
+    (sum, Some(scores[scores.len() / 2]))
 }
 
 #[cfg(test)]
@@ -192,8 +219,8 @@ mod tests {
         <{([([[(<>()){}]>(<<{{
         <{([{{}}[<[[[<>{}]]]>[]]
         ";
-        let lines = parse(&s);
-        assert_eq!((26397, 288957), solve(&lines));
+        let lines = parse(&s)?;
+        assert_eq!((26397, Some(288957)), solve(&lines));
         Ok(())
     }
 }
@@ -348,28 +348,65 @@
 // Your puzzle answer was 244.
 
 use std::collections::HashSet;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
-use crate::chargrid::Grid;
-use crate::geom::{Direction, Vector2};
+use aoc::geom::{Direction, Vector2};
+use aoc::render::{Animation, Colour, Palette};
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
+use self::chargrid::Grid;
+
+struct Day11;
+
+pub const SOLUTION: Entry = Entry::new::<Day11>();
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 11;
+
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        if let Some(i) = input.find(|c: char| !c.is_ascii_digit() && !c.is_whitespace()) {
+            return Err(AocError::parse_offset(input, i, "expected a digit").into());
+        }
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display> {
+        Ok(part1(&mut grid.clone(), 100))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display> {
+        let palette = Palette::new()
+            .with('0', Colour::White)
+            .with('9', Colour::Yellow);
+        let mut anim = Animation::from_env()?.with_palette(palette);
+        let res = part2(&mut grid.clone(), &mut anim);
+        anim.finish()?;
+        Ok(res)
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day11")?;
-    let mut grid = Grid::parse(&input).unwrap();
-    println!("{:?}", part1(&mut grid.clone(), 100));
-    println!("{:?}", part2(&mut grid));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 fn part1(grid: &mut Grid, steps: u32) -> u32 {
     (0..steps).map(|_| step(grid)).sum::<u32>()
 }
 
-fn part2(grid: &mut Grid) -> u32 {
+fn part2(grid: &mut Grid, anim: &mut Animation) -> u32 {
     let grid_size = (grid.num_cols * grid.num_rows) as u32;
-    (1..).find(|_| step(grid) == grid_size).unwrap()
+    anim.frame(|| &*grid);
+    (1..).find(|_| {
+        let num_flashed = step(grid);
+        anim.frame(|| &*grid);
+        num_flashed == grid_size
+    }).unwrap()
 }
 
 fn step(grid: &mut Grid) -> u32 {
@@ -390,7 +427,7 @@ fn step(grid: &mut Grid) -> u32 {
     }
 
     for (y, x, c) in grid.iter_mut() {
-        increase_energy(Vector2::new(x, y), c, &mut q, &mut flashed);
+        increase_energy(Vector2::new([x as i64, y as i64]), c, &mut q, &mut flashed);
     }
 
     while !q.is_empty() {
@@ -411,7 +448,7 @@ fn step(grid: &mut Grid) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::chargrid::Grid;
+    use super::chargrid::Grid;
 
     use super::*;
 
@@ -429,9 +466,9 @@ mod tests {
         4846848554
         5283751526
         ";
-        let mut grid = Grid::parse(&s).unwrap();
+        let mut grid = Grid::parse(&s)?;
         assert_eq!(1656, part1(&mut grid.clone(), 100));
-        assert_eq!(195, part2(&mut grid));
+        assert_eq!(195, part2(&mut grid, &mut Animation::disabled()));
         Ok(())
     }
 }
@@ -444,10 +481,10 @@ mod chargrid {
     use anyhow::Result;
     use itertools::Itertools;
 
+    use aoc::error::AocError;
+    use aoc::geom::Vector2;
     use aoc2021::{Enumerate2D, TrimEmpty};
 
-    use crate::geom::Vector2;
-
     #[derive(Clone, Hash, Eq, PartialEq, Debug)]
     pub struct Grid {
         grid: Vec<Vec<u8>>,
@@ -464,26 +501,26 @@ mod chargrid {
         }
 
         pub fn parse(s: &str) -> Result<Grid> {
-            let grid = s.lines()
+            let lines = s.lines()
                 .trim_empty()
-                .map(|line| line.as_bytes().to_vec())
                 .collect_vec();
-            if grid.is_empty() {
-                anyhow::bail!("empty grid");
+            if lines.is_empty() {
+                return Err(AocError::invalid("empty grid").into());
             }
-            let num_rows = grid.len();
-            let num_cols = grid[0].len();
+            let num_rows = lines.len();
+            let num_cols = lines[0].len();
 
-            if grid.iter().any(|row| row.len() != num_cols) {
-                anyhow::bail!("jagged grid");
+            if let Some(line) = lines.iter().find(|line| line.len() != num_cols) {
+                return Err(AocError::parse(s, line, format!("expected {} columns like the first row", num_cols)).into());
             }
+            let grid = lines.iter().map(|line| line.as_bytes().to_vec()).collect();
             Ok(Grid { grid, num_rows, num_cols })
         }
 
         pub fn contains_point(&self, point: &Vector2) -> bool {
-            point.x >= 0 && point.y >= 0
-                && (point.x as usize) < self.num_cols
-                && (point.y as usize) < self.num_rows
+            point.x() >= 0 && point.y() >= 0
+                && (point.x() as usize) < self.num_cols
+                && (point.y() as usize) < self.num_rows
         }
 
         pub fn iter(&self) -> impl Iterator<Item=(usize, usize, &u8)> {
@@ -567,16 +604,16 @@ mod chargrid {
         type Output = u8;
 
         fn index(&self, index: Vector2) -> &Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &self.grid[y][x]
         }
     }
 
     impl IndexMut<Vector2> for Grid {
         fn index_mut(&mut self, index: Vector2) -> &mut Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &mut self.grid[y][x]
         }
     }
@@ -595,169 +632,3 @@ mod chargrid {
         }
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    #[allow(dead_code)]
-    impl Vector2 {
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Sub for Vector2 {
-        type Output = Self;
-
-        fn sub(self, other: Vector2) -> Self {
-            Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        N,
-        NE,
-        E,
-        SE,
-        S,
-        SW,
-        W,
-        NW,
-    }
-
-    #[allow(dead_code)]
-    impl Direction {
-        pub const VALUES_4D: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub const VALUES_8D: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(1, 1),
-                Direction::S => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(-1, -1),
-            }
-        }
-
-        pub fn cw(&self, num_turns: isize) -> Direction {
-            let dir = ((*self as isize) + num_turns) % 8;
-            dir.try_into().unwrap()
-        }
-
-        pub fn ccw(&self, num_turns: isize) -> Direction {
-            let mut dir = ((*self as isize) - num_turns) % 8;
-            if dir < 0 { dir += 8; }
-            dir.try_into().unwrap()
-        }
-    }
-
-    impl TryFrom<isize> for Direction {
-        type Error = ();
-
-        fn try_from(i: isize) -> Result<Self, Self::Error> {
-            match i {
-                x if x == Direction::N as isize => Ok(Direction::N),
-                x if x == Direction::NE as isize => Ok(Direction::NE),
-                x if x == Direction::E as isize => Ok(Direction::E),
-                x if x == Direction::SE as isize => Ok(Direction::SE),
-                x if x == Direction::S as isize => Ok(Direction::S),
-                x if x == Direction::SW as isize => Ok(Direction::SW),
-                x if x == Direction::W as isize => Ok(Direction::W),
-                x if x == Direction::NW as isize => Ok(Direction::NW),
-                _ => Err(()),
-            }
-        }
-    }
-}
@@ -151,85 +151,99 @@
 //
 // Your puzzle answer was 152480.
 
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::collections::HashMap;
+use std::fmt::Display;
 
 use anyhow::Result;
 
+use aoc::error::AocError;
+use aoc::memo::Memo;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day12;
+
+pub const SOLUTION: Entry = Entry::new::<Day12>();
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 12;
+
+    type Input = HashMap<String, Vec<String>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(paths: &Self::Input) -> Result<impl Display> {
+        Ok(count_paths(paths, false))
+    }
+
+    fn part2(paths: &Self::Input) -> Result<impl Display> {
+        Ok(count_paths(paths, true))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day12")?;
-    let paths = parse(&input);
-    let count1 = count_paths(&paths, false);
-    let count2 = count_paths(&paths, true);
-    println!("{:?}", (count1, count2));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> HashMap<&str, Vec<&str>> {
+fn parse(s: &str) -> Result<HashMap<String, Vec<String>>> {
     let mut hm = HashMap::new();
     for line in s.lines().trim_empty() {
-        let (n1, n2) = line.split_once('-').unwrap();
-        hm.entry(n1).or_insert(vec![]).push(n2);
-        hm.entry(n2).or_insert(vec![]).push(n1);
+        let (n1, n2) = line.split_once('-')
+            .filter(|(n1, n2)| [n1, n2].iter().all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_alphabetic())))
+            .ok_or_else(|| AocError::parse(s, line, "expected a path such as start-A"))?;
+        if !is_small_cave(n1) && !is_small_cave(n2) {
+            return Err(AocError::parse(s, line, "two big caves can't be connected").into());
+        }
+        hm.entry(n1.to_string()).or_insert(vec![]).push(n2.to_string());
+        hm.entry(n2.to_string()).or_insert(vec![]).push(n1.to_string());
     }
-    hm
-}
-
-fn count_paths(paths: &HashMap<&str, Vec<&str>>, can_revisit: bool) -> u32 {
-    struct State<'a, 'b> {
-        node: &'a str,
-        visited_nodes: &'b mut HashSet<&'a str>,
-        used_revisit: bool,
+    if hm.len() > 64 {
+        return Err(AocError::invalid(format!("expected at most 64 caves, got {}", hm.len())).into());
     }
+    if !hm.contains_key("start") {
+        return Err(AocError::invalid("no path from start").into());
+    }
+    Ok(hm)
+}
 
-    return count_paths_rec(
-        State { node: "start", visited_nodes: &mut HashSet::new(), used_revisit: false },
-        paths, can_revisit);
+fn count_paths(paths: &HashMap<String, Vec<String>>, can_revisit: bool) -> u32 {
+    // number the caves, so the small caves visited so far fit in a bitmask
+    let names = paths.keys().map(String::as_str).collect::<Vec<_>>();
+    let index = |name: &str| names.iter().position(|&n| n == name);
+    let adj = names.iter()
+        .map(|&name| paths[name].iter().filter_map(|n| index(n)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let small = names.iter().enumerate()
+        .filter(|&(_, &name)| is_small_cave(name))
+        .fold(0_u64, |mask, (i, _)| mask | 1 << i);
+    let (start, end) = match (index("start"), index("end")) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return 0,
+    };
 
-    fn count_paths_rec<'a, 'b>(state: State<'a, 'b>,
-                               paths: &HashMap<&str, Vec<&'a str>>,
-                               can_revisit: bool) -> u32
-    {
-        if state.node == "end" {
+    // paths to the end from a cave, given the small caves visited and whether one was revisited
+    let mut memo = Memo::new();
+    memo.solve((start, 1 << start, !can_revisit), &|&(cave, visited, revisited), count: &mut dyn FnMut((usize, u64, bool)) -> u32| {
+        if cave == end {
             return 1;
         }
-
-        let mut count = 0;
-        let adj_nodes = paths.get(state.node).unwrap();
-        for &node in adj_nodes {
-            let visited_node_before = state.visited_nodes.contains(node);
-            if node != "start" &&
-                ((!can_revisit && !visited_node_before) ||
-                    (can_revisit && !state.used_revisit) ||
-                    (can_revisit && state.used_revisit && !visited_node_before))
-            {
-                if is_small_cave(node) {
-                    state.visited_nodes.insert(node);
-                }
-                let (using_revisit, used_revisit) =
-                    if can_revisit && !state.used_revisit && visited_node_before {
-                        (true, true)
-                    } else {
-                        (false, state.used_revisit)
-                    };
-
-                count += count_paths_rec(
-                    State { node, visited_nodes: state.visited_nodes, used_revisit },
-                    paths, can_revisit);
-
-                if !using_revisit {
-                    state.visited_nodes.remove(node);
-                }
-            }
-        }
-        count
-    }
+        adj[cave].iter()
+            .filter(|&&next| next != start)
+            .filter_map(|&next| match (visited >> next & 1 == 1, revisited) {
+                (false, _) => Some((next, visited | (small & 1 << next), revisited)),
+                (true, false) => Some((next, visited, true)),
+                (true, true) => None,
+            })
+            .map(|state| count(state))
+            .sum()
+    })
 }
 
 fn is_small_cave(node: &str) -> bool {
-    node.chars().next().unwrap().is_lowercase()
+    node.starts_with(|c: char| c.is_lowercase())
 }
 
 #[cfg(test)]
@@ -258,7 +272,7 @@ mod tests {
         pj-fs
         start-RW
         ";
-        let paths = parse(&s);
+        let paths = parse(&s)?;
         assert_eq!(226, count_paths(&paths, false));
         assert_eq!(3509, count_paths(&paths, true));
         Ok(())
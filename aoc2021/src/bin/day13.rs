@@ -121,62 +121,105 @@
 // Your puzzle answer was PERCGJPB.
 
 use std::collections::HashSet;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::geom::{Vector, Vector2};
+use aoc::ocr;
+use aoc::render::{Animation, Colour, Palette};
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
-use crate::geom::Vector2;
+
+struct Day13;
+
+pub const SOLUTION: Entry = Entry::new::<Day13>();
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 13;
+
+    type Input = (HashSet<Vector2>, Vec<Fold>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((points, folds): &Self::Input) -> Result<impl Display> {
+        part1(points, folds)
+    }
+
+    fn part2((points, folds): &Self::Input) -> Result<impl Display> {
+        let mut anim = Animation::from_env()?.with_palette(Palette::new().with('#', Colour::Yellow));
+        let res = part2(points, folds, &mut anim)?;
+        anim.finish()?;
+        Ok(res)
+    }
+
+    fn render((points, folds): &Self::Input, part: u8) -> Result<Option<String>> {
+        if part != 2 {
+            return Ok(None);
+        }
+        let ps = fold_all(points, folds, &mut Animation::disabled());
+        Ok(Some(ocr::draw_points(ps.iter().map(|p| (p.x(), p.y())))?))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day13")?;
-    let (points, folds) = parse(&input);
-    println!("{:?}", part1(&points, &folds));
-    part2(&points, &folds);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (HashSet<Vector2>, Vec<Fold>) {
+fn parse(s: &str) -> Result<(HashSet<Vector2>, Vec<Fold>)> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^fold along (x|y)=(\d+)$").unwrap();
     }
-    let (points, folds) = s.split_once("\n\n").unwrap();
+    let (points, folds) = s.split_once("\n\n")
+        .ok_or_else(|| AocError::invalid("expected a blank line between the dots and the folds"))?;
     let points = points.lines().trim_empty().map(|line| {
-        let (x, y) = line.split_once(',').unwrap();
-        let x = x.parse::<i32>().unwrap();
-        let y = y.parse::<i32>().unwrap();
-        Vector2::new(x, y)
-    }).collect();
+        let (x, y) = line.split_once(',').ok_or_else(|| AocError::parse(s, line, "expected a dot such as 6,10"))?;
+        let x = error::parse_at::<u16>(s, x)?;
+        let y = error::parse_at::<u16>(s, y)?;
+        Ok(Vector2::new([x, y]).map(i64::from))
+    }).collect::<Result<_, AocError>>()?;
 
     let folds = folds.lines().trim_empty().map(|line| {
-        let caps = RE.captures(line).unwrap();
+        let caps = RE.captures(line).ok_or_else(|| AocError::parse(s, line, "expected a fold such as fold along y=7"))?;
         let foldtype = match &caps[1] {
             "x" => FoldType::VERTICAL,
             "y" => FoldType::HORIZONTAL,
             _ => unreachable!()
         };
-        let val = caps[2].parse().unwrap();
-        Fold { foldtype, val }
-    }).collect_vec();
+        let val = error::parse_at(s, caps.get(2).unwrap().as_str())?;
+        Ok(Fold { foldtype, val })
+    }).collect::<Result<Vec<_>, AocError>>()?;
 
-    (points, folds)
+    Ok((points, folds))
 }
 
-fn part1(points: &HashSet<Vector2>, folds: &Vec<Fold>) -> usize {
-    fold(points, folds.first().unwrap()).len()
+fn part1(points: &HashSet<Vector2>, folds: &Vec<Fold>) -> Result<usize> {
+    let first = folds.first().ok_or_else(|| AocError::invalid("no folds"))?;
+    Ok(fold(points, first).len())
 }
 
-fn part2(points: &HashSet<Vector2>, folds: &Vec<Fold>) {
-    let ps = folds.iter().fold(None, |ps, f| {
-        match ps {
-            None => Some(fold(points, f)),
-            Some(ps) => Some(fold(&ps, f)),
-        }
-    }).unwrap();
-    pprint(&ps);
+fn part2(points: &HashSet<Vector2>, folds: &Vec<Fold>, anim: &mut Animation) -> Result<String> {
+    let ps = fold_all(points, folds, anim);
+    let res = ocr::recognize_points(ps.iter().map(|p| (p.x(), p.y())))?;
+    Ok(res)
+}
+
+fn fold_all(points: &HashSet<Vector2>, folds: &Vec<Fold>, anim: &mut Animation) -> HashSet<Vector2> {
+    anim.frame(|| render(points));
+    folds.iter().fold(None, |ps, f| {
+        let ps = match ps {
+            None => fold(points, f),
+            Some(ps) => fold(&ps, f),
+        };
+        anim.frame(|| render(&ps));
+        Some(ps)
+    }).unwrap_or_else(|| points.clone())
 }
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
@@ -192,27 +235,33 @@ struct Fold {
 }
 
 fn fold(points: &HashSet<Vector2>, fold: &Fold) -> HashSet<Vector2> {
-    points.iter().map(|&Vector2 { x, y }| {
+    points.iter().map(|&Vector([x, y])| {
         match fold.foldtype {
-            FoldType::HORIZONTAL if y > fold.val => Vector2::new(x, (2 * fold.val) - y),
-            FoldType::VERTICAL if x > fold.val => Vector2::new((2 * fold.val) - x, y),
-            _ => Vector2::new(x, y),
+            FoldType::HORIZONTAL if y > fold.val => Vector2::new([x, (2 * fold.val) - y]),
+            FoldType::VERTICAL if x > fold.val => Vector2::new([(2 * fold.val) - x, y]),
+            _ => Vector2::new([x, y]),
         }
     }).collect()
 }
 
-fn pprint(points: &HashSet<Vector2>) {
-    let max_y = points.iter().map(|p| p.y).max().unwrap() as usize;
-    let max_x = points.iter().map(|p| p.x).max().unwrap() as usize;
-    let mut screen = vec![vec![b' '; max_x + 1]; max_y + 1];
+fn render(points: &HashSet<Vector2>) -> String {
+    if points.is_empty() {
+        return String::new();
+    }
+    // folding past the middle of the paper can leave dots left of or above the origin
+    let min_y = points.iter().map(|p| p.y()).min().unwrap();
+    let min_x = points.iter().map(|p| p.x()).min().unwrap();
+    let max_y = points.iter().map(|p| p.y()).max().unwrap();
+    let max_x = points.iter().map(|p| p.x()).max().unwrap();
+    let mut screen = vec![vec![b' '; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
 
     for point in points {
-        screen[point.y as usize][point.x as usize] = b'#';
+        screen[(point.y() - min_y) as usize][(point.x() - min_x) as usize] = b'#';
     }
 
     screen.into_iter()
-        .map(|row| String::from_utf8(row).unwrap())
-        .for_each(|s| println!("{}", s));
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
 }
 
 #[cfg(test)]
@@ -244,175 +293,65 @@ mod tests {
         fold along y=7
         fold along x=5
         ";
-        let (points, folds) = parse(&s);
-        assert_eq!(17, part1(&points, &folds));
-        part2(&points, &folds);
+        let (points, folds) = parse(&s)?;
+        assert_eq!(17, part1(&points, &folds)?);
+        let mut anim = Animation::disabled();
+        assert_eq!(16, fold_all(&points, &folds, &mut anim).len());
+        assert!(part2(&points, &folds, &mut anim).is_err());
         Ok(())
     }
-}
-
-mod geom {
-    use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    #[allow(dead_code)]
-    impl Vector2 {
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Sub for Vector2 {
-        type Output = Self;
-
-        fn sub(self, other: Vector2) -> Self {
-            Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        N,
-        NE,
-        E,
-        SE,
-        S,
-        SW,
-        W,
-        NW,
-    }
-
-    #[allow(dead_code)]
-    impl Direction {
-        pub const VALUES_4D: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub const VALUES_8D: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(1, 1),
-                Direction::S => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(-1, -1),
-            }
-        }
-
-        pub fn cw(&self, num_turns: isize) -> Direction {
-            let dir = ((*self as isize) + num_turns) % 8;
-            dir.try_into().unwrap()
-        }
-
-        pub fn ccw(&self, num_turns: isize) -> Direction {
-            let mut dir = ((*self as isize) - num_turns) % 8;
-            if dir < 0 { dir += 8; }
-            dir.try_into().unwrap()
-        }
+    #[test]
+    fn test_letters() -> Result<()> {
+        // "ZIP" split over the four quarters of the paper, so both folds are needed to read it
+        let s = r"
+        0,0
+        1,12
+        24,0
+        23,12
+        5,0
+        6,12
+        19,0
+        17,12
+        10,0
+        11,12
+        23,1
+        20,11
+        9,1
+        12,11
+        24,2
+        20,10
+        9,2
+        12,10
+        25,3
+        20,9
+        9,3
+        10,9
+        15,3
+        26,8
+        6,4
+        9,8
+        26,5
+        25,7
+        2,5
+        3,7
+        21,5
+        20,7
+        7,5
+        9,7
+
+        fold along y=6
+        fold along x=13
+        ";
+        let (points, folds) = parse(s)?;
+        let mut anim = Animation::disabled();
+        assert_eq!("ZIP", part2(&points, &folds, &mut anim)?);
+        Ok(())
     }
 
-    impl TryFrom<isize> for Direction {
-        type Error = ();
-
-        fn try_from(i: isize) -> Result<Self, Self::Error> {
-            match i {
-                x if x == Direction::N as isize => Ok(Direction::N),
-                x if x == Direction::NE as isize => Ok(Direction::NE),
-                x if x == Direction::E as isize => Ok(Direction::E),
-                x if x == Direction::SE as isize => Ok(Direction::SE),
-                x if x == Direction::S as isize => Ok(Direction::S),
-                x if x == Direction::SW as isize => Ok(Direction::SW),
-                x if x == Direction::W as isize => Ok(Direction::W),
-                x if x == Direction::NW as isize => Ok(Direction::NW),
-                _ => Err(()),
-            }
-        }
+    #[test]
+    fn test_render() {
+        assert_eq!("", render(&HashSet::new()));
+        let points = [[-1, 0], [1, -1]].iter().map(|&p| Vector2::new(p)).collect();
+        assert_eq!("  #\n#  \n", render(&points));
     }
 }
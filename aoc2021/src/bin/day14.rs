@@ -65,57 +65,109 @@
 // Your puzzle answer was 2843834241366.
 
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
+use aoc::error::AocError;
+use aoc::matrix::Matrix;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day14;
+
+pub const SOLUTION: Entry = Entry::new::<Day14>();
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 14;
+
+    type Input = (String, HashMap<[u8; 2], u8>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((template, rules): &Self::Input) -> Result<impl Display> {
+        solve(template, rules, 10)
+    }
+
+    fn part2((template, rules): &Self::Input) -> Result<impl Display> {
+        solve(template, rules, 40)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day14")?;
-    let (template, rules) = parse(&input);
-    println!("{:?}", solve(&template, &rules, 10));
-    println!("{:?}", solve(&template, &rules, 40));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (String, HashMap<[u8; 2], u8>) {
-    let (template, rules) = s.trim().split_once("\n\n").unwrap();
+fn parse(s: &str) -> Result<(String, HashMap<[u8; 2], u8>)> {
+    let (template, rules) = s.trim().split_once("\n\n")
+        .ok_or_else(|| AocError::invalid("expected a blank line between the template and the rules"))?;
 
+    if let Some(i) = template.find(|c: char| !c.is_ascii_uppercase()) {
+        return Err(AocError::parse(s, &template[i..], "expected an element from A to Z").into());
+    }
     let template = template.to_string();
     let rules = rules.lines().trim_empty().map(|rule| {
-        let (lhs, rhs) = rule.split_once(" -> ").unwrap();
-        let lhs = lhs.as_bytes();
-        ([lhs[0], lhs[1]], rhs.as_bytes()[0])
-    }).collect();
+        match rule.split_once(" -> ").map(|(lhs, rhs)| (lhs.as_bytes(), rhs.as_bytes())) {
+            Some((&[l0, l1], &[r])) if [l0, l1, r].iter().all(u8::is_ascii_uppercase) => Ok(([l0, l1], r)),
+            _ => Err(AocError::parse(s, rule, "expected a rule such as CH -> B")),
+        }
+    }).collect::<Result<_, AocError>>()?;
 
-    (template, rules)
+    Ok((template, rules))
 }
 
-fn solve(template: &String, rules: &HashMap<[u8; 2], u8>, num_steps: u8) -> u64 {
-    let mut counts = HashMap::new();
-    for &c in template.as_bytes().iter() {
-        *counts.entry(c).or_insert(0) += 1;
-    }
+///
+/// Pairs of the elements in play and the step taking each pair to the pairs it splits into
+///
+fn pair_step(template: &str, rules: &HashMap<[u8; 2], u8>) -> (Vec<u8>, Matrix<u64>) {
+    let mut elements = template.bytes()
+        .chain(rules.iter().flat_map(|(&[l0, l1], &r)| [l0, l1, r]))
+        .collect::<Vec<_>>();
+    elements.sort_unstable();
+    elements.dedup();
 
-    let mut pairs = HashMap::new();
+    let idx = |c: u8| elements.binary_search(&c).unwrap();
+    let pair_idx = |l: u8, r: u8| idx(l) * elements.len() + idx(r);
+    let transitions = elements.iter()
+        .flat_map(|&l| elements.iter().map(move |&r| (l, r)))
+        .flat_map(|(l, r)| match rules.get(&[l, r]) {
+            Some(&mid) => vec![(pair_idx(l, r), pair_idx(l, mid)), (pair_idx(l, r), pair_idx(mid, r))],
+            None => vec![(pair_idx(l, r), pair_idx(l, r))],
+        })
+        .collect::<Vec<_>>();
+    let num_pairs = elements.len() * elements.len();
+    (elements, Matrix::transitions(num_pairs, transitions))
+}
+
+fn solve(template: &String, rules: &HashMap<[u8; 2], u8>, num_steps: u64) -> Result<u64> {
+    let (elements, step) = pair_step(template, rules);
+    let mut pairs = vec![0; elements.len() * elements.len()];
     for c in template.as_bytes().windows(2) {
-        *pairs.entry([c[0], c[1]]).or_insert(0) += 1;
+        let (l, r) = (elements.binary_search(&c[0]).unwrap(), elements.binary_search(&c[1]).unwrap());
+        pairs[l * elements.len() + r] += 1;
     }
+    let too_long = || AocError::invalid(format!("the polymer is too long to count after {} steps", num_steps));
+    let pairs = step.checked_pow(num_steps)
+        .and_then(|m| m.checked_mul_vec(&pairs))
+        .ok_or_else(too_long)?;
 
-    for _ in 0..num_steps {
-        let mut pairs2 = HashMap::new();
-        for (pair, &num) in pairs.iter() {
-            let mid = rules[pair];
-            *counts.entry(mid).or_insert(0) += num;
-            *pairs2.entry([pair[0], mid]).or_insert(0) += num;
-            *pairs2.entry([mid, pair[1]]).or_insert(0) += num;
-        }
-        pairs = pairs2;
+    // every element starts a pair except the last, which never moves
+    let mut counts = vec![0_u64; elements.len()];
+    for (i, &num) in pairs.iter().enumerate() {
+        let l = i / elements.len();
+        counts[l] = counts[l].checked_add(num).ok_or_else(too_long)?;
+    }
+    if let Some(&last) = template.as_bytes().last() {
+        let last = elements.binary_search(&last).unwrap();
+        counts[last] = counts[last].checked_add(1).ok_or_else(too_long)?;
     }
-    let max = counts.values().max().unwrap();
-    let min = counts.values().min().unwrap();
-    max - min
+    let counts = counts.into_iter().filter(|&n| n > 0).collect::<Vec<_>>();
+    let max = counts.iter().max().unwrap_or(&0);
+    let min = counts.iter().min().unwrap_or(&0);
+    Ok(max - min)
 }
 
 #[cfg(test)]
@@ -144,9 +196,10 @@ mod tests {
         CC -> N
         CN -> C
         ";
-        let (template, rules) = parse(&s);
-        assert_eq!(1588, solve(&template, &rules, 10));
-        assert_eq!(2188189693529, solve(&template, &rules, 40));
+        let (template, rules) = parse(&s)?;
+        assert_eq!(1588, solve(&template, &rules, 10)?);
+        assert_eq!(2188189693529, solve(&template, &rules, 40)?);
+        assert!(solve(&template, &rules, 1_000_000_000_000).is_err());
         Ok(())
     }
 }
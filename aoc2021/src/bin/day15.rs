@@ -165,36 +165,61 @@
 
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashSet};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
-use crate::chargrid::Grid;
-use crate::geom::{Direction, Vector2};
+use aoc::error::AocError;
+use aoc::geom::{Direction, Vector2};
+use aoc::solution::{Entry, Solution};
+
+use self::chargrid::Grid;
+
+struct Day15;
+
+pub const SOLUTION: Entry = Entry::new::<Day15>();
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 15;
+
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(grid: &Self::Input) -> Result<impl Display> {
+        Ok(solve(grid, false))
+    }
+
+    fn part2(grid: &Self::Input) -> Result<impl Display> {
+        Ok(solve(grid, true))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day15")?;
-    let grid = parse(&input);
-    println!("{:?}", solve(&grid, false));
-    println!("{:?}", solve(&grid, true));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Grid {
-    let mut grid = Grid::parse(s).unwrap();
+fn parse(s: &str) -> Result<Grid> {
+    if let Some(i) = s.find(|c: char| !c.is_ascii_digit() && !c.is_whitespace()) {
+        return Err(AocError::parse_offset(s, i, "expected a digit").into());
+    }
+    let mut grid = Grid::parse(s)?;
     for (_, _, c) in grid.iter_mut() {
         *c -= b'0';
     }
-    grid
+    Ok(grid)
 }
 
 fn solve(grid: &Grid, big_grid: bool) -> u32 {
     let mut visited = HashSet::new();
     let mut min_heap = BinaryHeap::new();
-    min_heap.push((Reverse(0), Vector2::new(0, 0)));
+    min_heap.push((Reverse(0), Vector2::zero()));
     let target = match big_grid {
-        true => Vector2::new((5 * grid.num_cols) - 1, (5 * grid.num_rows) - 1),
-        false => Vector2::new(grid.num_cols - 1, grid.num_rows - 1),
+        true => Vector2::new([(5 * grid.num_cols) as i64 - 1, (5 * grid.num_rows) as i64 - 1]),
+        false => Vector2::new([grid.num_cols as i64 - 1, grid.num_rows as i64 - 1]),
     };
 
     loop {
@@ -209,13 +234,13 @@ fn solve(grid: &Grid, big_grid: bool) -> u32 {
         for np in Direction::VALUES_4D.iter().map(|dir| pos + dir.dxdy())
         {
             // @formatter:off
-            if !visited.contains(&np) && np.x >= 0 && np.y >= 0 &&
+            if !visited.contains(&np) && np.x() >= 0 && np.y() >= 0 &&
                 ((!big_grid &&
-                    (np.x as usize) < grid.num_cols &&
-                    (np.y as usize) < grid.num_rows) ||
+                    (np.x() as usize) < grid.num_cols &&
+                    (np.y() as usize) < grid.num_rows) ||
                 (big_grid &&
-                    (np.x as usize) < (5 * grid.num_cols) &&
-                    (np.y as usize) < (5 * grid.num_rows)))
+                    (np.x() as usize) < (5 * grid.num_cols) &&
+                    (np.y() as usize) < (5 * grid.num_rows)))
             {
                 min_heap.push((Reverse(cost + get_cost(&grid, np, big_grid)), np));
             }
@@ -228,7 +253,7 @@ fn get_cost(grid: &Grid, p: Vector2, big_grid: bool) -> u32 {
     if !big_grid {
         grid[p] as u32
     } else {
-        let (x, y) = (p.x as usize, p.y as usize);
+        let (x, y) = (p.x() as usize, p.y() as usize);
         (((grid[y % grid.num_rows][x % grid.num_cols]) as u32
             + (x / grid.num_cols) as u32
             + (y / grid.num_rows) as u32 - 1) % 9) + 1
@@ -253,7 +278,7 @@ mod tests {
         1293138521
         2311944581
         ";
-        let grid = parse(&s);
+        let grid = parse(&s)?;
         assert_eq!(40, solve(&grid, false));
         assert_eq!(315, solve(&grid, true));
         Ok(())
@@ -268,10 +293,10 @@ mod chargrid {
     use anyhow::Result;
     use itertools::Itertools;
 
+    use aoc::error::AocError;
+    use aoc::geom::Vector2;
     use aoc2021::{Enumerate2D, TrimEmpty};
 
-    use crate::geom::Vector2;
-
     #[derive(Clone, Hash, Eq, PartialEq, Debug)]
     pub struct Grid {
         grid: Vec<Vec<u8>>,
@@ -288,26 +313,26 @@ mod chargrid {
         }
 
         pub fn parse(s: &str) -> Result<Grid> {
-            let grid = s.lines()
+            let lines = s.lines()
                 .trim_empty()
-                .map(|line| line.as_bytes().to_vec())
                 .collect_vec();
-            if grid.is_empty() {
-                anyhow::bail!("empty grid");
+            if lines.is_empty() {
+                return Err(AocError::invalid("empty grid").into());
             }
-            let num_rows = grid.len();
-            let num_cols = grid[0].len();
+            let num_rows = lines.len();
+            let num_cols = lines[0].len();
 
-            if grid.iter().any(|row| row.len() != num_cols) {
-                anyhow::bail!("jagged grid");
+            if let Some(line) = lines.iter().find(|line| line.len() != num_cols) {
+                return Err(AocError::parse(s, line, format!("expected {} columns like the first row", num_cols)).into());
             }
+            let grid = lines.iter().map(|line| line.as_bytes().to_vec()).collect();
             Ok(Grid { grid, num_rows, num_cols })
         }
 
         pub fn contains_point(&self, point: &Vector2) -> bool {
-            point.x >= 0 && point.y >= 0
-                && (point.x as usize) < self.num_cols
-                && (point.y as usize) < self.num_rows
+            point.x() >= 0 && point.y() >= 0
+                && (point.x() as usize) < self.num_cols
+                && (point.y() as usize) < self.num_rows
         }
 
         pub fn iter(&self) -> impl Iterator<Item=(usize, usize, &u8)> {
@@ -391,16 +416,16 @@ mod chargrid {
         type Output = u8;
 
         fn index(&self, index: Vector2) -> &Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &self.grid[y][x]
         }
     }
 
     impl IndexMut<Vector2> for Grid {
         fn index_mut(&mut self, index: Vector2) -> &mut Self::Output {
-            let y = index.y as usize;
-            let x = index.x as usize;
+            let y = index.y() as usize;
+            let x = index.x() as usize;
             &mut self.grid[y][x]
         }
     }
@@ -419,169 +444,3 @@ mod chargrid {
         }
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    #[allow(dead_code)]
-    impl Vector2 {
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Sub for Vector2 {
-        type Output = Self;
-
-        fn sub(self, other: Vector2) -> Self {
-            Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-    pub enum Direction {
-        N,
-        NE,
-        E,
-        SE,
-        S,
-        SW,
-        W,
-        NW,
-    }
-
-    #[allow(dead_code)]
-    impl Direction {
-        pub const VALUES_4D: [Direction; 4] = [
-            Direction::N,
-            Direction::E,
-            Direction::S,
-            Direction::W,
-        ];
-
-        pub const VALUES_8D: [Direction; 8] = [
-            Direction::N,
-            Direction::NE,
-            Direction::E,
-            Direction::SE,
-            Direction::S,
-            Direction::SW,
-            Direction::W,
-            Direction::NW,
-        ];
-
-        pub fn dxdy(&self) -> Vector2 {
-            match self {
-                Direction::N => Vector2::new(0, -1),
-                Direction::NE => Vector2::new(1, -1),
-                Direction::E => Vector2::new(1, 0),
-                Direction::SE => Vector2::new(1, 1),
-                Direction::S => Vector2::new(0, 1),
-                Direction::SW => Vector2::new(-1, 1),
-                Direction::W => Vector2::new(-1, 0),
-                Direction::NW => Vector2::new(-1, -1),
-            }
-        }
-
-        pub fn cw(&self, num_turns: isize) -> Direction {
-            let dir = ((*self as isize) + num_turns) % 8;
-            dir.try_into().unwrap()
-        }
-
-        pub fn ccw(&self, num_turns: isize) -> Direction {
-            let mut dir = ((*self as isize) - num_turns) % 8;
-            if dir < 0 { dir += 8; }
-            dir.try_into().unwrap()
-        }
-    }
-
-    impl TryFrom<isize> for Direction {
-        type Error = ();
-
-        fn try_from(i: isize) -> Result<Self, Self::Error> {
-            match i {
-                x if x == Direction::N as isize => Ok(Direction::N),
-                x if x == Direction::NE as isize => Ok(Direction::NE),
-                x if x == Direction::E as isize => Ok(Direction::E),
-                x if x == Direction::SE as isize => Ok(Direction::SE),
-                x if x == Direction::S as isize => Ok(Direction::S),
-                x if x == Direction::SW as isize => Ok(Direction::SW),
-                x if x == Direction::W as isize => Ok(Direction::W),
-                x if x == Direction::NW as isize => Ok(Direction::NW),
-                _ => Err(()),
-            }
-        }
-    }
-}
@@ -126,21 +126,43 @@
 //
 // Your puzzle answer was 9485076995911.
 
-use std::fs;
+use std::fmt::Display;
 use std::iter::successors;
 
 use anyhow::Result;
-use itertools::FoldWhile::{Continue, Done};
 use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
+
+struct Day16;
+
+pub const SOLUTION: Entry = Entry::new::<Day16>();
+
+impl Solution for Day16 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 16;
+
+    type Input = Packet;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut bs = get_bitstream(input.trim())?;
+        let p = parse_packet(&mut bs)?;
+        flush(&mut bs)?;
+        Ok(p)
+    }
+
+    fn part1(p: &Self::Input) -> Result<impl Display> {
+        Ok(sum_version(p))
+    }
+
+    fn part2(p: &Self::Input) -> Result<impl Display> {
+        Ok(p.val)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day16")?;
-    let mut bs = get_bitstream(input.trim());
-    let p = parse_packet(&mut bs);
-    flush(&mut bs);
-    println!("{:?}", sum_version(&p));
-    println!("{:?}", p.val);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
 #[derive(Eq, PartialEq, Hash, Clone, Debug)]
@@ -152,39 +174,41 @@ struct Packet {
     childs: Vec<Packet>,
 }
 
-fn parse_packet(bs: &mut impl Iterator<Item=u8>) -> Packet {
-    let version = read_val(bs, 3);
-    let op = read_val(bs, 3);
+fn parse_packet(bs: &mut impl Iterator<Item=u8>) -> Result<Packet, AocError> {
+    let version = read_val(bs, 3)?;
+    let op = read_val(bs, 3)?;
     let mut len = 6;
 
     if op == 4 {
-        let (slen, val) = parse_literal(bs);
-        return Packet { version, op, len: (len + slen), val, childs: vec![] };
+        let (slen, val) = parse_literal(bs)?;
+        return Ok(Packet { version, op, len: (len + slen), val, childs: vec![] });
     }
 
-    let i = bs.next().unwrap();
+    let i = read_val(bs, 1)?;
     len += 1;
 
     let mut childs = vec![];
     match i {
         0 => {
-            let l = read_val(bs, 15) as usize;
+            let l = read_val(bs, 15)? as usize;
             len += 15;
 
             let target = len + l;
             while len < target {
-                let packet = parse_packet(bs);
+                let packet = parse_packet(bs)?;
                 len += packet.len;
                 childs.push(packet);
             }
-            assert_eq!(len, target);
+            if len != target {
+                return Err(AocError::invalid(format!("sub-packets overrun their length of {} bits", l)));
+            }
         }
         1 => {
-            let l = read_val(bs, 11) as usize;
+            let l = read_val(bs, 11)? as usize;
             len += 11;
 
             for _ in 0..l {
-                let packet = parse_packet(bs);
+                let packet = parse_packet(bs)?;
                 len += packet.len;
                 childs.push(packet);
             }
@@ -192,54 +216,67 @@ fn parse_packet(bs: &mut impl Iterator<Item=u8>) -> Packet {
         _ => unreachable!(),
     }
 
-    let val = match op {
-        0 => childs.iter().map(|c| c.val).sum(),
-        1 => childs.iter().map(|c| c.val).product(),
-        2 => childs.iter().map(|c| c.val).min().unwrap(),
-        3 => childs.iter().map(|c| c.val).max().unwrap(),
-        5 => (childs[0].val > childs[1].val) as u64,
-        6 => (childs[0].val < childs[1].val) as u64,
-        7 => (childs[0].val == childs[1].val) as u64,
-        _ => unreachable!(),
+    let vals = childs.iter().map(|c| c.val).collect_vec();
+    let val = match (op, &vals[..]) {
+        (_, []) => return Err(AocError::invalid(format!("operator {} has no sub-packets", op))),
+        (0, _) => vals.iter().sum(),
+        (1, _) => vals.iter().product(),
+        (2, _) => *vals.iter().min().unwrap(),
+        (3, _) => *vals.iter().max().unwrap(),
+        (5, &[a, b]) => (a > b) as u64,
+        (6, &[a, b]) => (a < b) as u64,
+        (7, &[a, b]) => (a == b) as u64,
+        _ => return Err(AocError::invalid(format!("operator {} expects 2 sub-packets, not {}", op, vals.len()))),
     };
-    Packet { version, op, len, val, childs }
+    Ok(Packet { version, op, len, val, childs })
 }
 
 fn sum_version(p: &Packet) -> u64 {
     p.version + p.childs.iter().map(sum_version).sum::<u64>()
 }
 
-fn parse_literal(bs: &mut impl Iterator<Item=u8>) -> (usize, u64) {
-    let (i, v) = bs.tuples()
-        .fold_while((0, 0), |(i, acc), (sentinel, b3, b2, b1, b0)| {
-            let v = (b3 << 3) + (b2 << 2) + (b1 << 1) + b0;
-            let acc = (acc << 4) + (v as u64);
-            match sentinel {
-                1 => Continue((i + 1, acc)),
-                0 => Done((i + 1, acc)),
-                _ => unreachable!(),
-            }
-        }).into_inner();
-    (i * 5, v)
+fn parse_literal(bs: &mut impl Iterator<Item=u8>) -> Result<(usize, u64), AocError> {
+    let (mut i, mut v) = (0, 0u64);
+    loop {
+        let group = read_val(bs, 5)?;
+        i += 1;
+        v = v.checked_mul(16)
+            .ok_or_else(|| AocError::invalid("literal doesn't fit in 64 bits"))?
+            + (group & 0xf);
+        if group & 0x10 == 0 {
+            return Ok((i * 5, v));
+        }
+    }
 }
 
-fn get_bitstream<'a>(s: &'a str) -> impl Iterator<Item=u8> + 'a {
-    s.chars().flat_map(|c| {
+fn get_bitstream(s: &str) -> Result<impl Iterator<Item=u8> + '_, AocError> {
+    if let Some(i) = s.find(|c: char| !c.is_ascii_hexdigit()) {
+        return Err(AocError::parse_offset(s, i, "expected a hexadecimal digit"));
+    }
+    Ok(s.chars().flat_map(|c| {
         let c = c.to_digit(16).unwrap() as u8;
         successors(Some(0x8), |&mask| {
             let mask = mask >> 1;
             if mask > 0 { Some(mask) } else { None }
         }).map(move |mask| ((mask & c) > 0) as u8)
-    })
+    }))
 }
 
-fn read_val(bs: &mut impl Iterator<Item=u8>, num_bits: usize) -> u64 {
-    bs.take(num_bits)
-        .fold(0, |acc, b| (acc << 1) + (b as u64))
+fn read_val(bs: &mut impl Iterator<Item=u8>, num_bits: usize) -> Result<u64, AocError> {
+    let mut val = 0;
+    for _ in 0..num_bits {
+        let b = bs.next().ok_or_else(|| AocError::invalid("transmission ends inside a packet"))?;
+        val = (val << 1) + (b as u64);
+    }
+    Ok(val)
 }
 
-fn flush(bs: &mut impl Iterator<Item=u8>) {
-    assert!(bs.all(|b| b == 0));
+fn flush(bs: &mut impl Iterator<Item=u8>) -> Result<(), AocError> {
+    if bs.all(|b| b == 0) {
+        Ok(())
+    } else {
+        Err(AocError::invalid("trailing bits after the outermost packet"))
+    }
 }
 
 #[cfg(test)]
@@ -248,26 +285,26 @@ mod tests {
 
     #[test]
     fn test() -> Result<()> {
-        let mut bs = get_bitstream("C0015000016115A2E0802F182340");
-        assert_eq!(23, sum_version(&parse_packet(&mut bs)));
+        let mut bs = get_bitstream("C0015000016115A2E0802F182340")?;
+        assert_eq!(23, sum_version(&parse_packet(&mut bs)?));
 
-        let mut bs = get_bitstream("A0016C880162017C3686B18A3D4780");
-        assert_eq!(31, sum_version(&parse_packet(&mut bs)));
+        let mut bs = get_bitstream("A0016C880162017C3686B18A3D4780")?;
+        assert_eq!(31, sum_version(&parse_packet(&mut bs)?));
 
-        let mut bs = get_bitstream("C200B40A82");
-        assert_eq!(3, parse_packet(&mut bs).val);
+        let mut bs = get_bitstream("C200B40A82")?;
+        assert_eq!(3, parse_packet(&mut bs)?.val);
 
-        let mut bs = get_bitstream("04005AC33890");
-        assert_eq!(54, parse_packet(&mut bs).val);
+        let mut bs = get_bitstream("04005AC33890")?;
+        assert_eq!(54, parse_packet(&mut bs)?.val);
 
-        let mut bs = get_bitstream("880086C3E88112");
-        assert_eq!(7, parse_packet(&mut bs).val);
+        let mut bs = get_bitstream("880086C3E88112")?;
+        assert_eq!(7, parse_packet(&mut bs)?.val);
 
-        let mut bs = get_bitstream("9C005AC2F8F0");
-        assert_eq!(0, parse_packet(&mut bs).val);
+        let mut bs = get_bitstream("9C005AC2F8F0")?;
+        assert_eq!(0, parse_packet(&mut bs)?.val);
 
-        let mut bs = get_bitstream("9C0141080250320F1802104A08");
-        assert_eq!(1, parse_packet(&mut bs).val);
+        let mut bs = get_bitstream("9C0141080250320F1802104A08")?;
+        assert_eq!(1, parse_packet(&mut bs)?.val);
         Ok(())
     }
 }
@@ -135,36 +135,63 @@
 //
 // Your puzzle answer was 5523.
 
-use std::collections::HashSet;
-use std::fs;
+use std::fmt::Display;
 use std::iter::repeat;
 
 use anyhow::Result;
-use itertools::Itertools;
+use num::rational::Ratio;
 use regex::Regex;
 
+use aoc::aabox::{AaBox, AaBoxSet};
+use aoc::error::{self, AocError};
+use aoc::geom::Vector2;
+use aoc::interval::Interval;
+use aoc::solution::{Entry, Solution};
+
+struct Day17;
+
+pub const SOLUTION: Entry = Entry::new::<Day17>();
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 17;
+
+    type Input = AaBox<i32, 2>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(target: &Self::Input) -> Result<impl Display> {
+        Ok(max_height(target.min.y()))
+    }
+
+    fn part2(target: &Self::Input) -> Result<impl Display> {
+        Ok(count_unique_vs(target))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day17")?;
-    let coords = parse(&input);
-    println!("{:?}", max_height(coords.2));
-    println!("{:?}", count_unique_vs(coords));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (f64, f64, f64, f64) {
+fn parse(s: &str) -> Result<AaBox<i32, 2>> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)$").unwrap();
     }
-    let caps = RE.captures(s.trim()).unwrap();
-    (caps[1].parse::<f64>().unwrap(),
-     caps[2].parse::<f64>().unwrap(),
-     caps[3].parse::<f64>().unwrap(),
-     caps[4].parse::<f64>().unwrap(), )
+    let caps = RE.captures(s.trim())
+        .ok_or_else(|| AocError::parse(s, s.trim(), "expected target area: x=a..b, y=c..d"))?;
+    let bound = |i: usize| error::parse_at::<i32>(s, caps.get(i).unwrap().as_str());
+    let (x1, x2, y1, y2) = (bound(1)?, bound(2)?, bound(3)?, bound(4)?);
+    if !(0 < x1 && x1 <= x2 && y1 <= y2 && y2 < 0) {
+        return Err(AocError::invalid("the target should be to the right of and below the launcher").into());
+    }
+    Ok(AaBox::inclusive(Vector2::new([x1, y1]), Vector2::new([x2, y2])))
 }
 
 // max v when t = 2v, y = 0 => v = -y
-fn max_height(sy_min: f64) -> i32 {
-    let max_vy = -((sy_min as i32) + 1);
+fn max_height(sy_min: i32) -> i32 {
+    let max_vy = -(sy_min + 1);
     (max_vy * (max_vy + 1)) / 2
 }
 
@@ -177,27 +204,30 @@ fn max_height(sy_min: f64) -> i32 {
 //   = (u + (v + 1))/2 * (u - (v + 1) + 1) (v offset 1)
 // s = (2u - t + 1)/2 * t (subst v)
 //   = (u + 0.5)t - 0.5(t^2)
-fn count_unique_vs(coords: (f64, f64, f64, f64)) -> usize {
-    fn get_v_min_max(t: i32, s_min: f64, s_max: f64) -> (i32, i32) {
-        let t = t as f64;
-        let v_min = (((2.0 * s_min) + t.powi(2) - t) / (2.0 * t)).ceil() as i32;
-        let v_max = (((2.0 * s_max) + t.powi(2) - t) / (2.0 * t)).floor() as i32;
-        (v_min, v_max)
+fn count_unique_vs(target: &AaBox<i32, 2>) -> usize {
+    // u = (2s + t^2 - t) / 2t
+    fn get_v_min_max(t: i32, s_min: i32, s_max: i32) -> (i32, i32) {
+        let u = |s: i32| {
+            let (s, t) = (s as i64, t as i64);
+            Ratio::new(2 * s + t * t - t, 2 * t)
+        };
+        (u(s_min).ceil().to_integer() as i32, u(s_max).floor().to_integer() as i32)
     }
 
-    let (sx_min, sx_max, sy_min, sy_max) = coords;
+    let [sx_min, sy_min] = target.min.0;
+    let [sx_max, sy_max] = target.max.0.map(|c| c - 1);
 
     let mut vys = vec![];
-    for t in 1..=(-(sy_min as i32) * 2) {
+    for t in 1..=(-sy_min * 2) {
         let (vy_min, vx_max) = get_v_min_max(t, sy_min, sy_max);
-        vys.push(vy_min..=vx_max);
+        vys.push(Interval::inclusive(vy_min, vx_max));
     }
 
     let (mut vx_min_anyt, mut vx_max_anyt) = (None, None);
     let mut vxs = vec![];
     for t in 1.. {
         let (vx_min, vx_max) = get_v_min_max(t, sx_min, sx_max);
-        vxs.push(vx_min..=vx_max);
+        vxs.push(Interval::inclusive(vx_min, vx_max));
 
         if t > vx_min && vx_min_anyt.is_none() {
             vx_min_anyt = Some(vx_min);
@@ -208,11 +238,14 @@ fn count_unique_vs(coords: (f64, f64, f64, f64)) -> usize {
         }
     }
 
-    vxs.into_iter().chain(repeat(vx_min_anyt.unwrap()..=vx_max_anyt.unwrap()))
-        .zip(vys.into_iter())
-        .flat_map(|(xs, ys)| xs.cartesian_product(ys))
-        .collect::<HashSet<_>>()
-        .len()
+    // each time step hits the target with a box of velocities, which overlap between steps
+    let mut vs = AaBoxSet::new();
+    for (xs, ys) in vxs.into_iter()
+        .chain(repeat(Interval::inclusive(vx_min_anyt.unwrap(), vx_max_anyt.unwrap())))
+        .zip(vys.into_iter()) {
+        vs.insert(AaBox::from_intervals([xs, ys]));
+    }
+    vs.volume() as usize
 }
 
 #[cfg(test)]
@@ -224,9 +257,9 @@ mod tests {
         let s = r"
         target area: x=20..30, y=-10..-5
         ";
-        let coords = parse(&s);
-        assert_eq!(45, max_height(coords.2));
-        assert_eq!(112, count_unique_vs(coords));
+        let target = parse(&s)?;
+        assert_eq!(45, max_height(target.min.y()));
+        assert_eq!(112, count_unique_vs(&target));
         Ok(())
     }
 }
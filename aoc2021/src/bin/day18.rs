@@ -191,13 +191,15 @@
 //
 // Your puzzle answer was 4600.
 
-use std::fs;
+use std::fmt::Display;
 use std::ops::Range;
 
 use anyhow::Result;
 use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
 // optimisation:
@@ -210,16 +212,66 @@ use aoc2021::TrimEmpty;
 // split will increase depth to maximum 4
 // [.. (_, max depth), (_, max depth) ..] is guaranteed to be a pair
 // leftmost pair = leftmost max_depth, max_depth will be <= 4
+struct Day18;
+
+pub const SOLUTION: Entry = Entry::new::<Day18>();
+
+impl Solution for Day18 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 18;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(v: &Self::Input) -> Result<impl Display> {
+        Ok(part1(v))
+    }
+
+    fn part2(v: &Self::Input) -> Result<impl Display> {
+        Ok(part2(v))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day18")?;
-    let v = parse(&input);
-    println!("{:?}", part1(&v));
-    println!("{:?}", part2(&v));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<String> {
-    s.lines().trim_empty().map(|s| s.to_string()).collect_vec()
+fn parse(s: &str) -> Result<Vec<String>> {
+    // the index past a digit or a pair at `i`, which is inside `depth` pairs
+    fn element(b: &[u8], i: usize, depth: usize) -> Result<usize, (usize, &'static str)> {
+        match b.get(i) {
+            Some(b'0'..=b'9') if depth > 0 => Ok(i + 1),
+            Some(b'[') if depth == 4 => Err((i, "expected a digit, pairs nest at most 4 deep")),
+            Some(b'[') => {
+                let i = element(b, i + 1, depth + 1)?;
+                if b.get(i) != Some(&b',') {
+                    return Err((i, "expected ,"));
+                }
+                let i = element(b, i + 1, depth + 1)?;
+                if b.get(i) != Some(&b']') {
+                    return Err((i, "expected ]"));
+                }
+                Ok(i + 1)
+            }
+            _ if depth == 0 => Err((i, "expected [")),
+            _ => Err((i, "expected a digit or [")),
+        }
+    }
+
+    let v = s.lines().trim_empty().map(|line| {
+        match element(line.as_bytes(), 0, 0) {
+            Ok(i) if i == line.len() => Ok(line.to_string()),
+            Ok(i) => Err(AocError::parse(s, &line[i..], "expected the end of the number")),
+            Err((i, message)) => Err(AocError::parse(s, &line[i..], message)),
+        }
+    }).collect::<Result<Vec<_>, AocError>>()?;
+    if v.is_empty() {
+        return Err(AocError::invalid("no snailfish numbers").into());
+    }
+    Ok(v)
 }
 
 lazy_static::lazy_static! {
@@ -333,7 +385,7 @@ mod tests {
         [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
         [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
         ";
-        let v = parse(s);
+        let v = parse(s)?;
         assert_eq!(4140, part1(&v));
         assert_eq!(3993, part2(&v));
         Ok(())
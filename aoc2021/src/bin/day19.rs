@@ -378,49 +378,74 @@
 // Your puzzle answer was 11985.
 
 use std::collections::HashSet;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
-use crate::geom::Vector3;
+use aoc::error::{self, AocError};
+use aoc::geom::Vector3;
+use aoc::orient::Rot3;
+use aoc::solution::{Entry, Solution};
+
+struct Day19;
+
+pub const SOLUTION: Entry = Entry::new::<Day19>();
+
+impl Solution for Day19 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 19;
+
+    type Input = Vec<(HashSet<Vector3<i32>>, Vector3<i32>)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        get_scanners(parse(input)?)
+    }
+
+    fn part1(scanners: &Self::Input) -> Result<impl Display> {
+        Ok(count_beacons(scanners))
+    }
+
+    fn part2(scanners: &Self::Input) -> Result<impl Display> {
+        Ok(max_distance(scanners))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day19")?;
-    let v = parse(&input);
-    let (len, md) = solve(v);
-    println!("{:?}", len);
-    println!("{:?}", md);
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<HashSet<Vector3>> {
-    s.trim().split("\n\n").map(|s|
-        s.lines().skip(1).map(|s| {
-            let mut nums = s.trim().split(',');
-            Vector3::new(
-                nums.next().unwrap().parse::<i32>().unwrap(),
-                nums.next().unwrap().parse::<i32>().unwrap(),
-                nums.next().unwrap().parse::<i32>().unwrap())
-        }).collect())
-        .collect_vec()
+fn parse(input: &str) -> Result<Vec<HashSet<Vector3<i32>>>> {
+    Ok(input.trim().split("\n\n").map(|s| {
+        let mut lines = s.lines();
+        let header = lines.next().unwrap_or(s).trim();
+        if !(header.starts_with("--- scanner ") && header.ends_with(" ---")) {
+            return Err(AocError::parse(input, header, "expected a header such as --- scanner 0 ---"));
+        }
+        lines.map(|s| {
+            let s = s.trim();
+            let (x, y, z) = s.split(',').collect_tuple()
+                .ok_or_else(|| AocError::parse(input, s, "expected a beacon such as 404,-588,-901"))?;
+            Ok(Vector3::new([error::parse_at(input, x)?, error::parse_at(input, y)?, error::parse_at(input, z)?]))
+        }).collect()
+    }).collect::<Result<_, AocError>>()?)
 }
 
-fn solve(v: Vec<HashSet<Vector3>>) -> (usize, i32) {
-    let res = get_scanners(v);
-    let hm = res.iter().flat_map(|(hm, p)|
+fn count_beacons(scanners: &[(HashSet<Vector3<i32>>, Vector3<i32>)]) -> usize {
+    scanners.iter().flat_map(|(hm, p)|
         hm.iter().map(|&offset| *p + offset))
-        .collect::<HashSet<_>>();
+        .collect::<HashSet<_>>()
+        .len()
+}
 
-    let max = res.iter().map(|(_, p)| *p).tuple_combinations()
+fn max_distance(scanners: &[(HashSet<Vector3<i32>>, Vector3<i32>)]) -> i32 {
+    scanners.iter().map(|(_, p)| *p).tuple_combinations()
         .map(|(p0, p1)| p0.manhattan_distance(p1))
-        .max().unwrap();
-
-    (hm.len(), max)
+        .max().unwrap_or(0)
 }
 
-fn get_scanners(mut v: Vec<HashSet<Vector3>>) -> Vec<(HashSet<Vector3>, Vector3)> {
-    let mut res = vec![(v.remove(0), Vector3::ZERO)];
+fn get_scanners(mut v: Vec<HashSet<Vector3<i32>>>) -> Result<Vec<(HashSet<Vector3<i32>>, Vector3<i32>)>> {
+    let mut res = vec![(v.remove(0), Vector3::zero())];
     let (mut start, mut end) = (0, 1);
 
     while !v.is_empty() {
@@ -436,51 +461,32 @@ fn get_scanners(mut v: Vec<HashSet<Vector3>>) -> Vec<(HashSet<Vector3>, Vector3)
                 }
             }
         }
+        if end == res.len() {
+            return Err(AocError::no_solution(format!("{} scanners don't overlap any other", v.len())).into());
+        }
         start = end;
         end = res.len();
     }
-    res
+    Ok(res)
 }
 
-fn check_intersecting_permute(hs0: &HashSet<Vector3>, hs1: &HashSet<Vector3>) -> Option<(HashSet<Vector3>, Vector3)> {
-    for hs in rotate_lookat(hs1.clone()) {
-        for mut hs in reverse_lookat(hs) {
-            for i in 0..4 {
-                if let Some(p) = check_intersecting(hs0, &hs) {
-                    return Some((hs, p));
-                }
-                if i == 3 { break; }
-                hs = rotate_origin(hs);
-            }
+fn check_intersecting_permute(hs0: &HashSet<Vector3<i32>>, hs1: &HashSet<Vector3<i32>>) -> Option<(HashSet<Vector3<i32>>, Vector3<i32>)> {
+    for rot in Rot3::all() {
+        if let Some(p) = check_intersecting(hs0, hs1, rot) {
+            let hs = hs1.iter().map(|p| p.rotate(rot)).collect();
+            return Some((hs, p));
         }
     }
     None
 }
 
-fn rotate_lookat(hs: HashSet<Vector3>) -> [HashSet<Vector3>; 3] {
-    [hs.iter().map(|&Vector3 { x, y, z }| Vector3::new(y, -x, z)).collect(),
-        hs.iter().map(|&Vector3 { x, y, z }| Vector3::new(z, y, -x)).collect(),
-        hs, ]
-}
-
-fn reverse_lookat(hs: HashSet<Vector3>) -> [HashSet<Vector3>; 2] {
-    [hs.iter().map(|&Vector3 { x, y, z }| Vector3::new(-x, y, -z)).collect(),
-        hs, ]
-}
-
-fn rotate_origin(hs: HashSet<Vector3>) -> HashSet<Vector3> {
-    hs.iter().map(|&Vector3 { x, y, z }| Vector3::new(x, -z, y)).collect()
-}
-
-fn check_intersecting(hs0: &HashSet<Vector3>, hs1: &HashSet<Vector3>) -> Option<Vector3> {
+fn check_intersecting(hs0: &HashSet<Vector3<i32>>, hs1: &HashSet<Vector3<i32>>, rot: Rot3) -> Option<Vector3<i32>> {
     for (&p0, &p1) in hs0.iter().cartesian_product(hs1.iter()) {
-        let s1 = p0 - p1;
+        let s1 = p0 - p1.rotate(rot);
         let mut count = 0;
         for p in hs1.iter()
-            .map(|&offset| s1 + offset)
-            .filter(|p| p.x.abs() <= 1000 &&
-                p.y.abs() <= 1000 &&
-                p.z.abs() <= 1000)
+            .map(|&offset| s1 + offset.rotate(rot))
+            .filter(|p| p.chebyshev() <= 1000)
         {
             match hs0.contains(&p) {
                 true => count += 1,
@@ -638,121 +644,10 @@ mod tests {
         -652,-548,-490
         30,-46,-14
         ";
-        let v = parse(s);
-        let (len, md) = solve(v);
-        assert_eq!(79, len);
-        assert_eq!(3621, md);
+        let v = parse(s)?;
+        let scanners = get_scanners(v)?;
+        assert_eq!(79, count_beacons(&scanners));
+        assert_eq!(3621, max_distance(&scanners));
         Ok(())
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector3 {
-        pub x: i32,
-        pub y: i32,
-        pub z: i32,
-    }
-
-    #[allow(dead_code)]
-    impl Vector3 {
-        pub const ZERO: Vector3 = Vector3 { x: 0, y: 0, z: 0 };
-
-        pub fn new<T>(x: T, y: T, z: T) -> Vector3
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            let z = NumCast::from::<T>(z).unwrap();
-            Vector3 { x, y, z }
-        }
-
-        pub fn manhattan_distance(&self, other: Vector3) -> i32 {
-            (self.x - other.x).abs() +
-                (self.y - other.y).abs() +
-                (self.z - other.z).abs()
-        }
-    }
-
-    impl Add for Vector3 {
-        type Output = Self;
-
-        fn add(self, other: Vector3) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-                z: self.z + other.z,
-            }
-        }
-    }
-
-    impl Sub for Vector3 {
-        type Output = Self;
-
-        fn sub(self, other: Vector3) -> Self {
-            Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-                z: self.z - other.z,
-            }
-        }
-    }
-
-    impl Mul<i32> for Vector3 {
-        type Output = Self;
-
-        fn mul(self, rhs: i32) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-                z: self.z * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector3 {
-        fn add_assign(&mut self, other: Vector3) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-                z: self.z + other.z,
-            };
-        }
-    }
-
-    impl SubAssign for Vector3 {
-        fn sub_assign(&mut self, other: Vector3) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-                z: self.z - other.z,
-            };
-        }
-    }
-
-    impl MulAssign for Vector3 {
-        fn mul_assign(&mut self, other: Vector3) {
-            *self = Self {
-                x: self.x * other.x,
-                y: self.y * other.y,
-                z: self.z * other.z,
-            };
-        }
-    }
-
-    impl Neg for Vector3 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector3 {
-                x: -self.x,
-                y: -self.y,
-                z: -self.z,
-            }
-        }
-    }
-}
@@ -64,29 +64,49 @@
 //
 // Your puzzle answer was 1813664422.
 
-use std::fs;
+use std::fmt::Display;
 use std::str::FromStr;
 
-use anyhow::{ensure, Result};
-use itertools::Itertools;
+use anyhow::Result;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day2")?;
-    let ops = parse(&input);
+struct Day2;
+
+pub const SOLUTION: Entry = Entry::new::<Day2>();
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 2;
+
+    type Input = Vec<Op>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
 
-    println!("{:?}", part1(&ops));
-    println!("{:?}", part2(&ops));
-    Ok(())
+    fn part1(ops: &Self::Input) -> Result<impl Display> {
+        Ok(part1(ops))
+    }
+
+    fn part2(ops: &Self::Input) -> Result<impl Display> {
+        Ok(part2(ops))
+    }
+}
+
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<Op> {
+fn parse(s: &str) -> Result<Vec<Op>> {
     s.lines()
         .trim_empty()
-        .map(|s| s.parse::<Op>().unwrap())
-        .collect_vec()
+        .map(|line| line.parse::<Op>().map_err(|e| e.within(s, line)))
+        .collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
 fn part1(ops: &Vec<Op>) -> i32 {
@@ -131,22 +151,21 @@ enum OpType {
 }
 
 impl FromStr for Op {
-    type Err = anyhow::Error;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static::lazy_static! {
             static ref RE: Regex = Regex::new(r"^(forward|down|up) (\d+)$").unwrap();
         }
-        let cap = RE.captures(s);
-        ensure!(cap.is_some(), "cannot parse op");
-        let cap = cap.unwrap();
+        let cap = RE.captures(s)
+            .ok_or_else(|| AocError::parse(s, s, "expected forward, down or up and a distance"))?;
         let op = match &cap[1] {
             "forward" => OpType::Forward,
             "down" => OpType::Down,
             "up" => OpType::Up,
             _ => unreachable!(),
         };
-        let arg = cap[2].parse::<i32>().unwrap();
+        let arg = error::parse_at(s, cap.get(2).unwrap().as_str())?;
         Ok(Op { op, arg })
     }
 }
@@ -165,7 +184,7 @@ mod tests {
         down 8
         forward 2
         ";
-        let ops = parse(s);
+        let ops = parse(s)?;
         assert_eq!(150, part1(&ops));
         assert_eq!(900, part2(&ops));
         Ok(())
@@ -117,30 +117,70 @@
 //
 // Your puzzle answer was 15088.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 use aoc2021::{Enumerate2D, TrimEmpty};
 
+struct Day20;
+
+pub const SOLUTION: Entry = Entry::new::<Day20>();
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 20;
+
+    type Input = (Vec<char>, Vec<Vec<char>>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((lookup, points): &Self::Input) -> Result<impl Display> {
+        Ok(evolve(lookup, points, 2))
+    }
+
+    fn part2((lookup, points): &Self::Input) -> Result<impl Display> {
+        Ok(evolve(lookup, points, 50))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day20")?;
-    let (lookup, points) = parse(&input);
-    println!("{:?}", evolve(&lookup, &points, 2));
-    println!("{:?}", evolve(&lookup, &points, 50));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (Vec<char>, Vec<Vec<char>>) {
-    let (lookup, input) = s.trim().split_once("\n\n").unwrap();
+fn parse(s: &str) -> Result<(Vec<char>, Vec<Vec<char>>)> {
+    let (lookup, input) = s.trim().split_once("\n\n")
+        .ok_or_else(|| AocError::invalid("expected a blank line between the algorithm and the image"))?;
+    for line in s.lines() {
+        if let Some(i) = line.find(|c: char| !matches!(c, '#' | '.') && !c.is_whitespace()) {
+            return Err(AocError::parse(s, &line[i..], "expected # or .").into());
+        }
+    }
     let lookup = lookup.chars().collect_vec();
-    assert_eq!(512, lookup.len());
+    if lookup.len() != 512 {
+        return Err(AocError::invalid(format!("expected an algorithm of 512 pixels, not {}", lookup.len())).into());
+    }
+    if lookup[0] == '#' && lookup[0b111111111] == '#' {
+        return Err(AocError::invalid("the infinite background would stay lit").into());
+    }
 
-    let points = input.lines().trim_empty()
+    let lines = input.lines().trim_empty().collect_vec();
+    let width = lines.first().map_or(0, |line| line.len());
+    if width == 0 {
+        return Err(AocError::invalid("empty image").into());
+    }
+    if let Some(line) = lines.iter().find(|line| line.len() != width) {
+        return Err(AocError::parse(s, line, format!("expected {} pixels like the first row", width)).into());
+    }
+    let points = lines.iter()
         .map(|line| line.chars().collect_vec())
         .collect_vec();
-    (lookup, points)
+    Ok((lookup, points))
 }
 
 fn evolve(lookup: &Vec<char>, points: &Vec<Vec<char>>, num_steps: usize) -> usize {
@@ -215,7 +255,7 @@ mod tests {
         ..#..
         ..###
         ";
-        let (lookup, points) = parse(&s);
+        let (lookup, points) = parse(&s)?;
         assert_eq!(35, evolve(&lookup, &points, 2));
         assert_eq!(3351, evolve(&lookup, &points, 50));
         Ok(())
@@ -57,16 +57,59 @@
 // Your puzzle answer was 116741133558209.
 
 use std::cmp::{max, min};
-use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 use std::iter::successors;
 
 use anyhow::Result;
+use num::{BigInt, Zero};
+
+use aoc::error::{self, AocError};
+use aoc::memo::Memo;
+use aoc::solution::{Entry, Solution};
+use aoc2021::TrimEmpty;
+
+struct Day21;
+
+pub const SOLUTION: Entry = Entry::new::<Day21>();
+
+impl Solution for Day21 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 21;
+
+    type Input = (i32, i32);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(&(p1, p2): &Self::Input) -> Result<impl Display> {
+        Ok(part1(p1, p2))
+    }
+
+    fn part2(&(p1, p2): &Self::Input) -> Result<impl Display> {
+        Ok(part2(p1, p2))
+    }
+}
 
 fn main() -> Result<()> {
-    println!("{:?}", part1(8, 1));
-    println!("{:?}", part2(8, 1));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
+}
+
+fn parse(s: &str) -> Result<(i32, i32)> {
+    let pos = s.lines().trim_empty()
+        .map(|line| {
+            let (_, pos) = line.rsplit_once(": ")
+                .ok_or_else(|| AocError::parse(s, line, "expected Player 1 starting position: 4"))?;
+            match error::parse_at::<i32>(s, pos)? {
+                pos @ 1..=10 => Ok(pos),
+                _ => Err(AocError::parse(s, pos, "expected a position from 1 to 10")),
+            }
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+    if pos.len() != 2 {
+        return Err(AocError::invalid(format!("expected 2 starting positions, got {}", pos.len())).into());
+    }
+    Ok((pos[0], pos[1]))
 }
 
 fn part1(p1: i32, p2: i32) -> i32 {
@@ -88,36 +131,39 @@ fn part1(p1: i32, p2: i32) -> i32 {
     min(scores[0], scores[1]) * count
 }
 
-fn part2(p1: i32, p2: i32) -> u64 {
-    let (p1_win, p1_lose) = count_ways(p1);
-    let (p2_win, p2_lose) = count_ways(p2);
-
-    let p2_vs_p1_win = p2_win.iter().map(|(k, v)| v * p1_lose.get(k).unwrap_or(&0)).sum::<u64>();
-    let p1_vs_p2_win = p1_win.iter().map(|(k, v)| v * p2_lose.get(&(*k - 1)).unwrap_or(&0)).sum::<u64>();
-    max(p1_vs_p2_win, p2_vs_p1_win)
+fn part2(p1: i32, p2: i32) -> BigInt {
+    let (p1_wins, p2_wins) = dirac(p1, p2, 10, 21);
+    max(p1_wins, p2_wins)
 }
 
-fn count_ways(pos: i32) -> (HashMap<i32, u64>, HashMap<i32, u64>) {
-    let mut hm = HashMap::new(); // win at roll k
-    let mut hm2 = HashMap::new(); // did not win by roll k
-    count_ways_rec(0, pos, 0, 1, &mut hm, &mut hm2);
-    return (hm, hm2);
-
-    fn count_ways_rec(score: i32, pos: i32, num_rolls: i32, acc: u64,
-                      hm: &mut HashMap<i32, u64>,
-                      hm2: &mut HashMap<i32, u64>, )
-    {
-        if score >= 21 {
-            *hm.entry(num_rolls).or_insert(0) += acc;
-            return;
-        }
-        *hm2.entry(num_rolls).or_insert(0) += acc;
+///
+/// The universes in which each player wins with the quantum die, on a circular board of `size`
+/// spaces, playing to a score of `target`
+///
+/// There are at most `(size * target)^2` states between turns, each solved once.
+///
+fn dirac(p1: i32, p2: i32, size: i32, target: i32) -> (BigInt, BigInt) {
+    // the totals of three rolls of the die, and the universes giving each
+    const ROLLS: [(i32, u32); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
 
-        for (die, ways) in (3..=9).zip([1, 3, 6, 7, 6, 3, 1]) {
-            let npos = (((pos + die) - 1) % 10) + 1;
-            count_ways_rec(score + npos, npos, num_rolls + 1, ways * acc, hm, hm2);
+    // wins for the player about to move and for the other one, from their positions and scores
+    type State = (i32, i32, i32, i32);
+    let mut memo = Memo::new();
+    memo.solve((p1, 0, p2, 0), &|&(pos, score, other_pos, other_score): &State, wins: &mut dyn FnMut(State) -> (BigInt, BigInt)| {
+        let (mut mine, mut theirs) = (BigInt::zero(), BigInt::zero());
+        for (die, ways) in ROLLS {
+            let ways = BigInt::from(ways);
+            let npos = (((pos + die) - 1) % size) + 1;
+            if score + npos >= target {
+                mine = mine + ways;
+            } else {
+                let (other_wins, my_wins) = wins((other_pos, other_score, npos, score + npos));
+                mine = mine + &ways * my_wins;
+                theirs = theirs + ways * other_wins;
+            }
         }
-    }
+        (mine, theirs)
+    })
 }
 
 #[cfg(test)]
@@ -127,7 +173,16 @@ mod tests {
     #[test]
     fn test() -> Result<()> {
         assert_eq!(739785, part1(4, 8));
-        assert_eq!(444356092776315, part2(4, 8));
+        assert_eq!(BigInt::from(444356092776315_u64), part2(4, 8));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dirac() -> Result<()> {
+        let wins = |w1: &str, w2: &str| -> Result<_> { Ok((w1.parse::<BigInt>()?, w2.parse::<BigInt>()?)) };
+        assert_eq!(wins("444356092776315", "341960390180808")?, dirac(4, 8, 10, 21));
+        assert_eq!(wins("8728703493592547358", "5721227663624596647")?, dirac(1, 1, 3, 10));
+        assert_eq!(wins("1018661801859383088274507", "867509340587775712834928")?, dirac(4, 8, 10, 35));
         Ok(())
     }
 }
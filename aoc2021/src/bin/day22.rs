@@ -187,24 +187,46 @@
 //
 // Your puzzle answer was 1235484513229032.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 use regex::Regex;
 
+use aoc::aabox::{AaBox, AaBoxSet, CompressedBoxSet};
+use aoc::error::{self, AocError};
+use aoc::geom::Vector3;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day22;
+
+pub const SOLUTION: Entry = Entry::new::<Day22>();
+
+impl Solution for Day22 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 22;
+
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(steps: &Self::Input) -> Result<impl Display> {
+        Ok(part1(steps))
+    }
+
+    fn part2(steps: &Self::Input) -> Result<impl Display> {
+        Ok(part2(steps))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day22")?;
-    let steps = parse(&input);
-    println!("{:?}", part1(&steps));
-    println!("{:?}", part2(&steps));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-type Cuboid = [i64; 6];
-type Bounds = ([i64; 2], Vec<[i64; 2]>);
+type Cuboid = AaBox<i64, 3>;
 
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
 struct Step {
@@ -212,123 +234,57 @@ struct Step {
     cuboid: Cuboid,
 }
 
-fn parse(s: &str) -> Vec<Step> {
+fn parse(s: &str) -> Result<Vec<Step>> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^(on|off) x=(-?\d+)..(-?\d+),y=(-?\d+)..(-?\d+),z=(-?\d+)..(-?\d+)$").unwrap();
     }
     s.lines().trim_empty().map(|line| {
-        let caps = RE.captures(line).unwrap();
+        let caps = RE.captures(line)
+            .ok_or_else(|| AocError::parse(s, line, "expected a step such as on x=10..12,y=10..12,z=10..12"))?;
         let on = match &caps[1] {
             "on" => true,
             "off" => false,
             _ => unreachable!()
         };
-        let cuboid = (2..8)
-            .map(|i| caps[i].parse::<i64>().unwrap())
-            .collect_vec().try_into().unwrap();
-        Step { on, cuboid }
-    }).collect_vec()
+        let bound = |i: usize| error::parse_at::<i64>(s, caps.get(i).unwrap().as_str());
+        let min = [bound(2)?, bound(4)?, bound(6)?];
+        let max = [bound(3)?, bound(5)?, bound(7)?];
+        if min.iter().zip(max.iter()).any(|(lo, hi)| lo > hi) {
+            return Err(AocError::parse(s, line, "expected each range to go from low to high"));
+        }
+        let cuboid = Cuboid::inclusive(Vector3::new(min), Vector3::new(max));
+        Ok(Step { on, cuboid })
+    }).collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
+// few enough cubes in the region for a compressed grid
 fn part1(steps: &Vec<Step>) -> i64 {
+    let region = Cuboid::inclusive(Vector3::new([-50; 3]), Vector3::new([50; 3]));
     let steps = steps.iter()
-        .filter(|s| s.cuboid.iter().all(|i| (-50..=50).contains(i)))
-        .cloned().collect_vec();
-    volume(regions(steps))
-}
-
-fn part2(steps: &Vec<Step>) -> i64 {
-    volume(regions(steps.clone()))
-}
-
-fn regions(steps: Vec<Step>) -> Vec<Cuboid> {
-    let mut regions = vec![];
+        .filter(|s| s.cuboid.intersection(&region) == Some(s.cuboid))
+        .collect_vec();
+    let mut cubes = CompressedBoxSet::new(steps.iter().map(|s| &s.cuboid));
     for step in steps {
         if step.on {
-            let mut to_merge = vec![step.cuboid];
-            for region in regions.iter() {
-                merge(&mut to_merge, region);
-            }
-            regions.extend(to_merge);
+            cubes.insert(&step.cuboid);
         } else {
-            merge(&mut regions, &step.cuboid);
+            cubes.remove(&step.cuboid);
         }
     }
-    regions
+    cubes.volume()
 }
 
-fn merge(froms: &mut Vec<Cuboid>, to: &Cuboid) {
-    for i in (0..froms.len()).rev() {
-        let merged = subtract(froms.swap_remove(i), to);
-        froms.extend(merged);
-    }
-}
-
-fn subtract(mut from: Cuboid, to: &Cuboid) -> Vec<Cuboid> {
-    fn get_bounds(from: &Cuboid, to: &Cuboid) -> Option<(Bounds, Bounds, Bounds)> {
-        let x_bounds = intersect_bounds(from[0], from[1], to[0], to[1])?;
-        let y_bounds = intersect_bounds(from[2], from[3], to[2], to[3])?;
-        let z_bounds = intersect_bounds(from[4], from[5], to[4], to[5])?;
-        Some((x_bounds, y_bounds, z_bounds))
-    }
-    match get_bounds(&from, to) {
-        None => vec![from],
-        Some((x_bounds, y_bounds, z_bounds)) => {
-            let mut res = vec![];
-            res.extend(x_bounds.1.iter().map(|bounds| {
-                let mut a = from.clone();
-                a[0..2].copy_from_slice(bounds);
-                a
-            }));
-            from[0..2].copy_from_slice(&x_bounds.0);
-
-            res.extend(y_bounds.1.iter().map(|bounds| {
-                let mut a = from.clone();
-                a[2..4].copy_from_slice(bounds);
-                a
-            }));
-            from[2..4].copy_from_slice(&y_bounds.0);
-
-            res.extend(z_bounds.1.iter().map(|bounds| {
-                let mut a = from.clone();
-                a[4..6].copy_from_slice(bounds);
-                a
-            }));
-            res
-        }
-    }
-}
-
-fn intersect_bounds(from_l: i64, from_r: i64, to_l: i64, to_r: i64) -> Option<Bounds> {
-    assert!(from_l <= from_r);
-    assert!(to_l <= to_r);
-    enum RangeOrdering {
-        Less,
-        Within,
-        Greater,
-    }
-    fn range_cmp(i: i64, l: i64, r: i64) -> RangeOrdering {
-        match (i < l, i <= r) {
-            (true, _) => RangeOrdering::Less,
-            (false, true) => RangeOrdering::Within,
-            (_, false) => RangeOrdering::Greater,
+fn part2(steps: &Vec<Step>) -> i64 {
+    let mut cubes = AaBoxSet::new();
+    for step in steps {
+        if step.on {
+            cubes.insert(step.cuboid);
+        } else {
+            cubes.remove(step.cuboid);
         }
     }
-    match (range_cmp(from_l, to_l, to_r), range_cmp(from_r, to_l, to_r)) {
-        (RangeOrdering::Less, RangeOrdering::Less) => None,
-        (RangeOrdering::Less, RangeOrdering::Within) => Some(([to_l, from_r], vec![[from_l, to_l - 1]])),
-        (RangeOrdering::Less, RangeOrdering::Greater) => Some(([to_l, to_r], vec![[from_l, to_l - 1], [to_r + 1, from_r]])),
-        (RangeOrdering::Within, RangeOrdering::Within) => Some(([from_l, from_r], vec![])),
-        (RangeOrdering::Within, RangeOrdering::Greater) => Some(([from_l, to_r], vec![[to_r + 1, from_r]])),
-        (RangeOrdering::Greater, RangeOrdering::Greater) => None,
-        (_, _) => unreachable!(),
-    }
-}
-
-fn volume(regions: Vec<Cuboid>) -> i64 {
-    regions.iter()
-        .map(|c| (c[1] - c[0] + 1) * (c[3] - c[2] + 1) * (c[5] - c[4] + 1))
-        .sum::<i64>()
+    cubes.volume()
 }
 
 #[cfg(test)]
@@ -361,7 +317,7 @@ mod tests {
         on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
         on x=967..23432,y=45373..81175,z=27513..53682
         ";
-        let steps = parse(&s);
+        let steps = parse(&s)?;
         assert_eq!(590784, part1(&steps));
 
         let s = r"
@@ -426,7 +382,7 @@ mod tests {
         on x=-53470..21291,y=-120233..-33476,z=-44150..38147
         off x=-93533..-4276,y=-16170..68771,z=-104985..-24507
         ";
-        let steps = parse(&s);
+        let steps = parse(&s)?;
         assert_eq!(2758514936282235, part2(&steps));
         Ok(())
     }
@@ -74,27 +74,57 @@
 // Your puzzle answer was 2555739.
 
 use std::cmp::Ordering;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day3;
+
+pub const SOLUTION: Entry = Entry::new::<Day3>();
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<impl Display> {
+        Ok(part1(nums))
+    }
+
+    fn part2(nums: &Self::Input) -> Result<impl Display> {
+        Ok(part2(nums))
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day3")?;
-    let nums = parse(&input);
-    println!("{:?}", part1(&nums));
-    println!("{:?}", part2(&nums));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<Vec<u8>> {
-    let nums = s.lines()
-        .trim_empty()
-        .map(|s| s.as_bytes().to_owned())
-        .collect_vec();
-    nums
+fn parse(s: &str) -> Result<Vec<Vec<u8>>> {
+    let lines = s.lines().trim_empty().collect_vec();
+    let width = match lines.first() {
+        Some(line) => line.len(),
+        None => return Err(AocError::invalid("no numbers").into()),
+    };
+    for line in &lines {
+        if let Some(i) = line.find(|c| c != '0' && c != '1') {
+            return Err(AocError::parse(s, &line[i..], "expected 0 or 1").into());
+        }
+        if line.len() != width {
+            return Err(AocError::parse(s, line, format!("expected {} bits like the first number", width)).into());
+        }
+    }
+    Ok(lines.iter().map(|line| line.as_bytes().to_owned()).collect())
 }
 
 fn part1(nums: &Vec<Vec<u8>>) -> u32 {
@@ -180,7 +210,7 @@ mod tests {
         00010
         01010
         ";
-        let nums = parse(s);
+        let nums = parse(s)?;
         assert_eq!(198, part1(&nums));
         assert_eq!(230, part2(&nums));
         Ok(())
@@ -74,37 +74,66 @@
 // Your puzzle answer was 7686.
 
 use std::collections::{HashMap, HashSet};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::{self, AocError};
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day4;
+
+pub const SOLUTION: Entry = Entry::new::<Day4>();
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 4;
+
+    type Input = (Vec<u8>, Vec<Board>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((nums, boards): &Self::Input) -> Result<impl Display> {
+        let (first, _) = solve(nums, &mut boards.clone()).ok_or_else(|| AocError::no_solution("no board wins"))?;
+        Ok(first)
+    }
+
+    fn part2((nums, boards): &Self::Input) -> Result<impl Display> {
+        let (_, last) = solve(nums, &mut boards.clone()).ok_or_else(|| AocError::no_solution("no board wins"))?;
+        Ok(last)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day4")?;
-    let (nums, mut boards) = parse(&input);
-    println!("{:?}", solve(&nums, &mut boards));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> (Vec<u8>, Vec<Board>) {
+fn parse(s: &str) -> Result<(Vec<u8>, Vec<Board>)> {
     let mut iter = s.split("\n\n");
-    let nums = iter.next().unwrap()
+    let nums = iter.next().unwrap_or_default()
         .split(',').trim_empty()
-        .map(|s| s.parse::<u8>().unwrap())
-        .collect_vec();
+        .map(|num| error::parse_at(s, num))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let boards = iter.map(|vals|
-        Board::new(vals.split(char::is_whitespace).trim_empty()
+    let mut boards = Vec::new();
+    for vals in iter.filter(|vals| !vals.trim().is_empty()) {
+        let vals = vals.split(char::is_whitespace).trim_empty()
             .enumerate()
-            .map(|(idx, val)| (val.parse::<u8>().unwrap(), idx))
-            .collect()))
-        .collect_vec();
-    (nums, boards)
+            .map(|(idx, val)| Ok((error::parse_at(s, val)?, idx)))
+            .collect::<Result<HashMap<_, _>, AocError>>()?;
+        if vals.len() != 25 {
+            return Err(AocError::invalid(format!("board {} isn't 25 different numbers", boards.len() + 1)).into());
+        }
+        boards.push(Board::new(vals));
+    }
+    Ok((nums, boards))
 }
 
-fn solve(nums: &Vec<u8>, boards: &mut Vec<Board>) -> (u32, u32) {
+fn solve(nums: &Vec<u8>, boards: &mut Vec<Board>) -> Option<(u32, u32)> {
     let mut first = None;
     let mut prev = None;
 
@@ -122,7 +151,7 @@ fn solve(nums: &Vec<u8>, boards: &mut Vec<Board>) -> (u32, u32) {
             }
         }
     }
-    (first.unwrap(), prev.unwrap())
+    first.zip(prev)
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -198,8 +227,8 @@ mod tests {
         22 11 13  6  5
          2  0 12  3  7
         ";
-        let (nums, mut boards) = parse(s);
-        assert_eq!((4512, 1924), solve(&nums, &mut boards));
+        let (nums, mut boards) = parse(s)?;
+        assert_eq!(Some((4512, 1924)), solve(&nums, &mut boards));
         Ok(())
     }
 }
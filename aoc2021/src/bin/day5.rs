@@ -73,38 +73,56 @@
 // Your puzzle answer was 20012.
 
 use std::collections::HashMap;
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 use regex::Regex;
 
+use aoc::error::{self, AocError};
+use aoc::geom::Vector2;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
-use crate::geom::Vector2;
+
+struct Day5;
+
+pub const SOLUTION: Entry = Entry::new::<Day5>();
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 5;
+
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<impl Display> {
+        Ok(solve(lines, false))
+    }
+
+    fn part2(lines: &Self::Input) -> Result<impl Display> {
+        Ok(solve(lines, true))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day5")?;
-    let lines = parse(&input);
-    println!("{:?}", solve(&lines, false));
-    println!("{:?}", solve(&lines, true));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<Line> {
+fn parse(s: &str) -> Result<Vec<Line>> {
     lazy_static::lazy_static! {
         static ref RE: Regex = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
     }
     s.lines().trim_empty().map(|line| {
-        let caps = RE.captures(line).unwrap();
-        let p1 = Vector2::new(
-            caps[1].parse::<u32>().unwrap(),
-            caps[2].parse::<u32>().unwrap());
-        let p2 = Vector2::new(
-            caps[3].parse::<u32>().unwrap(),
-            caps[4].parse::<u32>().unwrap());
-        Line { p1, p2 }
-    }).collect_vec()
+        let caps = RE.captures(line).ok_or_else(|| AocError::parse(s, line, "expected x1,y1 -> x2,y2"))?;
+        let num = |i| error::parse_at::<u32>(s, caps.get(i).unwrap().as_str());
+        let p1 = Vector2::new([num(1)?, num(2)?]).map(i64::from);
+        let p2 = Vector2::new([num(3)?, num(4)?]).map(i64::from);
+        Ok(Line { p1, p2 })
+    }).collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
 fn solve(lines: &Vec<Line>, do_diagonal: bool) -> usize {
@@ -116,7 +134,7 @@ fn solve(lines: &Vec<Line>, do_diagonal: bool) -> usize {
             (do_diagonal && line.is_diagonal())
         {
             let p1p2 = line.p2 - line.p1;
-            let dxdy = Vector2::new(p1p2.x.signum(), p1p2.y.signum());
+            let dxdy = p1p2.signum();
 
             let mut p = line.p1;
             while p != (line.p2 + dxdy) {
@@ -136,16 +154,16 @@ struct Line {
 
 impl Line {
     fn is_horizontal(&self) -> bool {
-        self.p1.y == self.p2.y
+        self.p1.y() == self.p2.y()
     }
 
     fn is_vertical(&self) -> bool {
-        self.p1.x == self.p2.x
+        self.p1.x() == self.p2.x()
     }
 
     fn is_diagonal(&self) -> bool {
-        (self.p1.x - self.p2.x).abs() ==
-            (self.p1.y - self.p2.y).abs()
+        (self.p1.x() - self.p2.x()).abs() ==
+            (self.p1.y() - self.p2.y()).abs()
     }
 }
 
@@ -167,101 +185,9 @@ mod tests {
         0,0 -> 8,8
         5,5 -> 8,2
         ";
-        let lines = parse(s);
+        let lines = parse(s)?;
         assert_eq!(5, solve(&lines, false));
         assert_eq!(12, solve(&lines, true));
         Ok(())
     }
 }
-
-mod geom {
-    use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
-
-    use num::{NumCast, ToPrimitive};
-
-    #[derive(Eq, PartialEq, Hash, Copy, Clone, Default, Debug)]
-    pub struct Vector2 {
-        pub x: i64,
-        pub y: i64,
-    }
-
-    impl Vector2 {
-        #[allow(dead_code)]
-        pub const ZERO: Vector2 = Vector2 { x: 0, y: 0 };
-
-        pub fn new<T>(x: T, y: T) -> Vector2
-            where T: ToPrimitive
-        {
-            let x = NumCast::from::<T>(x).unwrap();
-            let y = NumCast::from::<T>(y).unwrap();
-            Vector2 { x, y }
-        }
-
-        #[allow(dead_code)]
-        pub fn manhattan_distance(&self, other: Vector2) -> i64 {
-            (self.y - other.y).abs() + (self.x - other.x).abs()
-        }
-    }
-
-    impl Add for Vector2 {
-        type Output = Self;
-
-        fn add(self, other: Vector2) -> Self {
-            Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            }
-        }
-    }
-
-    impl Sub for Vector2 {
-        type Output = Self;
-
-        fn sub(self, other: Vector2) -> Self {
-            Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            }
-        }
-    }
-
-    impl Mul<i64> for Vector2 {
-        type Output = Self;
-
-        fn mul(self, rhs: i64) -> Self::Output {
-            Self {
-                x: self.x * rhs,
-                y: self.y * rhs,
-            }
-        }
-    }
-
-    impl AddAssign for Vector2 {
-        fn add_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x + other.x,
-                y: self.y + other.y,
-            };
-        }
-    }
-
-    impl SubAssign for Vector2 {
-        fn sub_assign(&mut self, other: Vector2) {
-            *self = Self {
-                x: self.x - other.x,
-                y: self.y - other.y,
-            };
-        }
-    }
-
-    impl Neg for Vector2 {
-        type Output = Self;
-
-        fn neg(self) -> Self::Output {
-            Vector2 {
-                x: -self.x,
-                y: -self.y,
-            }
-        }
-    }
-}
\ No newline at end of file
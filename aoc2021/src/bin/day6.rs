@@ -65,35 +65,65 @@
 //
 // Your puzzle answer was 1590327954513.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 
+use aoc::error::{self, AocError};
+use aoc::matrix::Matrix;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day6")?;
-    let fish = parse(&input);
-    println!("{:?}", evolve(&fish, 80));
-    println!("{:?}", evolve(&fish, 256));
-    Ok(())
+struct Day6;
+
+pub const SOLUTION: Entry = Entry::new::<Day6>();
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 6;
+
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(fish: &Self::Input) -> Result<impl Display> {
+        evolve(fish, 80)
+    }
+
+    fn part2(fish: &Self::Input) -> Result<impl Display> {
+        evolve(fish, 256)
+    }
 }
 
-fn parse(s: &str) -> Vec<u64> {
-    s.split(',').trim_empty()
-        .fold(vec![0; 9], |mut v, num| {
-            v[num.parse::<usize>().unwrap()] += 1;
-            v
-        })
+fn main() -> Result<()> {
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn evolve(fish: &Vec<u64>, days: u32) -> u64 {
-    let mut fish = fish.clone();
-    for _ in 0..days {
-        fish.rotate_left(1);
-        fish[6] += fish[8];
+fn parse(s: &str) -> Result<Vec<u64>> {
+    let mut v = vec![0; 9];
+    for num in s.split(',').trim_empty() {
+        match error::parse_at::<usize>(s, num)? {
+            timer @ 0..=8 => v[timer] += 1,
+            _ => return Err(AocError::parse(s, num, "expected a timer from 0 to 8").into()),
+        }
     }
-    fish.iter().sum()
+    Ok(v)
+}
+
+///
+/// Each day a fish's timer counts down, and one at 0 resets to 6 and spawns a new fish at 8
+///
+fn step() -> Matrix<u64> {
+    Matrix::transitions(9, (1..9).map(|timer| (timer, timer - 1)).chain([(0, 6), (0, 8)]))
+}
+
+fn evolve(fish: &[u64], days: u64) -> Result<u64> {
+    step().checked_pow(days)
+        .and_then(|m| m.checked_mul_vec(fish))
+        .and_then(|fish| fish.iter().try_fold(0_u64, |acc, &n| acc.checked_add(n)))
+        .ok_or_else(|| AocError::invalid(format!("too many fish to count after {} days", days)).into())
 }
 
 #[cfg(test)]
@@ -105,10 +135,11 @@ mod tests {
         let s = r"
         3,4,3,1,2
         ";
-        let fish = parse(s);
-        assert_eq!(26, evolve(&fish, 18));
-        assert_eq!(5934, evolve(&fish, 80));
-        assert_eq!(26984457539, evolve(&fish, 256));
+        let fish = parse(s)?;
+        assert_eq!(26, evolve(&fish, 18)?);
+        assert_eq!(5934, evolve(&fish, 80)?);
+        assert_eq!(26984457539, evolve(&fish, 256)?);
+        assert!(evolve(&fish, 1_000_000_000_000).is_err());
         Ok(())
     }
 }
@@ -61,25 +61,42 @@
 //
 // Your puzzle answer was 99634572.
 
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
-use itertools::Itertools;
 
-use aoc2021::TrimEmpty;
+use aoc::parse;
+use aoc::solution::{Entry, Solution};
+
+struct Day7;
+
+pub const SOLUTION: Entry = Entry::new::<Day7>();
+
+impl Solution for Day7 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 7;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(crabs: &Self::Input) -> Result<impl Display> {
+        Ok(solve(crabs, linear_cost))
+    }
+
+    fn part2(crabs: &Self::Input) -> Result<impl Display> {
+        Ok(solve(crabs, sum_cost))
+    }
+}
 
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day7")?;
-    let crabs = parse(&input);
-    println!("{:?}", solve(&crabs, linear_cost));
-    println!("{:?}", solve(&crabs, sum_cost));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<i32> {
-    s.split(',').trim_empty()
-        .map(|s| s.parse::<i32>().unwrap())
-        .collect_vec()
+fn parse(s: &str) -> Result<Vec<i32>> {
+    Ok(parse::ints(s)?)
 }
 
 fn solve<F>(crabs: &Vec<i32>, cost_fn: F) -> i32
@@ -114,7 +131,7 @@ mod tests {
         let s = r"
         16,1,2,0,4,2,7,1,2,14
         ";
-        let crabs = parse(s);
+        let crabs = parse(s)?;
         assert_eq!(37, solve(&crabs, linear_cost));
         assert_eq!(168, solve(&crabs, sum_cost));
         Ok(())
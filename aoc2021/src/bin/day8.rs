@@ -132,29 +132,64 @@
 // Your puzzle answer was 982158.
 
 use std::collections::{BTreeSet, HashMap};
-use std::fs;
+use std::fmt::Display;
 
 use anyhow::Result;
 use itertools::Itertools;
 
+use aoc::error::AocError;
+use aoc::solution::{Entry, Solution};
 use aoc2021::TrimEmpty;
 
+struct Day8;
+
+pub const SOLUTION: Entry = Entry::new::<Day8>();
+
+impl Solution for Day8 {
+    const YEAR: u16 = 2021;
+    const DAY: u8 = 8;
+
+    type Input = Vec<(Vec<BTreeSet<u8>>, Vec<BTreeSet<u8>>)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(patterns: &Self::Input) -> Result<impl Display> {
+        Ok(solve(patterns).0)
+    }
+
+    fn part2(patterns: &Self::Input) -> Result<impl Display> {
+        Ok(solve(patterns).1)
+    }
+}
+
 fn main() -> Result<()> {
-    let input = fs::read_to_string("input/aoc2021/day8")?;
-    let patterns = parse(&input);
-    println!("{:?}", solve(&patterns));
-    Ok(())
+    aoc::runner::main(&[SOLUTION])
 }
 
-fn parse(s: &str) -> Vec<(Vec<BTreeSet<u8>>, Vec<BTreeSet<u8>>)> {
-    s.lines().trim_empty().map(|line|
-        line.split('|').map(|nums|
+fn parse(s: &str) -> Result<Vec<(Vec<BTreeSet<u8>>, Vec<BTreeSet<u8>>)>> {
+    s.lines().trim_empty().map(|line| {
+        if let Some(i) = line.find(|c: char| !matches!(c, 'a'..='g' | '|' | ' ')) {
+            return Err(AocError::parse(s, &line[i..], "expected a segment from a to g"));
+        }
+        let (lhs, rhs) = line.split('|').map(|nums|
             nums.split(' ').trim_empty()
                 .map(|num|
                     num.as_bytes().iter().copied().collect::<BTreeSet<_>>())
                 .collect_vec())
-            .collect_tuple::<(_, _)>().unwrap()
-    ).collect_vec()
+            .collect_tuple::<(_, _)>()
+            .ok_or_else(|| AocError::parse(s, line, "expected patterns | output"))?;
+        let lens = lhs.iter().map(BTreeSet::len).sorted().collect_vec();
+        if lens != [2, 3, 4, 5, 5, 5, 6, 6, 6, 7] {
+            return Err(AocError::parse(s, line, "expected the patterns of the 10 digits"));
+        }
+        if rhs.len() != 4 || rhs.iter().any(|p| !lhs.contains(p)) {
+            return Err(AocError::parse(s, line, "expected 4 output digits from the patterns"));
+        }
+        Ok((lhs, rhs))
+    }).collect::<Result<_, AocError>>()
+        .map_err(Into::into)
 }
 
 fn solve(patterns: &Vec<(Vec<BTreeSet<u8>>, Vec<BTreeSet<u8>>)>) -> (usize, u32) {
@@ -228,7 +263,7 @@ mod tests {
         egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
         gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
         ";
-        let patterns = parse(s);
+        let patterns = parse(s)?;
         assert_eq!((26, 61229), solve(&patterns));
         Ok(())
     }
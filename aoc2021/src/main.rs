@@ -1,4 +1,8 @@
-fn main() {}
+use anyhow::Result;
+
+fn main() -> Result<()> {
+    aoc::runner::main(aoc2021::SOLUTIONS)
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,12 @@
+//!
+//! Runs the solutions of every year, e.g. `cargo run --release -p runner -- --year 2020 --day 13`
+//!
+
+use anyhow::Result;
+
+use aoc::solution::Entry;
+
+fn main() -> Result<()> {
+    let solutions: Vec<Entry> = [aoc2019::SOLUTIONS, aoc2020::SOLUTIONS, aoc2021::SOLUTIONS].concat();
+    aoc::runner::main(&solutions)
+}